@@ -0,0 +1,132 @@
+%==============================================================%
+% File:        float.m                                         %
+% Description: This file contains the floating point routines  %
+%              used by the generated code. MOON has no FPU, so %
+%              floats are fixed-point numbers with three       %
+%              decimal digits: a float is stored as its value  %
+%              times 1000 (e.g. 1.5 is stored as 1500).        %
+%              Addition, substraction and comparisons are the  %
+%              integer ones, multiplication and division need  %
+%              the routines below. Load with util.m.           %
+%==============================================================%
+%
+%
+%--------------------------------------------------------------%
+% fmul                                                         %
+%--------------------------------------------------------------%
+% Multiply two floats. Both operands are split into their
+% integer and decimal parts so the intermediate products don't
+% overflow: a * b = ah*bh*1000 + ah*bl + al*bh + al*bl/1000
+% Entry : r1 -> a, r2 -> b.
+% Exit : result -> r1
+%
+fmul	align
+	clt	r3,r1,r0		% a < 0 ?
+	bz	r3,fmul1		% Branch if False (a >= 0)
+	sub	r1,r0,r1		% a = -a
+fmul1	clt	r4,r2,r0		% b < 0 ?
+	bz	r4,fmul2		% Branch if False (b >= 0)
+	sub	r2,r0,r2		% b = -b
+fmul2	ceq	r3,r3,r4		% Result is positive if signs are equal
+	divi	r4,r1,1000		% ah
+	modi	r5,r1,1000		% al
+	divi	r6,r2,1000		% bh
+	modi	r7,r2,1000		% bl
+	mul	r1,r4,r6		% ah * bh
+	muli	r1,r1,1000
+	mul	r2,r4,r7		% ah * bl
+	add	r1,r1,r2
+	mul	r2,r5,r6		% al * bh
+	add	r1,r1,r2
+	mul	r2,r5,r7		% al * bl
+	divi	r2,r2,1000
+	add	r1,r1,r2
+	bnz	r3,fmul3		% Branch if result is positive
+	sub	r1,r0,r1		% Negate result
+fmul3	jr	r15			% Return to the caller
+%
+%
+%--------------------------------------------------------------%
+% fdiv                                                         %
+%--------------------------------------------------------------%
+% Divide two floats. a * 1000 / b would overflow, so the
+% quotient is computed by long division, one decimal digit at
+% a time.
+% Entry : r1 -> a, r2 -> b.
+% Exit : result -> r1
+%
+fdiv	align
+	clt	r3,r1,r0		% a < 0 ?
+	bz	r3,fdiv1		% Branch if False (a >= 0)
+	sub	r1,r0,r1		% a = -a
+fdiv1	clt	r4,r2,r0		% b < 0 ?
+	bz	r4,fdiv2		% Branch if False (b >= 0)
+	sub	r2,r0,r2		% b = -b
+fdiv2	ceq	r3,r3,r4		% Result is positive if signs are equal
+	div	r4,r1,r2		% Q = a / b
+	mod	r5,r1,r2		% R = a % b
+	addi	r6,r0,3			% 3 decimal digits
+fdiv3	muli	r5,r5,10		% R *= 10
+	muli	r4,r4,10		% Q *= 10
+	div	r7,r5,r2		% Next digit
+	add	r4,r4,r7		% Q += digit
+	mod	r5,r5,r2		% R %= b
+	subi	r6,r6,1
+	bnz	r6,fdiv3		% Loop if not finished
+	add	r1,r0,r4
+	bnz	r3,fdiv4		% Branch if result is positive
+	sub	r1,r0,r1		% Negate result
+fdiv4	jr	r15			% Return to the caller
+%
+%
+%--------------------------------------------------------------%
+% putfloat                                                     %
+%--------------------------------------------------------------%
+% Write a float to stdout: its integer part, a dot and its
+% decimal part without trailing zeros (at least one digit).
+% Entry : r1 -> float to be printed
+% Exit : none.
+%
+putfloat	align
+	clt	r3,r1,r0		% N < 0 ?
+	bz	r3,putfloat1		% Branch if False (N >= 0)
+	sub	r1,r0,r1		% N = -N
+	addi	r3,r0,45
+	putc	r3			% Write '-'
+putfloat1	divi	r4,r1,1000		% Integer part
+	modi	r5,r1,1000		% Decimal part
+	add	r2,r0,r0		% Initialize buffer's index i
+putfloat2	modi	r3,r4,10		% Rightmost digit
+	addi	r3,r3,48		% Convert to ch
+	sb	putfloat9(r2),r3	% Store ch in buffer
+	addi	r2,r2,1			% i++
+	divi	r4,r4,10		% Integer part /= 10
+	bnz	r4,putfloat2		% Loop if not finished
+putfloat3	subi	r2,r2,1			% i--
+	lb	r3,putfloat9(r2)	% Load ch from buffer
+	putc	r3			% Write ch
+	bnz	r2,putfloat3		% Loop if not finished
+	addi	r3,r0,46
+	putc	r3			% Write '.'
+	addi	r6,r0,3			% Number of decimal digits
+putfloat4	subi	r7,r6,1
+	bz	r7,putfloat5		% Keep at least one digit
+	modi	r7,r5,10
+	bnz	r7,putfloat5		% Branch if no trailing zero
+	divi	r5,r5,10		% Drop trailing zero
+	subi	r6,r6,1
+	j	putfloat4
+putfloat5	addi	r7,r0,1			% Divisor of the leftmost digit
+putfloat6	subi	r6,r6,1
+	bz	r6,putfloat7
+	muli	r7,r7,10
+	j	putfloat6
+putfloat7	div	r3,r5,r7		% Leftmost digit
+	addi	r3,r3,48		% Convert to ch
+	putc	r3			% Write ch
+	mod	r5,r5,r7
+	divi	r7,r7,10
+	bnz	r7,putfloat7		% Loop if not finished
+	jr	r15			% Return to the caller
+putfloat9	res	12			% Local buffer (12 bytes)
+	align
@@ -305,7 +305,7 @@ void showfmta(long addr, wordtype word) {
 	case mul:
 	case newdiv:
 	case mod:
-	caseand :
+	case and:
 	case or :
 	case ceq:
 	case cne:
@@ -665,7 +665,7 @@ void execinstr(short tracing) {
 			break;
 
 			/* and Ri, Rj, Rk  (32-bit logical AND) */
-		caseand :
+		case and:
 			storereg(ir.fmta.ri,
 				fetchreg(ir.fmta.rj) & fetchreg(ir.fmta.rk));
 			newreg = ir.fmta.ri;
@@ -1471,7 +1471,7 @@ void readline() {
 		case mul:
 		case newdiv:
 		case mod:
-		caseand :
+		case and:
 		case or :
 		case ceq:
 		case cne:
@@ -1,16 +1,26 @@
 use crate::codegen::instruction_set::Register;
 use crate::codegen::instruction_set::Register::*;
+use crate::codegen::utils::sizeof;
+use crate::semantics::symbol_table::{FunctionEntry, Scope, SymbolTable, Type};
 use std::collections::HashMap;
 
 #[derive(Default, Debug)]
 pub(crate) struct LabelAllocator {
     while_statement_count: u32,
+    for_statement_count: u32,
     if_statement_count: u32,
-    temp_resource_count: u32,
+    logical_expression_count: u32,
+    string_literal_count: u32,
+    switch_statement_count: u32,
+    bounds_check_count: u32,
+    /// continue targets of the enclosing loops, innermost last
+    continue_labels: Vec<String>,
+    /// break targets of the enclosing loops and switch statements, innermost last
+    break_labels: Vec<String>,
 }
 
 impl LabelAllocator {
-    pub fn current_while_labels(&self) -> (String, String) {
+    fn current_while_labels(&self) -> (String, String) {
         (
             format!("while_{}", self.while_statement_count),
             format!("endwhile_{}", self.while_statement_count),
@@ -22,13 +32,53 @@ impl LabelAllocator {
         self.current_while_labels()
     }
 
-    pub fn current_temp_label(&self) -> String {
-        format!("t{}", self.temp_resource_count)
+    fn current_for_labels(&self) -> (String, String, String) {
+        (
+            format!("for_{}", self.for_statement_count),
+            format!("forstep_{}", self.for_statement_count),
+            format!("endfor_{}", self.for_statement_count),
+        )
+    }
+
+    pub fn next_for_labels(&mut self) -> (String, String, String) {
+        self.for_statement_count += 1;
+        self.current_for_labels()
     }
 
-    pub fn next_temp_label(&mut self) -> String {
-        self.temp_resource_count += 1;
-        self.current_temp_label()
+    /// Makes the given continue and break targets the ones of the innermost loop
+    pub fn enter_loop(&mut self, continue_label: &str, break_label: &str) {
+        self.continue_labels.push(continue_label.to_string());
+        self.break_labels.push(break_label.to_string());
+    }
+
+    pub fn exit_loop(&mut self) {
+        self.continue_labels.pop();
+        self.break_labels.pop();
+    }
+
+    /// Makes the end of a switch statement the break target, continue still targets the enclosing loop
+    pub fn enter_switch(&mut self, break_label: &str) {
+        self.break_labels.push(break_label.to_string());
+    }
+
+    pub fn exit_switch(&mut self) {
+        self.break_labels.pop();
+    }
+
+    /// Returns the break target of the innermost enclosing loop or switch statement
+    pub fn current_break_label(&self) -> String {
+        self.break_labels
+            .last()
+            .cloned()
+            .expect("break outside of a loop or switch")
+    }
+
+    /// Returns the continue target of the innermost enclosing loop
+    pub fn current_continue_label(&self) -> String {
+        self.continue_labels
+            .last()
+            .cloned()
+            .expect("continue outside of a loop")
     }
 
     fn current_if_labels(&self) -> (String, String, String) {
@@ -43,6 +93,38 @@ impl LabelAllocator {
         self.if_statement_count += 1;
         self.current_if_labels()
     }
+
+    /// Returns the label the right operand of the next & or | expression is skipped to
+    pub fn next_logical_label(&mut self) -> String {
+        self.logical_expression_count += 1;
+        format!("endlogical_{}", self.logical_expression_count)
+    }
+
+    /// Returns the labels of the cases of the next switch statement, of its jump table and of its end
+    pub fn next_switch_labels(&mut self, cases: usize) -> (Vec<String>, String, String) {
+        self.switch_statement_count += 1;
+        let n = self.switch_statement_count;
+        (
+            (1..=cases).map(|i| format!("case_{}_{}", n, i)).collect(),
+            format!("jumptable_{}", n),
+            format!("endswitch_{}", n),
+        )
+    }
+
+    /// Returns the labels an array index check branches to when the index is out of bounds and when it is in bounds
+    pub fn next_bounds_check_labels(&mut self) -> (String, String) {
+        self.bounds_check_count += 1;
+        (
+            format!("outofbounds_{}", self.bounds_check_count),
+            format!("inbounds_{}", self.bounds_check_count),
+        )
+    }
+
+    /// Returns the label of the next string literal in the data section
+    pub fn next_string_label(&mut self) -> String {
+        self.string_literal_count += 1;
+        format!("str_{}", self.string_literal_count)
+    }
 }
 
 #[derive(Debug)]
@@ -78,16 +160,114 @@ impl RegisterAllocator {
         panic!("OUT OF REGISTERS")
     }
 
+    /// Returns the registers that are currently allocated
+    pub fn used_registers(&self) -> Vec<Register> {
+        let mut used: Vec<Register> = self
+            .0
+            .iter()
+            .filter(|(_, available)| !**available)
+            .map(|(reg, _)| *reg)
+            .collect();
+        used.sort_by_key(|reg| *reg as u8);
+        used
+    }
+
     pub fn release_register(&mut self, reg: Register) {
         match reg {
             R1 | R2 | R3 | R4 | R5 | R6 | R7 | R8 | R9 | R10 | R11 | R12 | R13 => {
                 let entry = self.0.entry(reg);
                 *entry.or_insert(true) = true;
             }
-            R0 => {}
-            R14 | R15 => {
-                panic!("tried to release R14 / R15")
+            // the frame pointer is used as the base of frame slots, it is never allocated
+            R0 | R14 => {}
+            R15 => {
+                panic!("tried to release R15")
+            }
+        }
+    }
+}
+
+/// The activation record of a function, addressed through the frame pointer (R14).
+///
+/// Frames grow downwards from `topaddr`, so every slot is at a negative offset from the frame pointer:
+/// the return address, the return value, the object pointer of member functions, the parameters,
+/// the local variables and finally the temporaries allocated while generating the function body.
+#[derive(Debug, Default)]
+pub(crate) struct Frame {
+    offsets: HashMap<String, i32>,
+    size: u32,
+    return_value: i32,
+    this: Option<i32>,
+}
+
+impl Frame {
+    /// Offset of the return address of a function
+    pub const RETURN_ADDRESS: i32 = -4;
+
+    /// Lays out the frame of a function
+    pub fn new(fe: &FunctionEntry, symbols: &SymbolTable) -> Self {
+        let mut frame = Self {
+            offsets: HashMap::new(),
+            size: 4,
+            return_value: 0,
+            this: None,
+        };
+        // objects are returned by value, so the return value can take more than a word
+        frame.return_value = frame.allocate(sizeof(&fe.type_sig().1, symbols).max(4));
+        if fe.member_of().is_some() {
+            frame.this = Some(frame.allocate(4));
+        }
+        for scope in fe.table().scopes() {
+            if let Scope::FunctionParameter(pe) = scope {
+                // arrays are passed as a pointer to their first element
+                let size = match pe.param_type() {
+                    Type::IntegerArray(_)
+                    | Type::FloatArray(_)
+                    | Type::BoolArray(_)
+                    | Type::StringArray(_)
+                    | Type::CustomArray(_, _)
+                    | Type::EnumArray(_, _) => 4,
+                    t => sizeof(t, symbols),
+                };
+                let offset = frame.allocate(size);
+                frame.offsets.insert(pe.ident().to_string(), offset);
+            }
+        }
+        for scope in fe.table().scopes() {
+            if let Scope::Variable(ve) = scope {
+                let offset = frame.allocate(sizeof(ve.var_type(), symbols));
+                frame.offsets.insert(ve.ident().to_string(), offset);
             }
         }
+        frame
+    }
+
+    /// Reserves a new slot of `bytes` bytes, returning its offset
+    pub fn allocate(&mut self, bytes: u32) -> i32 {
+        self.size += bytes;
+        -(self.size as i32)
+    }
+
+    /// Offset of a parameter or local variable
+    pub fn offset(&self, ident: &str) -> i32 {
+        *self
+            .offsets
+            .get(ident)
+            .unwrap_or_else(|| panic!("{} is not in the frame", ident))
+    }
+
+    /// Offset of the return value
+    pub fn return_value(&self) -> i32 {
+        self.return_value
+    }
+
+    /// Offset of the pointer to the object a member function was called on
+    pub fn this(&self) -> i32 {
+        self.this.expect("only member functions have an object pointer")
+    }
+
+    /// Size of the frame in bytes, including the temporaries allocated so far
+    pub fn size(&self) -> u32 {
+        self.size
     }
 }
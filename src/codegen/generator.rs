@@ -1,16 +1,25 @@
 #![allow(dead_code)]
-use crate::codegen::allocator::{LabelAllocator, RegisterAllocator};
+use crate::codegen::allocator::{Frame, LabelAllocator, RegisterAllocator};
 use crate::codegen::generator::ExprParseStorage::*;
-use crate::codegen::instruction_set::Instruction::{Add, AddImmediate, And, BranchIfZeroLabel, Divide, Entry, Equal, Greater, GreaterEqual, Halt, JumpLabel, JumpLinkLabel, JumpRegister, Less, LessEqual, LoadWordLabel, Multiply, NoOp, NotEqual, Or, Res, StoreWordLabel, Substract, SubstractImmediate, LoadWord, MultiplyImmediate, StoreWord, Align, Comment};
+use crate::codegen::instruction_set::Instruction::{Add, AddImmediate, BranchIfNonZeroLabel, BranchIfZeroLabel, Divide, Entry, Equal, EqualImmediate, Greater, GreaterEqual, Halt, JumpLabel, JumpLinkLabel, JumpRegister, Less, LessEqual, LoadWordLabel, Modulus, Multiply, NoOp, NotEqual, Res, StoreWordLabel, Substract, SubstractImmediate, LoadWord, MultiplyImmediate, StoreWord, Align, Comment, ShiftLeft, StoreByte, Db, Dw, JumpLinkRegister, LessImmediate, GreaterEqualImmediate, PutCharacter, DivideImmediate};
 use crate::codegen::instruction_set::Register;
 use crate::codegen::instruction_set::Register::*;
 use crate::codegen::instruction_set::{Instruction, TaggedInstruction};
 use crate::codegen::utils::{
-    generate_arith_expr_postfix, is_arith_operand, is_arith_operator, sizeof,
+    class_layout, data_member_offset, fixed_point, function_label, generate_arith_expr_postfix, global_variable_label, has_vtable,
+    is_arith_operand, is_arith_operator, is_unary_operator, sizeof, use_jump_table, vtable_label, vtable_pointers,
+    vtable_slot, FLOAT_SCALE, STRING_SIZE,
 };
 use crate::lexer::token::TokenType;
 use crate::parser::ast::{InternalNodeType, Node, NodeVal};
-use crate::semantics::symbol_table::{ClassEntry, FunctionEntry, Scope, SymbolTable, Type, ParameterEntry};
+use crate::semantics::evaluation::{evaluate_case_label, line_of};
+use crate::semantics::symbol_table::{ClassEntry, FunctionEntry, Scope, SymbolTable, Type, ParameterEntry, VariableEntry};
+use crate::semantics::utils::{find_constant, find_function_for_definition, find_variable, get_class_method_table};
+use crate::semantics::validation::{
+    function_candidates, member_function_candidates, promoted_type, resolve_function_call, validate_arith_expr,
+    validate_arith_operand, validate_dot_operand, validate_expr,
+};
+use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
@@ -69,6 +78,23 @@ impl CodeGenerator {
             .push(TaggedInstruction(Some(tag.to_string()), Res(bytes)));
     }
 
+    /// Adds a string literal to the data section, with the capacity of a string
+    pub fn add_string_resource(&mut self, tag: &str, text: &str) {
+        let mut bytes: Vec<u8> = text.bytes().take(STRING_SIZE as usize - 1).collect();
+        bytes.push(0);
+        let padding = STRING_SIZE - bytes.len() as u32;
+        self.resources.0.push(TaggedInstruction(Some(tag.to_string()), Db(bytes)));
+        if padding > 0 {
+            self.resources.0.push(TaggedInstruction(None, Res(padding)));
+        }
+    }
+
+    /// Adds a table of labels to the data section, a word per label.
+    /// These are the vtables of classes and the jump tables of switch statements.
+    pub fn add_label_table_resource(&mut self, tag: &str, labels: Vec<String>) {
+        self.resources.0.push(TaggedInstruction(Some(tag.to_string()), Dw(labels)));
+    }
+
     /// Adds a new instruction, consuming a tag if it is buffered
     pub fn add_instruction(&mut self, inst: Instruction) {
         if self.current_label.is_some() {
@@ -107,6 +133,37 @@ impl CodeGenerator {
     pub fn add_comment(&mut self, comment: &str) {
         self.output.0.push(TaggedInstruction(None, Comment(comment.to_string())));
     }
+
+    /// Number of instructions output so far
+    pub fn output_len(&self) -> usize {
+        self.output.0.len()
+    }
+
+    /// Replaces the frame size placeholder of the immediates output since `start` with the actual frame size
+    pub fn patch_frame_size(&mut self, start: usize, frame_size: u32) {
+        for inst in self.output.0[start..].iter_mut() {
+            match &mut inst.1 {
+                AddImmediate(_, _, imm) | SubstractImmediate(_, _, imm) if imm == FRAME_SIZE => {
+                    *imm = frame_size.to_string();
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Stands for the size of the frame of the function being generated, which is only known once its body is generated
+const FRAME_SIZE: &str = "framesize";
+
+/// Routine an out of bounds array index jumps to, with the line of the index in R1
+const BOUNDS_ERROR_ROUTINE: &str = "boundserror";
+
+/// Options controlling the generated code
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CodegenOptions {
+    /// Check array indices against the dimensions of the array at runtime.
+    /// An out of bounds index prints its line and halts the program. Off by default.
+    pub bounds_check: bool,
 }
 
 /// The MOON code generator
@@ -114,26 +171,38 @@ pub struct MoonGenerator {
     label_allocator: LabelAllocator,
     register_allocator: RegisterAllocator,
     generator: CodeGenerator,
+    /// Frame of the function being generated
+    frame: Frame,
+    options: CodegenOptions,
 }
 
 impl MoonGenerator {
     pub fn new() -> Self {
+        Self::with_options(CodegenOptions::default())
+    }
+
+    pub fn with_options(options: CodegenOptions) -> Self {
         Self {
             label_allocator: Default::default(),
             register_allocator: RegisterAllocator::new(),
             generator: Default::default(),
+            frame: Default::default(),
+            options,
         }
     }
 
     /// Generates the MOON code given an AST and a symbol table
     pub fn generate(&mut self, ast: &Node, symbols: &SymbolTable) {
         log::info!("Beginning code generation");
-        self.visit_class_declarations(&ast.children()[0], symbols);
-        self.visit_function_definitions(&ast.children()[1], symbols);
-        self.visit_main_function(&ast.children()[2], symbols);
+        self.visit_global_variables(symbols);
+        self.visit_class_declarations(&ast.children()[1], symbols);
+        self.visit_function_definitions(&ast.children()[2], symbols);
+        self.visit_main_function(&ast.children()[3], symbols);
+        if self.options.bounds_check {
+            self.generate_bounds_error_routine();
+        }
 
         self.generator.add_comment(" - - - - Resources - - - -");
-        self.generator.add_tagged_resource("fn_ptr_stack", 512);
     }
 
     /// Finishes the code generation. Returns the output.
@@ -143,6 +212,18 @@ impl MoonGenerator {
         self.generator.output
     }
 
+    /// Global variables are laid out once, in the data section
+    fn visit_global_variables(&mut self, symbols: &SymbolTable) {
+        for scope in symbols.scopes() {
+            if let Scope::Variable(ve) = scope {
+                let size = sizeof(ve.var_type(), symbols);
+                self.generator
+                    .add_comment(&format!(" GLOBAL: {} ({} bytes)", ve.ident(), size));
+                self.generator.add_tagged_resource(&global_variable_label(ve), size);
+            }
+        }
+    }
+
     fn visit_class_declarations(&mut self, class_declarations: &Node, symbols: &SymbolTable) {
         for class_decl in class_declarations.children() {
             if let Some(NodeVal::Leaf(token)) = class_decl.children()[0].val() {
@@ -153,21 +234,48 @@ impl MoonGenerator {
         }
     }
 
+    /// Objects only hold data members, so a class has no code of its own. Its layout is documented in the output.
+    /// Member functions are generated with the other function definitions, the ones of classes with a vtable are listed in it.
     fn generate_class_code(
         &mut self,
-        _class_entry: &ClassEntry,
+        class_entry: &ClassEntry,
         _class_declaration: &Node,
-        _symbols: &SymbolTable,
+        symbols: &SymbolTable,
     ) {
-        unimplemented!("Code generation for classes is not implemented");
+        self.generator.add_comment(&format!(
+            " CLASS: {} ({} bytes)",
+            class_entry.ident(),
+            sizeof(&Type::Custom(class_entry.ident().to_string()), symbols)
+        ));
+        if has_vtable(class_entry, symbols) {
+            self.generator.add_comment("   vtable pointer at offset 0");
+            let labels = get_class_method_table(class_entry, symbols)
+                .iter()
+                .map(|fe| format!("fn_{}", function_label(fe)))
+                .collect();
+            self.generator.add_label_table_resource(&vtable_label(class_entry), labels);
+        }
+        for (offset, member) in class_layout(class_entry, symbols) {
+            self.generator
+                .add_comment(&format!("   {} at offset {}", member.ident(), offset));
+        }
     }
 
     fn visit_function_definitions(&mut self, function_definitions: &Node, symbols: &SymbolTable) {
         for function_def in function_definitions.children() {
-            if let Some(NodeVal::Leaf(token)) = function_def.children()[0].val() {
-                if let Some(Scope::Function(fe)) = symbols.find_scope_by_ident(token.lexeme()) {
-                    self.generate_function_code(fe, function_def, symbols);
+            let function_entry = match (function_def.children()[0].val(), function_def.children()[1].val()) {
+                (Some(NodeVal::Leaf(class_token)), Some(NodeVal::Leaf(_))) => {
+                    // member function
+                    match symbols.find_scope_by_ident(class_token.lexeme()) {
+                        Some(Scope::Class(ce)) => find_function_for_definition(function_def, ce.table(), symbols),
+                        _ => None,
+                    }
                 }
+                // free function
+                _ => find_function_for_definition(function_def, symbols, symbols),
+            };
+            if let Some(fe) = function_entry {
+                self.generate_function_code(fe, function_def, symbols);
             }
         }
     }
@@ -176,26 +284,42 @@ impl MoonGenerator {
 
         self.generator.add_comment(" ~ Main ~");
         if let Some(Scope::Function(main)) = symbols.find_scope_by_ident("main") {
-            for scope in main.table().scopes() {
-                if let Scope::Variable(ve) = scope {
-                    let tag: String = format!("var_{}_{}", main.ident(), ve.ident());
-                    self.generator
-                        .add_tagged_resource(&tag, sizeof(ve.var_type(), symbols));
-                }
-            }
+            self.frame = Frame::new(main, symbols);
+            let start = self.generator.output_len();
 
             self.generator.add_comment("Body");
             // Generate code for main function body
             self.generator
                 .add_tagged_instruction(TaggedInstruction(Some("fn_main".to_string()), Entry));
             self.generator.add_instruction(Align);
-            //self.generator.add_instruction(AddImmediate(R14, R0, "topaddr".to_string()));
+            // the stack of frames starts at the top of the memory
+            self.generator.add_instruction(AddImmediate(R14, R0, "topaddr".to_string()));
+            // global objects are pointed at their vtables once, before main runs
+            for scope in symbols.scopes().iter() {
+                if let Scope::Variable(ve) = scope {
+                    if vtable_pointers(ve.var_type(), symbols).is_empty() {
+                        continue;
+                    }
+                    let base_reg = self.register_allocator.next_free_register();
+                    self.generator
+                        .add_instruction(AddImmediate(base_reg, R0, global_variable_label(ve)));
+                    self.generate_vtable_pointers_code(base_reg, 0, ve.var_type(), symbols);
+                    self.register_allocator.release_register(base_reg);
+                }
+            }
+            for scope in main.table().scopes().iter() {
+                if let Scope::Variable(ve) = scope {
+                    let offset = self.frame.offset(ve.ident());
+                    self.generate_vtable_pointers_code(R14, offset, ve.var_type(), symbols);
+                }
+            }
 
             self.generate_statement_block_code(
                 &main_func.children()[0].children()[1],
                 main,
                 symbols,
             );
+            self.generator.patch_frame_size(start, self.frame.size());
         }
 
         self.generator.add_instruction(Halt);
@@ -209,32 +333,29 @@ impl MoonGenerator {
         symbols: &SymbolTable,
     ) {
         self.generator.add_comment(&format!(" FUNCTION: {}", fe.ident()));
-        // reserve memory for function return value
-        self.generator.add_tagged_resource(
-            &format!("fnres_{}", fe.ident()),
-            sizeof(&fe.type_sig().1, symbols),
-        );
-
-        // Allocate resources for the function parameters and local variables
-        for scope in fe.table().scopes().iter() {
-            if let Scope::FunctionParameter(fpe) = scope {
-                let tag: String = format!("param_{}_{}", fe.ident(), fpe.ident());
-                self.generator
-                    .add_tagged_resource(&tag, sizeof(fpe.param_type(), symbols));
-            } else if let Scope::Variable(ve) = scope {
-                let tag: String = format!("var_{}_{}", fe.ident(), ve.ident());
-                self.generator
-                    .add_tagged_resource(&tag, sizeof(ve.var_type(), symbols));
-            }
-        }
+        self.frame = Frame::new(fe, symbols);
+        let start = self.generator.output_len();
 
         // Add function body
         self.generator
-            .add_tagged_instruction(TaggedInstruction(Some(format!("fn_{}", fe.ident())), NoOp));
+            .add_tagged_instruction(TaggedInstruction(Some(format!("fn_{}", function_label(fe))), NoOp));
 
         self.generator.add_comment("Header");
 
-        self.store_and_inc_fn_ptr();
+        // the caller jumped here with the return address in R15
+        self.generator.add_instruction(StoreWord(R15, R14, Frame::RETURN_ADDRESS as i16));
+
+        // frames are reused, so local variables are reset on entry
+        for scope in fe.table().scopes().iter() {
+            if let Scope::Variable(ve) = scope {
+                let offset = self.frame.offset(ve.ident());
+                for word in 0..sizeof(ve.var_type(), symbols) / 4 {
+                    self.generator
+                        .add_instruction(StoreWord(R0, R14, (offset + 4 * word as i32) as i16));
+                }
+                self.generate_vtable_pointers_code(R14, offset, ve.var_type(), symbols);
+            }
+        }
 
         self.generator.add_comment("Body");
         self.generate_statement_block_code(
@@ -245,33 +366,14 @@ impl MoonGenerator {
 
         self.generator.add_comment("Tail");
 
-        // reset local variables
-        for scope in fe.table().scopes().iter() {
-            if let Scope::Variable(ve) = scope {
-                self.generator.add_instruction(StoreWordLabel(
-                    R0,
-                    R0,
-                    format!("var_{}_{}", fe.ident(), ve.ident()),
-                ));
-            }
-            else if let Scope::FunctionParameter(pe) = scope
-            {
-                self.generator.add_instruction(StoreWordLabel(
-                    R0,
-                    R0,
-                    format!("para,_{}_{}", fe.ident(), pe.ident()),
-                ));
-            }
-        }
-
         self.generator
-            .buffer_label(&format!("fn_{}_retblock", fe.ident()));
+            .buffer_label(&format!("fn_{}_retblock", function_label(fe)));
 
-        // jump back to callee, assuming address will be stored in R15
-        self.load_and_dec_fn_ptr();
+        // jump back to the caller
+        self.generator.add_instruction(LoadWord(R15, R14, Frame::RETURN_ADDRESS as i16));
         self.generator.add_instruction(JumpRegister(R15));
 
-        return;
+        self.generator.patch_frame_size(start, self.frame.size());
     }
 
     fn generate_statement_block_code(
@@ -295,6 +397,14 @@ impl MoonGenerator {
                             self.generator.add_comment(" ~ While Statement ~");
                             self.generate_while_statement_code(statement, fe, symbols);
                         }
+                        InternalNodeType::ForStatement => {
+                            self.generator.add_comment(" ~ For Statement ~");
+                            self.generate_for_statement_code(statement, fe, symbols);
+                        }
+                        InternalNodeType::SwitchStatement => {
+                            self.generator.add_comment(" ~ Switch Statement ~");
+                            self.generate_switch_statement_code(statement, fe, symbols);
+                        }
                         InternalNodeType::IfStatement => {
                             self.generator.add_comment(" ~ If Statement ~");
                             self.generate_if_statement_code(statement, fe, symbols);
@@ -313,13 +423,13 @@ impl MoonGenerator {
                         }
                         InternalNodeType::BreakStatement => {
                             self.generator.add_comment(" ~ Break Statement ~");
-                            let (_, endwhile_label) = self.label_allocator.current_while_labels();
-                            self.generator.add_instruction(JumpLabel(endwhile_label));
+                            let break_label = self.label_allocator.current_break_label();
+                            self.generator.add_instruction(JumpLabel(break_label));
                         }
                         InternalNodeType::ContinueStatement => {
                             self.generator.add_comment(" ~ Continue Statement ~");
-                            let (while_label, _) = self.label_allocator.current_while_labels();
-                            self.generator.add_instruction(JumpLabel(while_label));
+                            let continue_label = self.label_allocator.current_continue_label();
+                            self.generator.add_instruction(JumpLabel(continue_label));
                         }
                         InternalNodeType::GenericStatement => {
                             self.generate_generic_statement_code(statement, fe, symbols);
@@ -342,34 +452,14 @@ impl MoonGenerator {
         let (while_label, endwhile_label) = self.label_allocator.next_while_labels();
 
         self.generator.buffer_label(&while_label);
-        let rel_expr_res =
-            self.generate_relative_expression_code(&while_statement.children()[0].children()[0], fe, symbols);
-        let rel_expr_reg = self.register_allocator.next_free_register();
-        self.generator.add_instruction(Substract(rel_expr_reg, rel_expr_reg, rel_expr_reg));
-        match rel_expr_res {
-            Immediate(val) => {
-                self.generator.add_instruction(AddImmediate(rel_expr_reg, R0, val));
-            }
-            Labelled(label, offset) => {
-                self.generator.add_instruction(LoadWordLabel(rel_expr_reg, offset, label.to_string()));
-                self.register_allocator.release_register(offset);
-            }
-            Register(r) => {
-                self.generator.add_instruction(Add(rel_expr_reg, R0, r));
-                self.register_allocator.release_register(r);
-            }
-            Pointer(label, offs) => {
-                self.generator.add_instruction(LoadWordLabel(rel_expr_reg, R0, label)); // load address
-                self.generator.add_instruction(Add(rel_expr_reg, rel_expr_reg, offs)); // add offset
-                self.generator.add_instruction(LoadWord(rel_expr_reg, rel_expr_reg, 0)); // load actual value at memory location
-                self.register_allocator.release_register(offs);
-            }
-        }
+        let rel_expr_reg = self.generate_condition_code(&while_statement.children()[0], fe, symbols);
         self.generator.add_instruction(BranchIfZeroLabel(rel_expr_reg, endwhile_label.clone()));
         self.register_allocator.release_register(rel_expr_reg);
 
         // code for stat block
+        self.label_allocator.enter_loop(&while_label, &endwhile_label);
         self.generate_statement_block_code(&while_statement.children()[1], fe, symbols);
+        self.label_allocator.exit_loop();
 
         self.generator
             .add_instruction(JumpLabel(while_label.clone()));
@@ -378,46 +468,180 @@ impl MoonGenerator {
         return;
     }
 
-    fn generate_if_statement_code(
+    fn generate_for_statement_code(
         &mut self,
-        if_statement: &Node,
+        for_statement: &Node,
         fe: &FunctionEntry,
         symbols: &SymbolTable,
     ) {
         /*
-                {code for expr yields tn as a result}
-                lw r1,tn(r0)
-                bz r1,else1
-                {code for statblock1}
-                j endif1
-        else1  	{code for statblock2}
-        endif1 	{code continuation}
+                {code for initializer}
+        for1    {code for rel expr yields rn as a result}
+                bz rn,endfor1
+                {code for statblock}
+        forstep1 {code for step}
+                j for1
+        endfor1 {code continuation}
          */
-        let (if_label, else_label, endif_label) = self.label_allocator.next_if_labels();
+        let (for_label, step_label, endfor_label) = self.label_allocator.next_for_labels();
+
+        self.generate_assignment_code(&for_statement.children()[0], fe, symbols);
+
+        self.generator.buffer_label(&for_label);
+        let rel_expr_reg = self.generate_condition_code(&for_statement.children()[1], fe, symbols);
+        self.generator.add_instruction(BranchIfZeroLabel(rel_expr_reg, endfor_label.clone()));
+        self.register_allocator.release_register(rel_expr_reg);
+
+        // code for stat block
+        self.label_allocator.enter_loop(&step_label, &endfor_label);
+        self.generate_statement_block_code(&for_statement.children()[3], fe, symbols);
+        self.label_allocator.exit_loop();
+
+        self.generator.buffer_label(&step_label);
+        self.generate_assignment_code(&for_statement.children()[2], fe, symbols);
+        self.generator.add_instruction(JumpLabel(for_label));
+        self.generator.buffer_label(&endfor_label);
+    }
+
+    fn generate_switch_statement_code(
+        &mut self,
+        switch_statement: &Node,
+        fe: &FunctionEntry,
+        symbols: &SymbolTable,
+    ) {
+        /*
+                    {code for expr yields rn as a result}
+                    {compare chain: ceqi rt,rn,value ; bnz rt,case_1_i for each case ; j default}
+                 or {jump table: subi rn,rn,min ; bounds check to default ; sl rn,2 ; lw rn,jumptable_1(rn) ; jr rn}
+        case_1_1    {code for case}
+                    j endswitch_1
+                    ...
+        endswitch_1 {code continuation}
+         */
+        let cases = &switch_statement.children()[1..];
+        let (case_labels, jump_table_label, endswitch_label) = self.label_allocator.next_switch_labels(cases.len());
+
+        // where the switch goes when no case matches
+        let default_label = cases
+            .iter()
+            .zip(&case_labels)
+            .find(|(case, _)| case.val() == Some(&NodeVal::Internal(InternalNodeType::DefaultCase)))
+            .map_or(endswitch_label.clone(), |(_, label)| label.clone());
+        let case_values: Vec<(i32, String)> = cases
+            .iter()
+            .zip(&case_labels)
+            .filter(|(case, _)| case.val() == Some(&NodeVal::Internal(InternalNodeType::Case)))
+            .map(|(case, label)| (evaluate_case_label(case, fe, symbols).unwrap().1, label.clone()))
+            .collect();
+
+        let expr_reg = self.generate_condition_code(&switch_statement.children()[0], fe, symbols);
+        let temp_reg = self.register_allocator.next_free_register();
+        let values: Vec<i32> = case_values.iter().map(|(value, _)| *value).collect();
+        if use_jump_table(&values) {
+            let min = *values.iter().min().unwrap();
+            let range = (*values.iter().max().unwrap() - min + 1) as i16;
+            let mut table = vec![default_label.clone(); range as usize];
+            for (value, label) in &case_values {
+                table[(value - min) as usize] = label.clone();
+            }
+            self.generator.add_label_table_resource(&jump_table_label, table);
+
+            if min != 0 {
+                self.generator.add_instruction(SubstractImmediate(expr_reg, expr_reg, min.to_string()));
+            }
+            self.generator.add_instruction(LessImmediate(temp_reg, expr_reg, 0));
+            self.generator.add_instruction(BranchIfNonZeroLabel(temp_reg, default_label.clone()));
+            self.generator.add_instruction(GreaterEqualImmediate(temp_reg, expr_reg, range));
+            self.generator.add_instruction(BranchIfNonZeroLabel(temp_reg, default_label));
+            self.generator.add_instruction(ShiftLeft(expr_reg, 2));
+            self.generator.add_instruction(LoadWordLabel(expr_reg, expr_reg, jump_table_label));
+            self.generator.add_instruction(JumpRegister(expr_reg));
+        } else {
+            for (value, label) in case_values {
+                match i16::try_from(value) {
+                    Ok(imm) => self.generator.add_instruction(EqualImmediate(temp_reg, expr_reg, imm)),
+                    Err(_) => {
+                        self.generator.add_instruction(AddImmediate(temp_reg, R0, value.to_string()));
+                        self.generator.add_instruction(Equal(temp_reg, expr_reg, temp_reg));
+                    }
+                }
+                self.generator.add_instruction(BranchIfNonZeroLabel(temp_reg, label));
+            }
+            self.generator.add_instruction(JumpLabel(default_label));
+        }
+        self.register_allocator.release_register(temp_reg);
+        self.register_allocator.release_register(expr_reg);
+
+        // code for the cases, which don't fall through to the next one
+        self.label_allocator.enter_switch(&endswitch_label);
+        for (i, (case, label)) in cases.iter().zip(&case_labels).enumerate() {
+            self.generator.buffer_label(label);
+            self.generate_statement_block_code(case.children().last().unwrap(), fe, symbols);
+            if i + 1 < cases.len() {
+                self.generator.add_instruction(JumpLabel(endswitch_label.clone()));
+            }
+        }
+        self.label_allocator.exit_switch();
+
+        self.generator.buffer_label(&endswitch_label);
+    }
 
-        let rel_expr_res =
-            self.generate_relative_expression_code(&if_statement.children()[0].children()[0], fe, symbols);
-        let rel_expr_reg = self.register_allocator.next_free_register();
-        self.generator.add_instruction(Substract(rel_expr_reg, rel_expr_reg, rel_expr_reg));
-        match rel_expr_res {
+    /// Evaluates the expression of a condition into a newly allocated register
+    fn generate_condition_code(
+        &mut self,
+        condition: &Node,
+        fe: &FunctionEntry,
+        symbols: &SymbolTable,
+    ) -> Register {
+        let condition_res = self.generate_expression_code(condition, fe, symbols);
+        self.load_into_register(condition_res)
+    }
+
+    /// Loads the value of an evaluated expression into a newly allocated register
+    fn load_into_register(&mut self, storage: ExprParseStorage) -> Register {
+        let reg = self.register_allocator.next_free_register();
+        self.generator.add_instruction(Substract(reg, reg, reg));
+        match storage {
             Immediate(val) => {
-                self.generator.add_instruction(AddImmediate(rel_expr_reg, R0, val));
+                self.generator.add_instruction(AddImmediate(reg, R0, val));
             }
             Labelled(label, offset) => {
-                self.generator.add_instruction(LoadWordLabel(rel_expr_reg, offset, label.to_string()));
+                self.generator.add_instruction(LoadWordLabel(reg, offset, label.to_string()));
                 self.register_allocator.release_register(offset);
             }
             Register(r) => {
-                self.generator.add_instruction(Add(rel_expr_reg, R0, r));
+                self.generator.add_instruction(Add(reg, R0, r));
                 self.register_allocator.release_register(r);
             }
             Pointer(label, offs) => {
-                self.generator.add_instruction(LoadWordLabel(rel_expr_reg, R0, label)); // load address
-                self.generator.add_instruction(Add(rel_expr_reg, rel_expr_reg, offs)); // add offset
-                self.generator.add_instruction(LoadWord(rel_expr_reg, rel_expr_reg, 0)); // load actual value at memory location
+                self.generator.add_instruction(LoadWordLabel(reg, R14, label)); // load address
+                self.generator.add_instruction(Add(reg, reg, offs)); // add offset
+                self.generator.add_instruction(LoadWord(reg, reg, 0)); // load actual value at memory location
                 self.register_allocator.release_register(offs);
             }
         }
+
+        reg
+    }
+
+    fn generate_if_statement_code(
+        &mut self,
+        if_statement: &Node,
+        fe: &FunctionEntry,
+        symbols: &SymbolTable,
+    ) {
+        /*
+                {code for expr yields tn as a result}
+                lw r1,tn(r0)
+                bz r1,else1
+                {code for statblock1}
+                j endif1
+        else1  	{code for statblock2}
+        endif1 	{code continuation}
+         */
+        let (if_label, else_label, endif_label) = self.label_allocator.next_if_labels();
+
+        let rel_expr_reg = self.generate_condition_code(&if_statement.children()[0], fe, symbols);
         self.generator.add_tagged_instruction(TaggedInstruction(
             Some(if_label),
             BranchIfZeroLabel(rel_expr_reg, else_label.clone()),
@@ -442,24 +666,20 @@ impl MoonGenerator {
         &mut self,
         read_statement: &Node,
         fe: &FunctionEntry,
-        _symbols: &SymbolTable,
+        symbols: &SymbolTable,
     ) {
-        match read_statement.children()[0].children()[0].val()
-        {
-            Some(NodeVal::Leaf(token)) => {
-                if let Some(Scope::Variable(ve)) = fe.table().find_scope_by_ident(token.lexeme())
-                {
-                    self.generator.add_instruction(JumpLinkLabel(R15, "getint".to_string()));
-                    self.generator.add_instruction(StoreWordLabel(R1, R0, format!("var_{}_{}", fe.ident(), ve.ident())));
-                }
-                else if let Some(Scope::FunctionParameter(pe)) = fe.table().find_scope_by_ident(token.lexeme())
-                {
-                    self.generator.add_instruction(JumpLinkLabel(R15, "getint".to_string()));
-                    self.generator.add_instruction(StoreWordLabel(R1, R0, format!("param_{}_{}", fe.ident(), pe.ident())));
-                }
-            },
-            _ => log::error!("INVALID READ VARIABLE")
+        self.generator.add_instruction(JumpLinkLabel(R15, "getint".to_string()));
+        // getint uses R1 to R4, so the target is located after the call
+        let value_reg = self.register_allocator.next_free_register();
+        self.generator.add_instruction(Add(value_reg, R0, R1));
+        let target_node = &read_statement.children()[0].children()[0];
+        if let Ok(Type::Float) = validate_dot_operand(target_node, fe, symbols) {
+            // the integer read is converted to a float
+            self.generator.add_instruction(MultiplyImmediate(value_reg, value_reg, FLOAT_SCALE));
         }
+        let target = self.generate_dot_operand_code(target_node, fe, symbols);
+        self.store_into(value_reg, target);
+        self.register_allocator.release_register(value_reg);
     }
 
     fn generate_write_statement_code(
@@ -469,28 +689,21 @@ impl MoonGenerator {
         symbols: &SymbolTable,
     ) {
         let expr_res = self.generate_expression_code(&write_statement.children()[0], fe, symbols);
-        self.generator.add_instruction(Substract(R1, R1, R1));
-        match expr_res
-        {
-            Immediate(imm) => {
-
-                self.generator.add_instruction(AddImmediate(R1, R0, imm));
-            }
-            Labelled(label, offs) => {
-                self.generator.add_instruction(LoadWordLabel(R1, offs, label));
-                self.register_allocator.release_register(offs);
-            }
-            Register(r) => {
-                self.generator.add_instruction(Add(R1, R0, r));
-                self.register_allocator.release_register(r);
-            }
-            Pointer(label, offs) => {
-                self.generator.add_instruction(LoadWordLabel(R1, R0, label));
-                self.generator.add_instruction(Add(R1, R1, offs));
-                self.register_allocator.release_register(offs);
-            }
-        }
-        self.generator.add_instruction(JumpLinkLabel(R15, "putint".to_string()));
+        let expr_type = validate_expr(&write_statement.children()[0], fe, symbols);
+        // the value may be located through R1, so it is loaded before being moved into R1
+        let value_reg = match expr_type {
+            // strings are printed from their address
+            Ok(Type::String) => self.address_of(expr_res),
+            _ => self.load_into_register(expr_res),
+        };
+        self.generator.add_instruction(Add(R1, R0, value_reg));
+        self.register_allocator.release_register(value_reg);
+        let routine = match expr_type {
+            Ok(Type::Float) => "putfloat",
+            Ok(Type::String) => "putstr",
+            _ => "putint",
+        };
+        self.generator.add_instruction(JumpLinkLabel(R15, routine.to_string()));
     }
 
     fn generate_return_statement_code(
@@ -501,6 +714,22 @@ impl MoonGenerator {
     ) {
         let expr_result =
             self.generate_expression_code(&return_statement.children()[0], fe, symbols);
+        let expr_type = validate_expr(&return_statement.children()[0], fe, symbols)
+            .expect("return statement should have been validated");
+        // an integer returned from a float function is promoted
+        let expr_result = self.generate_conversion_code(expr_result, &expr_type, &fe.type_sig().1);
+        if let Type::Custom(_) | Type::String = fe.type_sig().1 {
+            // objects and strings are copied into the return value
+            let src_reg = self.address_of(expr_result);
+            let dst_reg = self.register_allocator.next_free_register();
+            self.generator.add_instruction(AddImmediate(dst_reg, R14, self.frame.return_value().to_string()));
+            self.copy_words(dst_reg, src_reg, sizeof(&fe.type_sig().1, symbols));
+            self.register_allocator.release_register(src_reg);
+            self.register_allocator.release_register(dst_reg);
+            self.generator
+                .add_instruction(JumpLabel(format!("fn_{}_retblock", function_label(fe))));
+            return;
+        }
         let expr_result_reg = self.register_allocator.next_free_register();
         self.generator.add_instruction(Substract(expr_result_reg, expr_result_reg, expr_result_reg));
         match expr_result {
@@ -516,21 +745,21 @@ impl MoonGenerator {
                 self.register_allocator.release_register(r);
             }
             Pointer(label, offs) => {
-                self.generator.add_instruction(LoadWordLabel(expr_result_reg, R0, label)); // load address
+                self.generator.add_instruction(LoadWordLabel(expr_result_reg, R14, label)); // load address
                 self.generator.add_instruction(Add(expr_result_reg, expr_result_reg, offs)); // add offset
                 self.generator.add_instruction(LoadWord(expr_result_reg, expr_result_reg, 0)); // load actual value at memory location
                 self.register_allocator.release_register(offs);
             }
         }
-        self.generator.add_instruction(StoreWordLabel(
+        self.generator.add_instruction(StoreWord(
             expr_result_reg,
-            R0,
-            format!("fnres_{}", fe.ident()),
+            R14,
+            self.frame.return_value() as i16,
         ));
         self.register_allocator.release_register(expr_result_reg);
 
         self.generator
-            .add_instruction(JumpLabel(format!("fn_{}_retblock", fe.ident())));
+            .add_instruction(JumpLabel(format!("fn_{}_retblock", function_label(fe))));
 
         return;
     }
@@ -577,93 +806,168 @@ impl MoonGenerator {
         function_entry: &FunctionEntry,
         symbols: &SymbolTable,
     ) -> ExprParseStorage {
-        let function_ident = match function_call.val() {
-            Some(NodeVal::Leaf(token)) => token.lexeme(),
+        let function_token = match function_call.val() {
+            Some(NodeVal::Leaf(token)) => token,
             _ => {
                 panic!()
             }
         };
 
+        // overloads were resolved when checking semantics, so this can't fail
+        let candidates = function_candidates(function_token.lexeme(), function_entry, symbols)
+            .expect("function call should have been validated");
+        let fe = resolve_function_call(
+            function_token,
+            &function_call.children()[0],
+            &candidates,
+            function_entry,
+            symbols,
+        )
+        .expect("function call should have been validated");
+
+        // a member function called without an object is called on the object of the caller
+        self.generate_call_code(fe, &function_call.children()[0], None, function_entry, symbols)
+    }
+
+    /// Generates the call of a function with the arguments under `func_call_params`.
+    /// Member functions are passed the address of the `receiver` object, or the object of the caller when there is none.
+    fn generate_call_code(
+        &mut self,
+        fe: &FunctionEntry,
+        func_call_params: &Node,
+        receiver: Option<ExprParseStorage>,
+        function_entry: &FunctionEntry,
+        symbols: &SymbolTable,
+    ) -> ExprParseStorage {
         let mut expr_results: Vec<ExprParseStorage> = Vec::new();
-        for expr_node in function_call.children()[0].children() {
+        let mut expr_types: Vec<Type> = Vec::new();
+        for expr_node in func_call_params.children() {
             match expr_node.val() {
                 None => {
                     break;
                 }
-                Some(_) => expr_results.push(self.generate_expression_code(
-                    expr_node,
-                    function_entry,
-                    symbols,
-                )),
+                Some(_) => {
+                    expr_results.push(self.generate_expression_code(expr_node, function_entry, symbols));
+                    expr_types.push(
+                        validate_expr(expr_node, function_entry, symbols).expect("call should have been validated"),
+                    );
+                }
             }
         }
 
-        if let Some(Scope::Function(fe)) = symbols.find_scope_by_ident(function_ident) {
-            let mut params: Vec<&ParameterEntry> = Vec::new();
-            for scope in fe.table().scopes() {
-                if let Scope::FunctionParameter(pe) = scope {
-                    params.push(pe);
-                }
+        let this_reg = match (fe.member_of(), receiver) {
+            (Some(_), Some(receiver)) => Some(self.address_of(receiver)),
+            (Some(_), None) => {
+                let this_reg = self.register_allocator.next_free_register();
+                self.generator.add_instruction(LoadWordLabel(this_reg, R14, self.frame.this().to_string()));
+                Some(this_reg)
             }
-            assert_eq!(params.len(), expr_results.len());
-            for (param, expr_res) in params.drain(..).zip(expr_results.drain(..)) {
-                let param_label = format!("param_{}_{}", fe.ident(), param.ident());
-                self.generator.add_comment(&format!("Storing into function parameter {}", param_label));
-                match expr_res {
-                    Immediate(imm) => {
-                        let param_reg = self.register_allocator.next_free_register();
-                        self.generator.add_instruction(Substract(param_reg, param_reg, param_reg));
-                        self.generator.add_instruction(AddImmediate(param_reg, R0, imm));
-                        self.generator.add_instruction(StoreWordLabel(param_reg, R0, param_label));
-                        self.register_allocator.release_register(param_reg);
-                    }
-                    Labelled(label, offs) => {
-                        match param.param_type() {
-                            Type::IntegerArray(_)
-                            | Type::FloatArray(_) => {
-                                self.generator.add_instruction(AddImmediate(offs, offs, label));
-                                self.generator.add_instruction(StoreWordLabel(offs, R0, param_label));
-                                self.register_allocator.release_register(offs);
-                            }
-                            Type::Float | Type::Integer => {
-                                let expr_reg = self.register_allocator.next_free_register();
-                                self.generator.add_instruction(Substract(expr_reg, expr_reg, expr_reg));
-                                self.generator.add_instruction(LoadWordLabel(expr_reg, offs, label));
-                                self.generator.add_instruction(StoreWordLabel(expr_reg, R0, param_label));
-                                self.register_allocator.release_register(expr_reg);
-                                self.register_allocator.release_register(offs);
-                            }
-                            Type::String
-                            | Type::StringArray(_)
-                            | Type::Custom(_)
-                            | Type::CustomArray(_, _) => { unimplemented!("Class and String not implemented") },
-                            _ => panic!()
-                        }
-                    }
-                    Register(r) => {
-                        self.generator.add_instruction(StoreWordLabel(r, R0, param_label));
-                        self.register_allocator.release_register(r);
-                    }
-                    Pointer(label, offs) => {
-                        // copy the pointer into the param
-                        let ptr_reg = self.register_allocator.next_free_register();
-                        self.generator.add_instruction(Substract(ptr_reg, ptr_reg, ptr_reg));
-                        self.generator.add_instruction(LoadWordLabel(ptr_reg, R0, label));
-                        self.generator.add_instruction(Add(ptr_reg, ptr_reg, offs));
-                        self.generator.add_instruction(StoreWordLabel(ptr_reg, R0, param_label));
-                        self.register_allocator.release_register(ptr_reg);
-                        self.register_allocator.release_register(offs);
-                    }
+            (None, _) => None,
+        };
+        // member functions with a vtable slot are dispatched on the class of the object
+        let dispatch_reg = match (this_reg, vtable_slot(fe, symbols)) {
+            (Some(this_reg), Some(slot)) => {
+                let dispatch_reg = self.register_allocator.next_free_register();
+                self.generator.add_instruction(LoadWord(dispatch_reg, this_reg, 0));
+                self.generator.add_instruction(LoadWord(dispatch_reg, dispatch_reg, (4 * slot) as i16));
+                Some(dispatch_reg)
+            }
+            _ => None,
+        };
+
+        let mut params: Vec<&ParameterEntry> = Vec::new();
+        for scope in fe.table().scopes() {
+            if let Scope::FunctionParameter(pe) = scope {
+                params.push(pe);
+            }
+        }
+        assert_eq!(params.len(), expr_results.len());
+
+        // the frame of the callee starts where the frame of the caller ends
+        let callee_frame = Frame::new(fe, symbols);
+        let frame_reg = self.register_allocator.next_free_register();
+        self.generator.add_instruction(SubstractImmediate(frame_reg, R14, FRAME_SIZE.to_string()));
+        for ((param, expr_res), expr_type) in params.drain(..).zip(expr_results.drain(..)).zip(expr_types.iter()) {
+            let param_offset = callee_frame.offset(param.ident()).to_string();
+            self.generator.add_comment(&format!("Storing into function parameter {}", param.ident()));
+            match param.param_type() {
+                Type::IntegerArray(_)
+                | Type::FloatArray(_)
+                | Type::BoolArray(_)
+                | Type::StringArray(_)
+                | Type::CustomArray(_, _)
+                | Type::EnumArray(_, _) => {
+                    // pass the address of the array
+                    let addr_reg = self.address_of(expr_res);
+                    self.generator.add_instruction(StoreWordLabel(addr_reg, frame_reg, param_offset));
+                    self.register_allocator.release_register(addr_reg);
                 }
+                Type::Float | Type::Integer | Type::Bool | Type::Enum(_) => {
+                    // an integer passed to a float parameter is promoted
+                    let expr_res = self.generate_conversion_code(expr_res, expr_type, param.param_type());
+                    let expr_reg = self.load_into_register(expr_res);
+                    self.generator.add_instruction(StoreWordLabel(expr_reg, frame_reg, param_offset));
+                    self.register_allocator.release_register(expr_reg);
+                }
+                Type::Custom(_) | Type::String => {
+                    // objects and strings are passed by value
+                    let src_reg = self.address_of(expr_res);
+                    let dst_reg = self.register_allocator.next_free_register();
+                    self.generator.add_instruction(AddImmediate(dst_reg, frame_reg, param_offset));
+                    self.copy_words(dst_reg, src_reg, sizeof(param.param_type(), symbols));
+                    self.register_allocator.release_register(src_reg);
+                    self.register_allocator.release_register(dst_reg);
+                }
+                _ => panic!()
             }
-        } else {
-            panic!()
+        }
+        if let Some(this_reg) = this_reg {
+            self.generator.add_instruction(StoreWordLabel(this_reg, frame_reg, callee_frame.this().to_string()));
+            self.register_allocator.release_register(this_reg);
+        }
+        self.register_allocator.release_register(frame_reg);
+
+        // the callee uses every register, save the ones still in use
+        let mut saved_registers: Vec<(Register, String)> = Vec::new();
+        for reg in self.register_allocator.used_registers() {
+            let temp = self.next_temp();
+            self.generator.add_instruction(StoreWordLabel(reg, R14, temp.clone()));
+            saved_registers.push((reg, temp));
         }
 
-        self.generator
-            .add_instruction(JumpLinkLabel(R15, format!("fn_{}", function_ident))); // jump to function
+        self.generator.add_instruction(SubstractImmediate(R14, R14, FRAME_SIZE.to_string()));
+        match dispatch_reg {
+            Some(dispatch_reg) => self.generator.add_instruction(JumpLinkRegister(R15, dispatch_reg)),
+            None => self
+                .generator
+                .add_instruction(JumpLinkLabel(R15, format!("fn_{}", function_label(fe)))), // jump to function
+        }
+        self.generator.add_instruction(AddImmediate(R14, R14, FRAME_SIZE.to_string()));
+
+        for (reg, temp) in saved_registers {
+            self.generator.add_instruction(LoadWordLabel(reg, R14, temp));
+        }
+        if let Some(dispatch_reg) = dispatch_reg {
+            self.register_allocator.release_register(dispatch_reg);
+        }
 
-        return ExprParseStorage::Labelled(format!("fnres_{}", function_ident), R0);
+        // copy the return value out of the callee frame before another call reuses it
+        let return_type = &fe.type_sig().1;
+        if *return_type == Type::Void {
+            return Register(R0);
+        }
+        let size = sizeof(return_type, symbols).max(4);
+        let result = self.frame.allocate(size).to_string();
+        let src_reg = self.register_allocator.next_free_register();
+        self.generator.add_instruction(SubstractImmediate(src_reg, R14, FRAME_SIZE.to_string()));
+        self.generator.add_instruction(AddImmediate(src_reg, src_reg, callee_frame.return_value().to_string()));
+        let dst_reg = self.register_allocator.next_free_register();
+        self.generator.add_instruction(AddImmediate(dst_reg, R14, result.clone()));
+        self.copy_words(dst_reg, src_reg, size);
+        self.register_allocator.release_register(src_reg);
+        self.register_allocator.release_register(dst_reg);
+
+        ExprParseStorage::Labelled(result, R14)
     }
 
     fn generate_assignment_code(
@@ -680,71 +984,8 @@ impl MoonGenerator {
         //          sw lhs, rn
         //
 
-        let lhs: ExprParseStorage = match assignment_statement.children()[0].val() {
-            Some(NodeVal::Internal(InternalNodeType::DotOp)) => self.generate_dot_operator_code(
-                &assignment_statement.children()[0],
-                function_entry,
-                symbols,
-            ),
-            Some(NodeVal::Leaf(token)) => {
-                match function_entry.table().find_scope_by_ident(token.lexeme()) {
-                    Some(Scope::Variable(ve)) => match ve.var_type() {
-                        Type::Integer | Type::Float => ExprParseStorage::Labelled(
-                            format!("var_{}_{}", function_entry.ident(), ve.ident()),
-                            R0,
-                        ),
-                        Type::String | Type::StringArray(_) => {
-                            unimplemented!("String types not implemented")
-                        }
-                        Type::CustomArray(_, _) | Type::Custom(_) => {
-                            unimplemented!("Class types not implemented")
-                        }
-
-                        Type::IntegerArray(_dim) | Type::FloatArray(_dim) => {
-                            let idx_res: ExprParseStorage = self.generate_array_indexing_code(ve.var_type(), &assignment_statement.children()[0], function_entry, symbols);
-                            match idx_res
-                            {
-                                Register(offs_reg) => {
-                                    // for local variables, no need for pointer indirection
-                                    ExprParseStorage::Labelled(format!("var_{}_{}", function_entry.ident(), ve.ident()), offs_reg)
-                                },
-                                _ => panic!("Array index was not stored in a register")
-                            }
-                        }
-                        _ => panic!(),
-                    },
-                    Some(Scope::FunctionParameter(pe)) => match pe.param_type() {
-                        Type::Integer | Type::Float => ExprParseStorage::Labelled(
-                            format!("param_{}_{}", function_entry.ident(), pe.ident()),
-                            R0,
-                        ),
-                        Type::String | Type::StringArray(_) => {
-                            unimplemented!("String types not implemented")
-                        }
-                        Type::CustomArray(_, _) | Type::Custom(_) => {
-                            unimplemented!("Class types not implemented")
-                        }
-
-                        Type::IntegerArray(dim) | Type::FloatArray(dim) => {
-                            let idx_res: ExprParseStorage = self.generate_array_indexing_code(pe.param_type(), &assignment_statement.children()[0], function_entry, symbols);
-                            match idx_res
-                            {
-                                Register(offs_reg) => {
-                                    //for function params, the data is stored elsewhere so we need indirection}
-                                    ExprParseStorage::Pointer(format!("param_{}_{}", function_entry.ident(), pe.ident()), offs_reg)
-                                },
-                                _ => panic!("Array index was not stored in a register")
-                            }
-                        }
-                        _ => panic!(),
-                    },
-                    _ => panic!(),
-                }
-            }
-            _ => {
-                panic!()
-            }
-        };
+        let lhs: ExprParseStorage =
+            self.generate_dot_operand_code(&assignment_statement.children()[0], function_entry, symbols);
 
         let rhs: ExprParseStorage = self.generate_expression_code(
             &assignment_statement.children()[1],
@@ -753,6 +994,21 @@ impl MoonGenerator {
         );
 
         self.generator.add_comment(&format!("{:?} = {:?}", lhs, rhs));
+        let rhs_type = validate_expr(&assignment_statement.children()[1], function_entry, symbols)
+            .expect("assignment should have been validated");
+        let lhs_type = validate_dot_operand(&assignment_statement.children()[0], function_entry, symbols)
+            .expect("assignment should have been validated");
+        // an integer assigned to a float is promoted
+        let rhs = self.generate_conversion_code(rhs, &rhs_type, &lhs_type);
+        if let Type::Custom(_) | Type::String = rhs_type {
+            // objects are assigned by copying their data members, strings by copying their characters
+            let dst_reg = self.address_of(lhs);
+            let src_reg = self.address_of(rhs);
+            self.copy_words(dst_reg, src_reg, sizeof(&rhs_type, symbols));
+            self.register_allocator.release_register(dst_reg);
+            self.register_allocator.release_register(src_reg);
+            return;
+        }
         match (lhs, rhs) {
             (Labelled(llabel, loffs), Labelled(rlabel, roffs)) => {
                 let rhs_reg = self.register_allocator.next_free_register();
@@ -778,7 +1034,7 @@ impl MoonGenerator {
             },
             (Labelled(llabel, loffs), Pointer(rlabel, roffs)) => {
                 let temp_reg = self.register_allocator.next_free_register();
-                self.generator.add_instruction(LoadWordLabel(temp_reg, R0, rlabel)); // load address into register
+                self.generator.add_instruction(LoadWordLabel(temp_reg, R14, rlabel)); // load address into register
                 self.generator.add_instruction(Add(temp_reg, temp_reg, roffs)); // add offset
                 self.generator.add_instruction(LoadWord(temp_reg, temp_reg, 0)); // load actual value
                 self.generator.add_instruction(StoreWordLabel(temp_reg, loffs, llabel));
@@ -791,10 +1047,10 @@ impl MoonGenerator {
                 let rhs_reg = self.register_allocator.next_free_register();
                 self.generator.add_instruction(Substract(rhs_reg, rhs_reg, rhs_reg));
 
-                self.generator.add_instruction(LoadWordLabel(lhs_reg, R0, llabel)); // load address into register
+                self.generator.add_instruction(LoadWordLabel(lhs_reg, R14, llabel)); // load address into register
                 self.generator.add_instruction(Add(lhs_reg, lhs_reg, loffs)); // add offset
 
-                self.generator.add_instruction(LoadWordLabel(rhs_reg, R0, rlabel)); // load address into register
+                self.generator.add_instruction(LoadWordLabel(rhs_reg, R14, rlabel)); // load address into register
                 self.generator.add_instruction(Add(rhs_reg, rhs_reg, roffs)); // add offset
                 self.generator.add_instruction(LoadWord(rhs_reg, rhs_reg, 0)); // load actual value
 
@@ -812,7 +1068,7 @@ impl MoonGenerator {
                 let rhs_reg = self.register_allocator.next_free_register();
                 self.generator.add_instruction(Substract(rhs_reg, rhs_reg, rhs_reg));
 
-                self.generator.add_instruction(LoadWordLabel(lhs_reg, R0, llabel)); // load address into register
+                self.generator.add_instruction(LoadWordLabel(lhs_reg, R14, llabel)); // load address into register
                 self.generator.add_instruction(Add(lhs_reg, lhs_reg, loffs)); // add offset
 
                 self.generator.add_instruction(LoadWordLabel(rhs_reg, roffs, rlabel));
@@ -825,6 +1081,11 @@ impl MoonGenerator {
                 self.register_allocator.release_register(loffs);
                 self.register_allocator.release_register(roffs);
             },
+            (lhs @ Pointer(_, _), rhs) => {
+                let rhs_reg = self.load_into_register(rhs);
+                self.store_into(rhs_reg, lhs);
+                self.register_allocator.release_register(rhs_reg);
+            },
             _ => panic!(),
         }
 
@@ -839,9 +1100,19 @@ impl MoonGenerator {
         function_entry: &FunctionEntry,
         symbols: &SymbolTable,
     ) -> ExprParseStorage {
-        match expr.children()[0].val() {
+        self.generate_expr_operand_code(&expr.children()[0], function_entry, symbols)
+    }
+
+    /// Generates code for the child of an expression node, or for an operand of a logical operator.
+    fn generate_expr_operand_code(
+        &mut self,
+        operand: &Node,
+        function_entry: &FunctionEntry,
+        symbols: &SymbolTable,
+    ) -> ExprParseStorage {
+        match operand.val() {
             Some(NodeVal::Internal(InternalNodeType::ArithExpr)) => {
-                return self.generate_arith_expr_code(&expr.children()[0], function_entry, symbols);
+                self.generate_arith_expr_code(operand, function_entry, symbols)
             }
             Some(NodeVal::Internal(InternalNodeType::Equal))
             | Some(NodeVal::Internal(InternalNodeType::NotEqual))
@@ -849,16 +1120,53 @@ impl MoonGenerator {
             | Some(NodeVal::Internal(InternalNodeType::LessEqualThan))
             | Some(NodeVal::Internal(InternalNodeType::GreaterThan))
             | Some(NodeVal::Internal(InternalNodeType::GreaterEqualThan)) => {
-                return self.generate_relative_expression_code(
-                    &expr.children()[0],
-                    function_entry,
-                    symbols,
-                );
+                self.generate_relative_expression_code(operand, function_entry, symbols)
+            }
+            Some(NodeVal::Internal(InternalNodeType::And))
+            | Some(NodeVal::Internal(InternalNodeType::Or)) => {
+                self.generate_logical_expression_code(operand, function_entry, symbols)
             }
             _ => panic!(),
         }
     }
 
+    /// Given an and/or node, generates code for it.
+    /// Both operands are bools (0/1 words). The right operand is only evaluated when the left one doesn't decide the result:
+    /// the result is the left operand if it is false for &, or true for |, and the right operand otherwise.
+    fn generate_logical_expression_code(
+        &mut self,
+        logical_op: &Node,
+        function_entry: &FunctionEntry,
+        symbols: &SymbolTable,
+    ) -> ExprParseStorage {
+        let end_label = self.label_allocator.next_logical_label();
+        let temp_storage_label = self.next_temp();
+
+        let left =
+            self.generate_expr_operand_code(&logical_op.children()[0], function_entry, symbols);
+        let lr = self.load_into_register(left);
+        self.generator.add_instruction(StoreWordLabel(lr, R14, temp_storage_label.clone()));
+        match logical_op.val() {
+            Some(NodeVal::Internal(InternalNodeType::And)) => {
+                self.generator.add_instruction(BranchIfZeroLabel(lr, end_label.clone()))
+            }
+            Some(NodeVal::Internal(InternalNodeType::Or)) => {
+                self.generator.add_instruction(BranchIfNonZeroLabel(lr, end_label.clone()))
+            }
+            _ => panic!(),
+        }
+        self.register_allocator.release_register(lr);
+
+        let right =
+            self.generate_expr_operand_code(&logical_op.children()[1], function_entry, symbols);
+        let rr = self.load_into_register(right);
+        self.generator.add_instruction(StoreWordLabel(rr, R14, temp_storage_label.clone()));
+        self.register_allocator.release_register(rr);
+        self.generator.buffer_label(&end_label);
+
+        Labelled(temp_storage_label, R14)
+    }
+
     /// Given a relative expression node, generates code for it.
     /// Return value is where/what of the return value of the expression.
     fn generate_relative_expression_code(
@@ -877,8 +1185,17 @@ impl MoonGenerator {
             function_entry,
             symbols,
         );
-        let temp_storage_label = self.label_allocator.next_temp_label();
-        self.generator.add_tagged_resource(&temp_storage_label, 4);
+        let left_type = validate_arith_expr(&rel_expr.children()[0], function_entry, symbols)
+            .expect("relative expression should have been validated");
+        let right_type = validate_arith_expr(&rel_expr.children()[1], function_entry, symbols)
+            .expect("relative expression should have been validated");
+        if left_type == Type::String {
+            return self.generate_string_comparison_code(rel_expr, left, right);
+        }
+        // an integer compared to a float is promoted
+        let operand_type = promoted_type(&left_type, &right_type).unwrap();
+        let left = self.generate_conversion_code(left, &left_type, &operand_type);
+        let right = self.generate_conversion_code(right, &right_type, &operand_type);
 
         let mut lr = self.register_allocator.next_free_register();
         self.generator.add_instruction(Substract(lr, lr, lr));
@@ -903,7 +1220,7 @@ impl MoonGenerator {
             (Immediate(lval), Pointer(rlabel, roffs)) => {
                 self.generator.add_instruction(AddImmediate(lr, R0, lval.clone()));
 
-                self.generator.add_instruction(LoadWordLabel(rr, R0, rlabel.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(rr, R14, rlabel.clone())); // load address
                 self.generator.add_instruction(Add(rr, rr, *roffs)); // add offset
                 self.generator.add_instruction(LoadWord(rr, rr, 0)); // load actual value at memory location
                 self.register_allocator.release_register(*roffs);
@@ -927,7 +1244,7 @@ impl MoonGenerator {
                     .add_instruction(LoadWordLabel(lr, *loffs, llabel.clone()));
                 self.register_allocator.release_register(*loffs);
 
-                self.generator.add_instruction(LoadWordLabel(rr, R0, rlabel.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(rr, R14, rlabel.clone())); // load address
                 self.generator.add_instruction(Add(rr, rr, *roffs)); // add offset
                 self.generator.add_instruction(LoadWord(rr, rr, 0)); // load actual value at memory location
                 self.register_allocator.release_register(*roffs);
@@ -963,13 +1280,13 @@ impl MoonGenerator {
                 self.register_allocator.release_register(lr);
                 lr = *_lr;
 
-                self.generator.add_instruction(LoadWordLabel(rr, R0, rlabel.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(rr, R14, rlabel.clone())); // load address
                 self.generator.add_instruction(Add(rr, rr, *roffs)); // add offset
                 self.generator.add_instruction(LoadWord(rr, rr, 0)); // load actual value at memory location
                 self.register_allocator.release_register(*roffs);
             }
             (Pointer(llabel, loffs), Immediate(rval)) => {
-                self.generator.add_instruction(LoadWordLabel(lr, R0, llabel.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(lr, R14, llabel.clone())); // load address
                 self.generator.add_instruction(Add(lr, lr, *loffs)); // add offset
                 self.generator.add_instruction(LoadWord(lr, lr, 0)); // load actual value at memory location
                 self.register_allocator.release_register(*loffs);
@@ -977,7 +1294,7 @@ impl MoonGenerator {
                 self.generator.add_instruction(AddImmediate(rr, R0, rval.clone()));
             }
             (Pointer(llabel, loffs), Labelled(rlabel, roffs)) => {
-                self.generator.add_instruction(LoadWordLabel(lr, R0, llabel.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(lr, R14, llabel.clone())); // load address
                 self.generator.add_instruction(Add(lr, lr, *loffs)); // add offset
                 self.generator.add_instruction(LoadWord(lr, lr, 0)); // load actual value at memory location
                 self.register_allocator.release_register(*loffs);
@@ -986,7 +1303,7 @@ impl MoonGenerator {
                 self.register_allocator.release_register(*roffs);
             }
             (Pointer(llabel, loffs), Register(_rr)) => {
-                self.generator.add_instruction(LoadWordLabel(lr, R0, llabel.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(lr, R14, llabel.clone())); // load address
                 self.generator.add_instruction(Add(lr, lr, *loffs)); // add offset
                 self.generator.add_instruction(LoadWord(lr, lr, 0)); // load actual value at memory location
                 self.register_allocator.release_register(*loffs);
@@ -995,12 +1312,12 @@ impl MoonGenerator {
                 rr = *_rr;
             }
             (Pointer(llabel, loffs), Pointer(rlabel, roffs)) => {
-                self.generator.add_instruction(LoadWordLabel(lr, R0, llabel.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(lr, R14, llabel.clone())); // load address
                 self.generator.add_instruction(Add(lr, lr, *loffs)); // add offset
                 self.generator.add_instruction(LoadWord(lr, lr, 0)); // load actual value at memory location
                 self.register_allocator.release_register(*loffs);
 
-                self.generator.add_instruction(LoadWordLabel(rr, R0, rlabel.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(rr, R14, rlabel.clone())); // load address
                 self.generator.add_instruction(Add(rr, rr, *roffs)); // add offset
                 self.generator.add_instruction(LoadWord(rr, rr, 0)); // load actual value at memory location
                 self.register_allocator.release_register(*roffs);
@@ -1052,12 +1369,178 @@ impl MoonGenerator {
     /// Return value is in where/what of the return value of the expression.
     fn generate_dot_operator_code(
         &mut self,
-        _dot_op: &Node,
-        _function_entry: &FunctionEntry,
-        _symbols: &SymbolTable,
+        dot_op: &Node,
+        function_entry: &FunctionEntry,
+        symbols: &SymbolTable,
     ) -> ExprParseStorage {
-        log::error!("CODE GENERATION FOR DOT OPERATOR: NOT IMPLEMENTED");
-        return Register(R0);
+        let lhs = self.generate_dot_operand_code(&dot_op.children()[0], function_entry, symbols);
+        let class_entry = match validate_dot_operand(&dot_op.children()[0], function_entry, symbols) {
+            Ok(Type::Custom(class_ident)) => match symbols.find_scope_by_ident(&class_ident) {
+                Some(Scope::Class(ce)) => ce,
+                _ => panic!(),
+            },
+            _ => panic!("dot operator should have been validated"),
+        };
+        let rhs_token = match dot_op.children()[1].val() {
+            Some(NodeVal::Leaf(token)) => token,
+            _ => panic!(),
+        };
+
+        match dot_op.children().get(2).and_then(|node| node.val()) {
+            Some(NodeVal::Internal(InternalNodeType::FuncCallParams)) => {
+                // member function call on the lhs object
+                let candidates = member_function_candidates(class_entry, rhs_token.lexeme(), symbols);
+                let fe = resolve_function_call(
+                    rhs_token,
+                    &dot_op.children()[2],
+                    &candidates,
+                    function_entry,
+                    symbols,
+                )
+                .expect("member function call should have been validated");
+                self.generate_call_code(fe, &dot_op.children()[2], Some(lhs), function_entry, symbols)
+            }
+            _ => {
+                // data member of the lhs object, the element of a data member array if indexed
+                let (offset, ve) = data_member_offset(class_entry, rhs_token.lexeme(), symbols)
+                    .expect("data member should have been validated");
+                let addr_reg = self.address_of(lhs);
+                let offs_reg = self.generate_data_member_offset_code(ve, offset, &dot_op.children()[2..], function_entry, symbols);
+                self.generator.add_instruction(Add(addr_reg, addr_reg, offs_reg));
+                self.register_allocator.release_register(offs_reg);
+                Labelled("0".to_string(), addr_reg)
+            }
+        }
+    }
+
+    /// Given the lhs of a dot operator, or an assignment target, generates code for it.
+    /// It's either a variable, a function call or another dot operator.
+    fn generate_dot_operand_code(
+        &mut self,
+        operand: &Node,
+        function_entry: &FunctionEntry,
+        symbols: &SymbolTable,
+    ) -> ExprParseStorage {
+        match operand.val() {
+            Some(NodeVal::Internal(InternalNodeType::DotOp)) => {
+                self.generate_dot_operator_code(operand, function_entry, symbols)
+            }
+            Some(NodeVal::Leaf(_))
+                if operand.children().first().and_then(|node| node.val())
+                    == Some(&NodeVal::Internal(InternalNodeType::FuncCallParams)) =>
+            {
+                self.generate_function_call_code(operand, function_entry, symbols)
+            }
+            Some(NodeVal::Leaf(_)) => self.generate_variable_code(operand, function_entry, symbols),
+            _ => panic!(),
+        }
+    }
+
+    /// Given an identifier node, generates code locating the variable or parameter it refers to.
+    /// In member functions, it can also be a data member of the object the function was called on.
+    /// Otherwise, it is a global variable.
+    /// Array elements are located with the indices under the node.
+    fn generate_variable_code(
+        &mut self,
+        var_node: &Node,
+        function_entry: &FunctionEntry,
+        symbols: &SymbolTable,
+    ) -> ExprParseStorage {
+        let token = match var_node.val() {
+            Some(NodeVal::Leaf(token)) => token,
+            _ => panic!(),
+        };
+        match function_entry.table().find_scope_by_ident(token.lexeme()) {
+            Some(Scope::Variable(ve)) => match ve.var_type() {
+                Type::Integer | Type::Float | Type::Bool | Type::String | Type::Custom(_) | Type::Enum(_) => {
+                    ExprParseStorage::Labelled(self.slot(ve.ident()), R14)
+                }
+                Type::IntegerArray(_) | Type::FloatArray(_) | Type::BoolArray(_) | Type::StringArray(_) | Type::CustomArray(_, _) | Type::EnumArray(_, _) => {
+                    let idx_res: ExprParseStorage = self.generate_array_indexing_code(ve.var_type(), var_node.children(), function_entry, symbols);
+                    match idx_res
+                    {
+                        Register(offs_reg) => {
+                            // for local variables, no need for pointer indirection
+                            self.local_array_element(ve.ident(), offs_reg)
+                        },
+                        _ => panic!("Array index was not stored in a register")
+                    }
+                }
+                _ => panic!(),
+            },
+            Some(Scope::FunctionParameter(pe)) => match pe.param_type() {
+                Type::Integer | Type::Float | Type::Bool | Type::String | Type::Custom(_) | Type::Enum(_) => {
+                    ExprParseStorage::Labelled(self.slot(pe.ident()), R14)
+                }
+                Type::IntegerArray(_) | Type::FloatArray(_) | Type::BoolArray(_) | Type::StringArray(_) | Type::CustomArray(_, _) | Type::EnumArray(_, _) => {
+                    let idx_res: ExprParseStorage = self.generate_array_indexing_code(pe.param_type(), var_node.children(), function_entry, symbols);
+                    match idx_res
+                    {
+                        Register(offs_reg) => {
+                            //for function params, the data is stored elsewhere so we need indirection
+                            ExprParseStorage::Pointer(self.slot(pe.ident()), offs_reg)
+                        },
+                        _ => panic!("Array index was not stored in a register")
+                    }
+                }
+                _ => panic!(),
+            },
+            _ if find_variable(token.lexeme(), function_entry, symbols).is_some() => {
+                // global variable, at its label in the data section
+                let ve = find_variable(token.lexeme(), function_entry, symbols).unwrap();
+                match ve.var_type() {
+                    Type::Integer | Type::Float | Type::Bool | Type::String | Type::Custom(_) | Type::Enum(_) => {
+                        ExprParseStorage::Labelled(global_variable_label(ve), R0)
+                    }
+                    Type::IntegerArray(_) | Type::FloatArray(_) | Type::BoolArray(_) | Type::StringArray(_) | Type::CustomArray(_, _) | Type::EnumArray(_, _) => {
+                        match self.generate_array_indexing_code(ve.var_type(), var_node.children(), function_entry, symbols) {
+                            Register(offs_reg) => ExprParseStorage::Labelled(global_variable_label(ve), offs_reg),
+                            _ => panic!("Array index was not stored in a register")
+                        }
+                    }
+                    _ => panic!(),
+                }
+            }
+            _ => {
+                // data member of the object the function was called on, which is pointed to by the frame
+                let class_entry = match function_entry.member_of().and_then(|class_ident| symbols.find_scope_by_ident(class_ident)) {
+                    Some(Scope::Class(ce)) => ce,
+                    _ => panic!("Undeclared variable {}", token.lexeme()),
+                };
+                let (offset, ve) = data_member_offset(class_entry, token.lexeme(), symbols)
+                    .expect("data member should have been validated");
+                let offs_reg = self.generate_data_member_offset_code(ve, offset, var_node.children(), function_entry, symbols);
+                ExprParseStorage::Pointer(self.frame.this().to_string(), offs_reg)
+            }
+        }
+    }
+
+    /// Computes the offset of a data member within its object into a register, array indices included.
+    fn generate_data_member_offset_code(
+        &mut self,
+        member: &VariableEntry,
+        offset: u32,
+        indices: &[Node],
+        function_entry: &FunctionEntry,
+        symbols: &SymbolTable,
+    ) -> Register {
+        match member.var_type() {
+            Type::Integer | Type::Float | Type::Bool | Type::String | Type::Custom(_) | Type::Enum(_) => {
+                let offs_reg = self.register_allocator.next_free_register();
+                self.generator.add_instruction(AddImmediate(offs_reg, R0, offset.to_string()));
+                offs_reg
+            }
+            Type::IntegerArray(_) | Type::FloatArray(_) | Type::BoolArray(_) | Type::StringArray(_) | Type::CustomArray(_, _) | Type::EnumArray(_, _) => {
+                match self.generate_array_indexing_code(member.var_type(), indices, function_entry, symbols) {
+                    Register(offs_reg) => {
+                        self.generator.add_instruction(AddImmediate(offs_reg, offs_reg, offset.to_string()));
+                        offs_reg
+                    }
+                    _ => panic!("Array index was not stored in a register"),
+                }
+            }
+            _ => panic!(),
+        }
     }
 
     fn generate_ternary_operation_code(
@@ -1067,9 +1550,8 @@ impl MoonGenerator {
         symbols: &SymbolTable,
     ) -> ExprParseStorage {
         self.generator.add_comment("Ternary Operator");
-        let (cond_label, then_label, else_label) = self.label_allocator.next_if_labels();
-        let temp_label = self.label_allocator.next_temp_label();
-        self.generator.add_tagged_resource(&temp_label, 4);
+        let (cond_label, else_label, endif_label) = self.label_allocator.next_if_labels();
+        let temp_label = self.next_temp();
 
         let cond_reg = self.register_allocator.next_free_register();
         self.generator.add_instruction(Substract(cond_reg, cond_reg, cond_reg));
@@ -1091,7 +1573,7 @@ impl MoonGenerator {
                 self.register_allocator.release_register(*r);
             }
             Pointer(label, offs) => {
-                self.generator.add_instruction(LoadWordLabel(cond_reg, R0, label.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(cond_reg, R14, label.clone())); // load address
                 self.generator.add_instruction(Add(cond_reg, cond_reg, *offs)); // add offset
                 self.generator.add_instruction(LoadWord(cond_reg, cond_reg, 0)); // load actual value at memory location
                 self.register_allocator.release_register(*offs);
@@ -1100,7 +1582,6 @@ impl MoonGenerator {
         self.generator.add_instruction(BranchIfZeroLabel(cond_reg, else_label.clone()));
         self.register_allocator.release_register(cond_reg);
 
-        self.generator.buffer_label(&then_label);
         let then_res = self.generate_expression_code(&ternary_op.children()[1], fe, symbols);
         match &then_res
         {
@@ -1108,33 +1589,35 @@ impl MoonGenerator {
                 let temp_reg = self.register_allocator.next_free_register();
                 self.generator.add_instruction(Substract(temp_reg, temp_reg, temp_reg));
                 self.generator.add_instruction(AddImmediate(temp_reg, temp_reg, imm.clone()));
-                self.generator.add_instruction(StoreWordLabel(temp_reg, R0, temp_label.clone()));
+                self.generator.add_instruction(StoreWordLabel(temp_reg, R14, temp_label.clone()));
                 self.register_allocator.release_register(temp_reg);
             }
             Labelled(label, offs) => {
                 let temp_reg = self.register_allocator.next_free_register();
                 self.generator.add_instruction(Substract(temp_reg, temp_reg, temp_reg));
                 self.generator.add_instruction(LoadWordLabel(temp_reg, *offs, label.clone()));
-                self.generator.add_instruction(StoreWordLabel(temp_reg, R0, temp_label.clone()));
+                self.generator.add_instruction(StoreWordLabel(temp_reg, R14, temp_label.clone()));
                 self.register_allocator.release_register(*offs);
                 self.register_allocator.release_register(temp_reg);
             }
             Register(r) => {
-                self.generator.add_instruction(StoreWordLabel(*r, R0, temp_label.clone()));
+                self.generator.add_instruction(StoreWordLabel(*r, R14, temp_label.clone()));
                 self.register_allocator.release_register(*r);
             }
             Pointer(label, offs) => {
                 let temp_reg = self.register_allocator.next_free_register();
                 self.generator.add_instruction(Substract(temp_reg, temp_reg, temp_reg));
-                self.generator.add_instruction(LoadWordLabel(temp_reg, R0, label.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(temp_reg, R14, label.clone())); // load address
                 self.generator.add_instruction(Add(temp_reg, temp_reg, *offs)); // add offset
                 self.generator.add_instruction(LoadWord(temp_reg, temp_reg, 0)); // load actual value at memory location
-                self.generator.add_instruction(StoreWordLabel(temp_reg, R0, temp_label.clone()));
+                self.generator.add_instruction(StoreWordLabel(temp_reg, R14, temp_label.clone()));
                 self.register_allocator.release_register(*offs);
                 self.register_allocator.release_register(temp_reg);
             }
         }
 
+        self.generator.add_instruction(JumpLabel(endif_label.clone()));
+
         self.generator.buffer_label(&else_label);
         let else_res = self.generate_expression_code(&ternary_op.children()[2], fe, symbols);
         match &else_res
@@ -1143,34 +1626,35 @@ impl MoonGenerator {
                 let temp_reg = self.register_allocator.next_free_register();
                 self.generator.add_instruction(Substract(temp_reg, temp_reg, temp_reg));
                 self.generator.add_instruction(AddImmediate(temp_reg, temp_reg, imm.clone()));
-                self.generator.add_instruction(StoreWordLabel(temp_reg, R0, temp_label.clone()));
+                self.generator.add_instruction(StoreWordLabel(temp_reg, R14, temp_label.clone()));
                 self.register_allocator.release_register(temp_reg);
             }
             Labelled(label, offs) => {
                 let temp_reg = self.register_allocator.next_free_register();
                 self.generator.add_instruction(Substract(temp_reg, temp_reg, temp_reg));
                 self.generator.add_instruction(LoadWordLabel(temp_reg, *offs, label.clone()));
-                self.generator.add_instruction(StoreWordLabel(temp_reg, R0, temp_label.clone()));
+                self.generator.add_instruction(StoreWordLabel(temp_reg, R14, temp_label.clone()));
                 self.register_allocator.release_register(*offs);
                 self.register_allocator.release_register(temp_reg);
             }
             Register(r) => {
-                self.generator.add_instruction(StoreWordLabel(*r, R0, temp_label.clone()));
+                self.generator.add_instruction(StoreWordLabel(*r, R14, temp_label.clone()));
                 self.register_allocator.release_register(*r);
             }
             Pointer(label, offs) => {
                 let temp_reg = self.register_allocator.next_free_register();
                 self.generator.add_instruction(Substract(temp_reg, temp_reg, temp_reg));
-                self.generator.add_instruction(LoadWordLabel(temp_reg, R0, label.clone())); // load address
+                self.generator.add_instruction(LoadWordLabel(temp_reg, R14, label.clone())); // load address
                 self.generator.add_instruction(Add(temp_reg, temp_reg, *offs)); // add offset
                 self.generator.add_instruction(LoadWord(temp_reg, temp_reg, 0)); // load actual value at memory location
-                self.generator.add_instruction(StoreWordLabel(temp_reg, R0, temp_label.clone()));
+                self.generator.add_instruction(StoreWordLabel(temp_reg, R14, temp_label.clone()));
                 self.register_allocator.release_register(*offs);
                 self.register_allocator.release_register(temp_reg);
             }
         }
+        self.generator.buffer_label(&endif_label);
         self.generator.add_comment(&format!(" ^^^ ?[ {:?} : {:?} : {:?} ] ^^^", cond_res, then_res, else_res));
-        return Labelled(temp_label, R0);
+        Labelled(temp_label, R14)
     }
 
     fn generate_arith_expr_code(
@@ -1182,12 +1666,25 @@ impl MoonGenerator {
         let mut postfix: Vec<&Node> = Vec::new();
         generate_arith_expr_postfix(arith_expr, &mut postfix);
         let mut eval_stack: Vec<ExprParseStorage> = Vec::new();
+        // the type of each value of the eval stack, an integer operand is promoted when the other one is a float
+        let mut type_stack: Vec<Type> = Vec::new();
         for postfix_node in postfix {
             if is_arith_operator(postfix_node) {
                 let op2 = eval_stack.pop().unwrap();
                 let op1 = eval_stack.pop().unwrap();
-                let temp = self.label_allocator.next_temp_label();
-                self.generator.add_tagged_resource(&temp, 4);
+                let op2_type = type_stack.pop().unwrap();
+                let op1_type = type_stack.pop().unwrap();
+                let op_type = promoted_type(&op1_type, &op2_type).expect("operands should have been validated");
+                type_stack.push(op_type.clone());
+                if op_type == Type::String {
+                    // + is the only arithmetic operator on strings
+                    eval_stack.push(self.generate_string_concatenation_code(op1, op2));
+                    continue;
+                }
+                let is_float = op_type == Type::Float;
+                let op1 = self.generate_conversion_code(op1, &op1_type, &op_type);
+                let op2 = self.generate_conversion_code(op2, &op2_type, &op_type);
+                let temp = self.next_temp();
                 let op1_reg = self.register_allocator.next_free_register();
                 self.generator
                     .add_instruction(Substract(op1_reg, op1_reg, op1_reg));
@@ -1214,7 +1711,7 @@ impl MoonGenerator {
                     (Labelled(llabel, loffs), Pointer(rlabel, roffs)) => {
                         self.generator.add_instruction(LoadWordLabel(op1_reg, loffs, llabel));
 
-                        self.generator.add_instruction(LoadWordLabel(op2_reg, R0, rlabel));
+                        self.generator.add_instruction(LoadWordLabel(op2_reg, R14, rlabel));
                         self.generator.add_instruction(Add(op2_reg, op2_reg, roffs));
                         self.generator.add_instruction(LoadWord(op2_reg, op2_reg, 0));
 
@@ -1237,7 +1734,7 @@ impl MoonGenerator {
                     (Immediate(limm), Pointer(rlabel, roffs)) => {
                         self.generator.add_instruction(AddImmediate(op1_reg, R0, limm));
 
-                        self.generator.add_instruction(LoadWordLabel(op2_reg, R0, rlabel));
+                        self.generator.add_instruction(LoadWordLabel(op2_reg, R14, rlabel));
                         self.generator.add_instruction(Add(op2_reg, op2_reg, roffs));
                         self.generator.add_instruction(LoadWord(op2_reg, op2_reg, 0));
 
@@ -1245,11 +1742,11 @@ impl MoonGenerator {
                     },
                     (Pointer(llabel, loffs), Pointer(rlabel, roffs)) =>
                     {
-                        self.generator.add_instruction(LoadWordLabel(op1_reg, R0, llabel));
+                        self.generator.add_instruction(LoadWordLabel(op1_reg, R14, llabel));
                         self.generator.add_instruction(Add(op1_reg, op1_reg, loffs));
                         self.generator.add_instruction(LoadWord(op1_reg, op1_reg, 0));
 
-                        self.generator.add_instruction(LoadWordLabel(op2_reg, R0, rlabel));
+                        self.generator.add_instruction(LoadWordLabel(op2_reg, R14, rlabel));
                         self.generator.add_instruction(Add(op2_reg, op2_reg, roffs));
                         self.generator.add_instruction(LoadWord(op2_reg, op2_reg, 0));
 
@@ -1258,7 +1755,7 @@ impl MoonGenerator {
                     },
                     (Pointer(llabel, loffs), Labelled(rlabel, roffs)) =>
                     {
-                        self.generator.add_instruction(LoadWordLabel(op1_reg, R0, llabel));
+                        self.generator.add_instruction(LoadWordLabel(op1_reg, R14, llabel));
                         self.generator.add_instruction(Add(op1_reg, op1_reg, loffs));
                         self.generator.add_instruction(LoadWord(op1_reg, op1_reg, 0));
 
@@ -1269,7 +1766,7 @@ impl MoonGenerator {
                     },
                     (Pointer(llabel, loffs), Immediate(rimm)) =>
                     {
-                        self.generator.add_instruction(LoadWordLabel(op1_reg, R0, llabel));
+                        self.generator.add_instruction(LoadWordLabel(op1_reg, R14, llabel));
                         self.generator.add_instruction(Add(op1_reg, op1_reg, loffs));
                         self.generator.add_instruction(LoadWord(op1_reg, op1_reg, 0));
 
@@ -1287,52 +1784,48 @@ impl MoonGenerator {
                         InternalNodeType::Sub => self
                             .generator
                             .add_instruction(Substract(op1_reg, op1_reg, op2_reg)),
-                        InternalNodeType::Or => self
-                            .generator
-                            .add_instruction(Or(op1_reg, op1_reg, op2_reg)),
+                        InternalNodeType::Mult if is_float => self.generate_routine_call("fmul", op1_reg, op2_reg),
+                        InternalNodeType::Div if is_float => self.generate_routine_call("fdiv", op1_reg, op2_reg),
                         InternalNodeType::Mult => self
                             .generator
                             .add_instruction(Multiply(op1_reg, op1_reg, op2_reg)),
                         InternalNodeType::Div => self
                             .generator
                             .add_instruction(Divide(op1_reg, op1_reg, op2_reg)),
-                        InternalNodeType::And => self
+                        InternalNodeType::Mod => self
                             .generator
-                            .add_instruction(And(op1_reg, op1_reg, op2_reg)),
+                            .add_instruction(Modulus(op1_reg, op1_reg, op2_reg)),
                         _ => panic!(),
                     },
                     _ => panic!(),
                 }
-                self.generator.add_instruction(StoreWordLabel(op1_reg, R0, temp.clone()));
-                eval_stack.push(ExprParseStorage::Labelled(temp, R0));
+                self.generator.add_instruction(StoreWordLabel(op1_reg, R14, temp.clone()));
+                eval_stack.push(ExprParseStorage::Labelled(temp, R14));
                 self.register_allocator.release_register(op1_reg);
                 self.register_allocator.release_register(op2_reg);
+            } else if is_unary_operator(postfix_node) {
+                // only negation for now, bools are 0/1 words so !x is x == 0
+                let operand = eval_stack.pop().unwrap();
+                type_stack.pop();
+                type_stack.push(Type::Bool);
+                let temp = self.next_temp();
+                let op_reg = self.load_into_register(operand);
+                self.generator.add_instruction(EqualImmediate(op_reg, op_reg, 0));
+                self.generator.add_instruction(StoreWordLabel(op_reg, R14, temp.clone()));
+                eval_stack.push(ExprParseStorage::Labelled(temp, R14));
+                self.register_allocator.release_register(op_reg);
             } else if is_arith_operand(postfix_node) {
+                type_stack.push(
+                    validate_arith_operand(postfix_node, function_entry, symbols)
+                        .expect("operand should have been validated"),
+                );
                 match postfix_node.val() {
                     Some(NodeVal::Internal(InternalNodeType::SignedFactor)) => {
-                        if let Some(NodeVal::Leaf(sign_token)) = postfix_node.children()[0].val() {
-                            let sign = sign_token.lexeme();
-                            match postfix_node.children()[1].val() {
-                                Some(NodeVal::Leaf(val_token)) => {
-                                    match val_token.token_type()
-                                    {
-                                        TokenType::IntegerLit => { eval_stack.push(Immediate(format!("{}{}", sign, val_token.lexeme()))); },
-                                        TokenType::FloatLit => { eval_stack.push(ExprParseStorage::Immediate(format!("{}{}", sign, val_token.lexeme().parse::<f32>().unwrap().to_bits().to_string()))); }, //FIXME
-                                        _ => panic!()
-                                    }
-                                }
-                                Some(NodeVal::Internal(InternalNodeType::ArithExpr)) => {
-                                    eval_stack.push(self.generate_arith_expr_code(
-                                        &postfix_node.children()[1],
-                                        function_entry,
-                                        symbols,
-                                    ));
-                                }
-                                _ => panic!(),
-                            }
-                        } else {
-                            panic!()
-                        }
+                        eval_stack.push(self.generate_signed_factor_code(
+                            postfix_node,
+                            function_entry,
+                            symbols,
+                        ));
                     }
                     Some(NodeVal::Internal(InternalNodeType::DotOp)) => {
                         eval_stack.push(self.generate_dot_operator_code(
@@ -1348,90 +1841,54 @@ impl MoonGenerator {
                             symbols,
                         ));
                     }
+                    Some(NodeVal::Internal(InternalNodeType::Expr)) => {
+                        eval_stack.push(self.generate_expression_code(
+                            postfix_node,
+                            function_entry,
+                            symbols,
+                        ));
+                    }
+                    Some(NodeVal::Internal(InternalNodeType::Cast)) => {
+                        let operand_type = validate_expr(&postfix_node.children()[1], function_entry, symbols)
+                            .expect("cast should have been validated");
+                        let operand = self.generate_expression_code(&postfix_node.children()[1], function_entry, symbols);
+                        let cast_type = type_stack.last().unwrap().clone();
+                        eval_stack.push(self.generate_conversion_code(operand, &operand_type, &cast_type));
+                    }
                     Some(NodeVal::Leaf(token)) => {
                         match token.token_type() {
                             TokenType::Id => {
-                                if let Some(Scope::Variable(ve)) =
-                                    function_entry.table().find_scope_by_ident(token.lexeme())
-                                {
-                                    let operand: ExprParseStorage = match ve.var_type()
-                                    {
-                                        Type::Integer
-                                        | Type::Float => ExprParseStorage::Labelled(format!("var_{}_{}", function_entry.ident(), ve.ident()), R0),
-                                        Type::IntegerArray(_) | Type::FloatArray(_) => {
-                                            let idx_res = self.generate_array_indexing_code(ve.var_type(), postfix_node, function_entry, symbols);
-                                            match idx_res
-                                            {
-                                                Register(r) => {
-                                                    ExprParseStorage::Labelled(format!("var_{}_{}", function_entry.ident(), ve.ident()), r)
-                                                }
-                                                _ => panic!()
-                                            }
-                                        }
-                                        Type::String
-                                        | Type::StringArray(_)
-                                        | Type::Custom(_)
-                                        | Type::CustomArray(_, _) => {
-                                            unimplemented!(
-                                                "Class and string types not implemented."
-                                            );
-                                        }
-                                        _ => panic!(),
-                                    };
-
-                                    eval_stack.push(operand);
-                                } else if let Some(Scope::FunctionParameter(pe)) =
-                                    function_entry.table().find_scope_by_ident(token.lexeme())
+                                if let Some(ce) =
+                                    find_constant(token.lexeme(), function_entry, symbols)
                                 {
-                                    let operand: ExprParseStorage = match pe.param_type()
-                                    {
-                                        Type::Integer
-                                        | Type::Float => ExprParseStorage::Labelled(format!("param_{}_{}", function_entry.ident(), pe.ident()), R0),
-                                        Type::IntegerArray(_) | Type::FloatArray(_) => {
-                                            let idx_res = self.generate_array_indexing_code(pe.param_type(), postfix_node, function_entry, symbols);
-                                            match idx_res
-                                            {
-                                                Register(r) => {
-                                                    ExprParseStorage::Pointer(format!("param_{}_{}", function_entry.ident(), pe.ident()), r)
-                                                }
-                                                _ => panic!()
-                                            }
-                                        }
-                                        Type::String
-                                        | Type::StringArray(_)
-                                        | Type::Custom(_)
-                                        | Type::CustomArray(_, _) => {
-                                            unimplemented!(
-                                                "Class and string types not implemented."
-                                            );
-                                        }
-                                        _ => panic!(),
-                                    };
-
-                                    eval_stack.push(operand);
-                                } else if let Some(Scope::Function(_)) =
-                                    symbols.find_scope_by_ident(token.lexeme())
-                                {
-                                    eval_stack.push(self.generate_function_call_code(
+                                    // constants are known at compile time, no need to load them
+                                    eval_stack.push(Immediate(ce.value().to_string()));
+                                } else {
+                                    // a variable, a data member or a function call
+                                    eval_stack.push(self.generate_dot_operand_code(
                                         postfix_node,
                                         function_entry,
                                         symbols,
                                     ));
-                                } else {
-                                    panic!()
                                 }
                             }
                             TokenType::IntegerLit => {
                                 eval_stack
                                     .push(ExprParseStorage::Immediate(token.lexeme().to_string()));
                             }
+                            TokenType::BoolLit => {
+                                // bools are stored as 0/1 words
+                                let val = if token.lexeme() == "true" { "1" } else { "0" };
+                                eval_stack.push(ExprParseStorage::Immediate(val.to_string()));
+                            }
                             TokenType::FloatLit => {
-                                log::warn!("FLOATING POINTS WILL NOT WORK PROPERLY");
-                                eval_stack
-                                    .push(ExprParseStorage::Immediate(token.lexeme().parse::<f32>().unwrap().to_bits().to_string())); //FIXME
+                                eval_stack.push(self.generate_constant_code(fixed_point(token.lexeme())));
                             }
                             TokenType::StringLit => {
-                                unimplemented!("String operations are not implemented.");
+                                // string literals are in the data section
+                                let label = self.label_allocator.next_string_label();
+                                self.generator.add_string_resource(&label, token.lexeme().trim_matches('"'));
+                                eval_stack.push(Labelled(label, R0));
                             }
                             _ => {
                                 log::error!("{:?}", token);
@@ -1447,16 +1904,76 @@ impl MoonGenerator {
         return eval_stack.pop().unwrap();
     }
 
-    fn generate_array_indexing_code(&mut self, var_type: &Type, var_node: &Node, fe: &FunctionEntry, symbols: &SymbolTable) -> ExprParseStorage {
+    /// Negation is `0 - x`, which also works for floats since they are fixed-point.
+    /// Literals and constants are folded.
+    fn generate_signed_factor_code(&mut self, signed_factor: &Node, fe: &FunctionEntry, symbols: &SymbolTable) -> ExprParseStorage {
+        let negate = match signed_factor.children()[0].val() {
+            Some(NodeVal::Leaf(sign_token)) => sign_token.token_type() == TokenType::Minus,
+            _ => panic!(),
+        };
+        let operand_node = &signed_factor.children()[1];
+        let operand = match operand_node.val() {
+            Some(NodeVal::Leaf(val_token)) => match val_token.token_type() {
+                TokenType::IntegerLit => Immediate(val_token.lexeme().to_string()),
+                TokenType::FloatLit => self.generate_constant_code(fixed_point(val_token.lexeme())),
+                _ => panic!(),
+            },
+            Some(NodeVal::Internal(InternalNodeType::Factor)) => {
+                let ident = &operand_node.children()[0];
+                match ident.val() {
+                    Some(NodeVal::Leaf(id_token)) => match find_constant(id_token.lexeme(), fe, symbols) {
+                        Some(ce) => Immediate(ce.value().to_string()),
+                        None => self.generate_dot_operand_code(ident, fe, symbols),
+                    },
+                    Some(NodeVal::Internal(InternalNodeType::DotOp)) => self.generate_dot_operator_code(ident, fe, symbols),
+                    _ => panic!(),
+                }
+            }
+            Some(NodeVal::Internal(InternalNodeType::SignedFactor)) => self.generate_signed_factor_code(operand_node, fe, symbols),
+            Some(NodeVal::Internal(InternalNodeType::Expr)) => self.generate_expression_code(operand_node, fe, symbols),
+            Some(NodeVal::Internal(InternalNodeType::TernaryOperation)) => self.generate_ternary_operation_code(operand_node, fe, symbols),
+            Some(NodeVal::Internal(InternalNodeType::Cast)) => {
+                let operand_type = validate_expr(&operand_node.children()[1], fe, symbols)
+                    .expect("cast should have been validated");
+                let cast_type = validate_arith_operand(operand_node, fe, symbols)
+                    .expect("cast should have been validated");
+                let value = self.generate_expression_code(&operand_node.children()[1], fe, symbols);
+                self.generate_conversion_code(value, &operand_type, &cast_type)
+            }
+            _ => panic!(),
+        };
+        if !negate {
+            return operand;
+        }
+
+        match operand {
+            Immediate(imm) => match imm.parse::<i32>() {
+                Ok(value) => self.generate_constant_code(-value),
+                Err(_) => panic!("immediate values are integers"),
+            },
+            operand => {
+                let reg = self.load_into_register(operand);
+                self.generator.add_instruction(Substract(reg, R0, reg));
+                self.store_in_temp(reg)
+            }
+        }
+    }
+
+    fn generate_array_indexing_code(&mut self, var_type: &Type, indices: &[Node], fe: &FunctionEntry, symbols: &SymbolTable) -> ExprParseStorage {
         self.generator.add_comment("`~ Computing array indices ~");
         let idx_reg = self.register_allocator.next_free_register();
         self.generator.add_instruction(Substract(idx_reg, idx_reg, idx_reg));
         match var_type
         {
             Type::IntegerArray(dim)
-            | Type::FloatArray(dim) => {
-                // step is 4 bytes
-                for (idx, indice_node) in var_node.children().iter().enumerate()
+            | Type::FloatArray(dim)
+            | Type::BoolArray(dim)
+            | Type::StringArray(dim)
+            | Type::CustomArray(_, dim)
+            | Type::EnumArray(_, dim) => {
+                // step is the size of an element, e.g. the size of the class for object arrays
+                let step = sizeof(&var_type.to_simple_type(), symbols) as i16;
+                for (idx, indice_node) in indices.iter().enumerate()
                 {
                     let col = if idx + 1 >= dim.len() { 1 } else { dim[idx + 1] };
                     match self.generate_arith_expr_code(&indice_node.children()[0], fe, symbols)
@@ -1465,7 +1982,8 @@ impl MoonGenerator {
                             let temp_reg = self.register_allocator.next_free_register();
                             self.generator.add_instruction(Substract(temp_reg, temp_reg, temp_reg));
                             self.generator.add_instruction(AddImmediate(temp_reg, R0, imm)); // indice
-                            self.generator.add_instruction(MultiplyImmediate(temp_reg, temp_reg, 4)); // sizeof
+                            self.generate_bounds_check_code(temp_reg, dim[idx], indice_node);
+                            self.generator.add_instruction(MultiplyImmediate(temp_reg, temp_reg, step)); // sizeof
                             self.generator.add_instruction(MultiplyImmediate(temp_reg, temp_reg, col as i16)); // col
                             self.generator.add_instruction(Add(idx_reg, idx_reg, temp_reg));
                             self.register_allocator.release_register(temp_reg);
@@ -1473,14 +1991,16 @@ impl MoonGenerator {
                         Labelled(label, offs) => {
                             let temp_reg = self.register_allocator.next_free_register();
                             self.generator.add_instruction(LoadWordLabel(temp_reg, offs, label)); // indice
-                            self.generator.add_instruction(MultiplyImmediate(temp_reg, temp_reg, 4)); // sizeof
+                            self.generate_bounds_check_code(temp_reg, dim[idx], indice_node);
+                            self.generator.add_instruction(MultiplyImmediate(temp_reg, temp_reg, step)); // sizeof
                             self.generator.add_instruction(MultiplyImmediate(temp_reg, temp_reg, col as i16)); // col
                             self.generator.add_instruction(Add(idx_reg, idx_reg, temp_reg));
                             self.register_allocator.release_register(offs);
                             self.register_allocator.release_register(temp_reg);
                         }
                         Register(r) => {
-                            self.generator.add_instruction(MultiplyImmediate(r, r, 4)); // sizeof
+                            self.generate_bounds_check_code(r, dim[idx], indice_node);
+                            self.generator.add_instruction(MultiplyImmediate(r, r, step)); // sizeof
                             self.generator.add_instruction(MultiplyImmediate(r, r, col as i16)); // col
                             self.generator.add_instruction(Add(idx_reg, idx_reg, r));
                             self.register_allocator.release_register(r);
@@ -1488,11 +2008,12 @@ impl MoonGenerator {
                         Pointer(label, offs) => {
                             let temp_reg = self.register_allocator.next_free_register();
                             self.generator.add_instruction(Substract(temp_reg, temp_reg, temp_reg));
-                            self.generator.add_instruction(LoadWordLabel(temp_reg, R0, label)); // load address
+                            self.generator.add_instruction(LoadWordLabel(temp_reg, R14, label)); // load address
                             self.generator.add_instruction(Add(temp_reg, temp_reg, offs)); // add offset
                             self.generator.add_instruction(LoadWord(temp_reg, temp_reg, 0)); // load actual value at memory location
+                            self.generate_bounds_check_code(temp_reg, dim[idx], indice_node);
 
-                            self.generator.add_instruction(MultiplyImmediate(temp_reg, temp_reg, 4)); // sizeof
+                            self.generator.add_instruction(MultiplyImmediate(temp_reg, temp_reg, step)); // sizeof
                             self.generator.add_instruction(MultiplyImmediate(temp_reg, temp_reg, col as i16)); // col
                             self.generator.add_instruction(Add(idx_reg, idx_reg, temp_reg));
 
@@ -1502,30 +2023,232 @@ impl MoonGenerator {
                     }
                 }
             }
-            Type::StringArray(_)
-            | Type::CustomArray(_, _) => {
-                unimplemented!("Class and string array indexing not implemented")
-            }
             _ => panic!()
         }
 
         Register(idx_reg)
     }
 
-    /// When jumping to a function, we store the return address from R15 into the next slot of the fn ptr stack
-    fn store_and_inc_fn_ptr(&mut self) {
-        self.generator
-            .add_instruction(AddImmediate(R14, R14, 4.to_string()));
-        self.generator
-            .add_instruction(StoreWordLabel(R15, R14, "fn_ptr_stack".to_string()));
+    /// Checks that the index in `index_reg` is within `0..size` when bounds checking is on,
+    /// otherwise jumps to the bounds error routine with the line of the index
+    fn generate_bounds_check_code(&mut self, index_reg: Register, size: u32, indice: &Node) {
+        // the size of array parameters isn't known
+        if !self.options.bounds_check || size == 0 {
+            return;
+        }
+        let (outofbounds_label, inbounds_label) = self.label_allocator.next_bounds_check_labels();
+        let temp_reg = self.register_allocator.next_free_register();
+        self.generator.add_instruction(LessImmediate(temp_reg, index_reg, 0));
+        self.generator.add_instruction(BranchIfNonZeroLabel(temp_reg, outofbounds_label.clone()));
+        self.generator.add_instruction(LessImmediate(temp_reg, index_reg, size as i16));
+        self.generator.add_instruction(BranchIfNonZeroLabel(temp_reg, inbounds_label.clone()));
+        self.register_allocator.release_register(temp_reg);
+
+        // the routine halts, so R1 doesn't need to be saved
+        self.generator.buffer_label(&outofbounds_label);
+        self.generator.add_instruction(AddImmediate(R1, R0, line_of(indice).to_string()));
+        self.generator.add_instruction(JumpLabel(BOUNDS_ERROR_ROUTINE.to_string()));
+        self.generator.buffer_label(&inbounds_label);
     }
 
-    /// When returning from a function, we pop & load the return address of the top of the fn ptr stack into R15 and jump
-    fn load_and_dec_fn_ptr(&mut self) {
-        self.generator
-            .add_instruction(LoadWordLabel(R15, R14, "fn_ptr_stack".to_string()));
-        self.generator
-            .add_instruction(SubstractImmediate(R14, R14, 4.to_string()));
+    /// Generates the routine reporting an out of bounds index: it writes the line in R1 and halts
+    fn generate_bounds_error_routine(&mut self) {
+        let message_label = format!("{}_msg", BOUNDS_ERROR_ROUTINE);
+        self.generator.add_string_resource(&message_label, "index out of bounds at line ");
+
+        self.generator.add_comment(" - - - - Runtime errors - - - -");
+        self.generator.buffer_label(BOUNDS_ERROR_ROUTINE);
+        // putstr only uses R1 and R2
+        self.generator.add_instruction(Add(R12, R0, R1));
+        self.generator.add_instruction(AddImmediate(R1, R0, message_label));
+        self.generator.add_instruction(JumpLinkLabel(R15, "putstr".to_string()));
+        self.generator.add_instruction(Add(R1, R0, R12));
+        self.generator.add_instruction(JumpLinkLabel(R15, "putint".to_string()));
+        self.generator.add_instruction(AddImmediate(R1, R0, "10".to_string()));
+        self.generator.add_instruction(PutCharacter(R1));
+        self.generator.add_instruction(Halt);
+    }
+
+    /// Returns the frame offset of a parameter or local variable of the function being generated
+    fn slot(&self, ident: &str) -> String {
+        self.frame.offset(ident).to_string()
+    }
+
+    /// Reserves a word for a temporary value in the frame of the function being generated
+    fn next_temp(&mut self) -> String {
+        self.frame.allocate(4).to_string()
+    }
+
+    /// Local arrays live in the frame, so the address of an element is relative to the frame pointer
+    fn local_array_element(&mut self, ident: &str, idx_reg: Register) -> ExprParseStorage {
+        self.generator.add_instruction(Add(idx_reg, idx_reg, R14));
+        Labelled(self.slot(ident), idx_reg)
+    }
+
+    /// Computes the address of a variable into a newly allocated register
+    fn address_of(&mut self, storage: ExprParseStorage) -> Register {
+        let reg = self.register_allocator.next_free_register();
+        match storage {
+            Labelled(label, offs) => {
+                self.generator.add_instruction(AddImmediate(reg, offs, label));
+                self.register_allocator.release_register(offs);
+            }
+            Pointer(label, offs) => {
+                self.generator.add_instruction(LoadWordLabel(reg, R14, label)); // load address
+                self.generator.add_instruction(Add(reg, reg, offs)); // add offset
+                self.register_allocator.release_register(offs);
+            }
+            Immediate(_) | Register(_) => panic!("Only variables have an address"),
+        }
+        reg
+    }
+
+    /// Stores the value of a register into a variable
+    fn store_into(&mut self, value_reg: Register, target: ExprParseStorage) {
+        match target {
+            Labelled(label, offs) => {
+                self.generator.add_instruction(StoreWordLabel(value_reg, offs, label));
+                self.register_allocator.release_register(offs);
+            }
+            target => {
+                let addr_reg = self.address_of(target);
+                self.generator.add_instruction(StoreWord(value_reg, addr_reg, 0));
+                self.register_allocator.release_register(addr_reg);
+            }
+        }
+    }
+
+    /// Points the objects of a variable at the vtable of their class, the variable being at `offset` from `base_reg`
+    fn generate_vtable_pointers_code(&mut self, base_reg: Register, offset: i32, var_type: &Type, symbols: &SymbolTable) {
+        let pointers = vtable_pointers(var_type, symbols);
+        if pointers.is_empty() {
+            return;
+        }
+        let vtable_reg = self.register_allocator.next_free_register();
+        for (pointer_offset, label) in pointers {
+            self.generator.add_instruction(AddImmediate(vtable_reg, R0, label));
+            self.generator
+                .add_instruction(StoreWord(vtable_reg, base_reg, (offset + pointer_offset as i32) as i16));
+        }
+        self.register_allocator.release_register(vtable_reg);
+    }
+
+    /// Concatenates two strings into a new temporary string
+    fn generate_string_concatenation_code(&mut self, lhs: ExprParseStorage, rhs: ExprParseStorage) -> ExprParseStorage {
+        // twice the capacity of a string so catstr can't overflow it, the result is then truncated to the capacity
+        let result = self.frame.allocate(2 * STRING_SIZE);
+        let dst_reg = self.register_allocator.next_free_register();
+        self.generator.add_instruction(AddImmediate(dst_reg, R14, result.to_string()));
+        let src_reg = self.address_of(lhs);
+        self.copy_words(dst_reg, src_reg, STRING_SIZE);
+        self.register_allocator.release_register(src_reg);
+        let rhs_reg = self.address_of(rhs);
+        self.generate_routine_call("catstr", dst_reg, rhs_reg);
+        self.register_allocator.release_register(dst_reg);
+        self.register_allocator.release_register(rhs_reg);
+        self.generator.add_instruction(StoreByte(R0, R14, (result + STRING_SIZE as i32 - 1) as i16));
+        Labelled(result.to_string(), R14)
+    }
+
+    /// Compares two strings for equality, the result is a bool in a register
+    fn generate_string_comparison_code(
+        &mut self,
+        rel_expr: &Node,
+        left: ExprParseStorage,
+        right: ExprParseStorage,
+    ) -> ExprParseStorage {
+        let routine = match rel_expr.val() {
+            Some(NodeVal::Internal(InternalNodeType::Equal)) => "eqstr",
+            Some(NodeVal::Internal(InternalNodeType::NotEqual)) => "neqstr",
+            _ => panic!("strings can only be compared for equality"),
+        };
+        self.generator.add_comment(&format!("{:?} {} {:?}", left, routine, right));
+        let lr = self.address_of(left);
+        let rr = self.address_of(right);
+        self.generate_routine_call(routine, lr, rr);
+        self.register_allocator.release_register(rr);
+        Register(lr)
+    }
+
+    /// Returns a constant as an immediate value, or in a temporary when it doesn't fit in the 16 bits of an immediate value
+    /// Converts a number between integer and float. Floats are fixed-point, so the value is scaled by `FLOAT_SCALE`,
+    /// converting a float to an integer truncates it. Other values are left as they are.
+    fn generate_conversion_code(&mut self, value: ExprParseStorage, from: &Type, to: &Type) -> ExprParseStorage {
+        match (from, to) {
+            (Type::Integer, Type::Float) => {
+                if let Immediate(imm) = &value {
+                    // literals and constants are converted at compile time
+                    if let Some(scaled) = imm.parse::<i32>().ok().and_then(|v| v.checked_mul(FLOAT_SCALE as i32)) {
+                        return self.generate_constant_code(scaled);
+                    }
+                }
+                let reg = self.load_into_register(value);
+                self.generator.add_instruction(MultiplyImmediate(reg, reg, FLOAT_SCALE));
+                self.store_in_temp(reg)
+            }
+            (Type::Float, Type::Integer) => {
+                let reg = self.load_into_register(value);
+                self.generator.add_instruction(DivideImmediate(reg, reg, FLOAT_SCALE));
+                self.store_in_temp(reg)
+            }
+            _ => value,
+        }
+    }
+
+    /// Stores a register into a new temporary and releases it
+    fn store_in_temp(&mut self, reg: Register) -> ExprParseStorage {
+        let temp = self.next_temp();
+        self.generator.add_instruction(StoreWordLabel(reg, R14, temp.clone()));
+        self.register_allocator.release_register(reg);
+        Labelled(temp, R14)
+    }
+
+    fn generate_constant_code(&mut self, value: i32) -> ExprParseStorage {
+        if (i16::MIN as i32..=i16::MAX as i32).contains(&value) {
+            return Immediate(value.to_string());
+        }
+        let reg = self.register_allocator.next_free_register();
+        // the upper 16 bits, then the lower bits one byte at a time since immediate values are signed
+        self.generator.add_instruction(AddImmediate(reg, R0, (value >> 16).to_string()));
+        self.generator.add_instruction(ShiftLeft(reg, 8));
+        self.generator.add_instruction(AddImmediate(reg, reg, ((value >> 8) & 0xFF).to_string()));
+        self.generator.add_instruction(ShiftLeft(reg, 8));
+        self.generator.add_instruction(AddImmediate(reg, reg, (value & 0xFF).to_string()));
+        let temp = self.next_temp();
+        self.generator.add_instruction(StoreWordLabel(reg, R14, temp.clone()));
+        self.register_allocator.release_register(reg);
+        Labelled(temp, R14)
+    }
+
+    /// Calls a routine of moon_vm/util.m or moon_vm/float.m on two operands, its result is put in `op1_reg`.
+    /// The routine takes its operands in R1 and R2 and uses the other registers freely, so the ones in use are saved.
+    fn generate_routine_call(&mut self, routine: &str, op1_reg: Register, op2_reg: Register) {
+        let mut saved_registers: Vec<(Register, String)> = Vec::new();
+        for reg in self.register_allocator.used_registers() {
+            let temp = self.next_temp();
+            self.generator.add_instruction(StoreWordLabel(reg, R14, temp.clone()));
+            saved_registers.push((reg, temp));
+        }
+        let saved = |reg: Register| saved_registers.iter().find(|(r, _)| *r == reg).unwrap().1.clone();
+        self.generator.add_instruction(LoadWordLabel(R1, R14, saved(op1_reg)));
+        self.generator.add_instruction(LoadWordLabel(R2, R14, saved(op2_reg)));
+        self.generator.add_instruction(JumpLinkLabel(R15, routine.to_string()));
+        // the result replaces the saved lhs, so restoring the registers moves it into op1_reg
+        self.generator.add_instruction(StoreWordLabel(R1, R14, saved(op1_reg)));
+
+        for (reg, temp) in saved_registers {
+            self.generator.add_instruction(LoadWordLabel(reg, R14, temp));
+        }
+    }
+
+    /// Copies `bytes` bytes, word by word, from the address in `src_reg` to the address in `dst_reg`
+    fn copy_words(&mut self, dst_reg: Register, src_reg: Register, bytes: u32) {
+        let word_reg = self.register_allocator.next_free_register();
+        for offset in (0..bytes).step_by(4) {
+            self.generator.add_instruction(LoadWord(word_reg, src_reg, offset as i16));
+            self.generator.add_instruction(StoreWord(word_reg, dst_reg, offset as i16));
+        }
+        self.register_allocator.release_register(word_reg);
     }
 }
 
@@ -42,6 +42,7 @@ pub enum Instruction {
     BranchIfZero(Register, i16),
     BranchIfZeroLabel(Register, String),
     BranchIfNonZero(Register, i16),
+    BranchIfNonZeroLabel(Register, String),
     Jump(i16),
     JumpLabel(String),
     JumpRegister(Register),
@@ -55,6 +56,8 @@ pub enum Instruction {
     Align,
     Org(u32),
     Res(u32),
+    Db(Vec<u8>),
+    Dw(Vec<String>),
     Comment(String)
 }
 
@@ -77,7 +80,7 @@ impl ToString for Instruction {
                 format!("sw {}({:?}),{:?}", label, rj, ri)
             }
             Instruction::StoreByte(ri, rj, k) => {
-                format!("sb {}({:?}),({:?})", k, rj, ri)
+                format!("sb {}({:?}),{:?}", k, rj, ri)
             }
             Instruction::Add(ri, rj, rk) => {
                 format!("add {:?},{:?},{:?}", ri, rj, rk)
@@ -91,8 +94,8 @@ impl ToString for Instruction {
             Instruction::Divide(ri, rj, rk) => {
                 format!("div {:?},{:?},{:?}", ri, rj, rk)
             }
-            Instruction::Modulus(_, _, _) => {
-                unimplemented!()
+            Instruction::Modulus(ri, rj, rk) => {
+                format!("mod {:?},{:?},{:?}", ri, rj, rk)
             }
             Instruction::And(ri, rj, rk) => {
                 format!("and {:?},{:?},{:?}", ri, rj, rk)
@@ -133,8 +136,8 @@ impl ToString for Instruction {
             Instruction::DivideImmediate(ri, rj, k) => {
                 format!("divi {:?},{:?},{}", ri, rj, k)
             }
-            Instruction::ModulusImmediate(_, _, _) => {
-                unimplemented!()
+            Instruction::ModulusImmediate(ri, rj, k) => {
+                format!("modi {:?},{:?},{}", ri, rj, k)
             }
             Instruction::AndImmediate(ri, rj, k) => {
                 format!("andi {:?},{:?},{}", ri, rj, k)
@@ -181,6 +184,9 @@ impl ToString for Instruction {
             Instruction::BranchIfNonZero(ri, k) => {
                 format!("bnz {:?},{}", ri, k)
             }
+            Instruction::BranchIfNonZeroLabel(ri, label) => {
+                format!("bnz {:?},{}", ri, label)
+            }
             Instruction::Jump(k) => {
                 format!("j {}", k)
             }
@@ -214,15 +220,16 @@ impl ToString for Instruction {
             Instruction::Org(k) => {
                 format!("org {}", k)
             }
-            /*Instruction::StoreWord(words) => {
-                format!("dw {}", words)
-            }
-            Instruction::StoreByte(bytes) => {
-                format!("dw {}", bytes)
-            }*/
             Instruction::Res(b) => {
                 format!("res {}", b)
             }
+            Instruction::Db(bytes) => {
+                let bytes: Vec<String> = bytes.iter().map(|b| b.to_string()).collect();
+                format!("db {}", bytes.join(","))
+            }
+            Instruction::Dw(words) => {
+                format!("dw {}", words.join(","))
+            }
             Instruction::Comment(comment) => {
                 format!("%{}", comment)
             }
@@ -259,6 +266,6 @@ pub enum Register {
     R11,
     R12,
     R13,
-    R14, // fn ptr offset
+    R14, // frame pointer
     R15, // is used at the beginning/end of a function to jump back to the callee
 }
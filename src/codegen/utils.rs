@@ -1,10 +1,38 @@
 use crate::codegen::generator::CodeGenOutput;
 use crate::parser::ast::{InternalNodeType, Node, NodeVal};
-use crate::semantics::symbol_table::{Scope, SymbolTable, Type};
+use crate::semantics::symbol_table::{ClassEntry, FunctionEntry, Scope, SymbolTable, Type, VariableEntry};
 use std::fs::OpenOptions;
 use std::io;
 use std::io::{BufWriter, Write};
-use crate::semantics::utils::get_class_hierarchy_data_members;
+use crate::semantics::utils::{get_ancestors_for_class, get_class_hierarchy_data_members, get_class_hierarchy_functions, get_class_method_table};
+
+/// Floats are fixed-point numbers with three decimal digits, stored as their value times `FLOAT_SCALE`.
+/// See moon_vm/float.m for the routines multiplying, dividing and printing them.
+pub const FLOAT_SCALE: i16 = 1000;
+
+/// Returns the fixed-point representation of a float literal
+pub fn fixed_point(lexeme: &str) -> i32 {
+    (lexeme.parse::<f64>().unwrap() * FLOAT_SCALE as f64).round() as i32
+}
+
+/// Strings are stored inline with a fixed capacity, null terminator included.
+/// They are copied like objects, longer literals and concatenations are truncated.
+pub const STRING_SIZE: u32 = 64;
+
+/// Switch statements jump through a table in the data section when they have at least this many cases,
+/// and the cases fill at least half of the range between the smallest and largest label
+pub const JUMP_TABLE_MIN_CASES: usize = 4;
+
+/// Tells if a switch statement with the given case labels should use a jump table instead of a compare chain
+pub fn use_jump_table(case_values: &[i32]) -> bool {
+    match (case_values.iter().min(), case_values.iter().max()) {
+        (Some(&min), Some(&max)) => {
+            let range = max as i64 - min as i64 + 1;
+            case_values.len() >= JUMP_TABLE_MIN_CASES && range <= 2 * case_values.len() as i64
+        }
+        _ => false,
+    }
+}
 
 /// Returns the size of the given type in bytes
 pub fn sizeof(t: &Type, symbols: &SymbolTable) -> u32 {
@@ -15,11 +43,25 @@ pub fn sizeof(t: &Type, symbols: &SymbolTable) -> u32 {
             if size == 0 { 4 } else { size }
         },
         Type::Float => 4,
-        Type::FloatArray(dim) => 8u32 * dim.iter().product::<u32>(),
-        Type::String => {
-            unimplemented!("Sizeof for string is not implemented")
-        }
-        Type::StringArray(dim) => 0u32 * dim.iter().product::<u32>(),
+        Type::Bool => 4,
+        Type::Enum(_) => 4,
+        Type::BoolArray(dim) => {
+            let size = 4u32 * dim.iter().product::<u32>();
+            if size == 0 { 4 } else { size }
+        },
+        Type::FloatArray(dim) => {
+            let size = 4u32 * dim.iter().product::<u32>();
+            if size == 0 { 4 } else { size }
+        },
+        Type::EnumArray(_, dim) => {
+            let size = 4u32 * dim.iter().product::<u32>();
+            if size == 0 { 4 } else { size }
+        },
+        Type::String => STRING_SIZE,
+        Type::StringArray(dim) => {
+            let size = STRING_SIZE * dim.iter().product::<u32>();
+            if size == 0 { STRING_SIZE } else { size }
+        },
         Type::Custom(ident) => {
             let mut temp_size: u32 = 0;
             if let Some(Scope::Class(ce)) = symbols.find_scope_by_ident(ident) {
@@ -28,6 +70,9 @@ pub fn sizeof(t: &Type, symbols: &SymbolTable) -> u32 {
                 {
                     temp_size += sizeof(member.var_type(), symbols);
                 }
+                if has_vtable(ce, symbols) {
+                    temp_size += 4;
+                }
             } else {
                 panic!()
             }
@@ -41,6 +86,9 @@ pub fn sizeof(t: &Type, symbols: &SymbolTable) -> u32 {
                 {
                     temp_size += sizeof(member.var_type(), symbols);
                 }
+                if has_vtable(ce, symbols) {
+                    temp_size += 4;
+                }
             } else {
                 panic!()
             }
@@ -51,6 +99,169 @@ pub fn sizeof(t: &Type, symbols: &SymbolTable) -> u32 {
     size
 }
 
+/// Returns the data members of an object of the given class along with their offset.
+/// The data members of ancestors come first, so inherited member functions find them where they expect them.
+/// They follow the vtable pointer when the class has one.
+pub fn class_layout<'a>(
+    class_entry: &'a ClassEntry,
+    symbols: &'a SymbolTable,
+) -> Vec<(u32, &'a VariableEntry)> {
+    let (mut classes, _) = get_ancestors_for_class(class_entry, symbols);
+    classes.reverse();
+    classes.push(class_entry);
+
+    let mut layout: Vec<(u32, &VariableEntry)> = Vec::new();
+    let mut offset: u32 = if has_vtable(class_entry, symbols) { 4 } else { 0 };
+    for class in classes {
+        for scope in class.table().scopes() {
+            if let Scope::Variable(ve) = scope {
+                layout.push((offset, ve));
+                offset += sizeof(ve.var_type(), symbols);
+            }
+        }
+    }
+    layout
+}
+
+/// Returns the offset of a data member within an object of the given class, along with its entry.
+/// A data member hides the ones with the same identifier in its ancestors.
+pub fn data_member_offset<'a>(
+    class_entry: &'a ClassEntry,
+    ident: &str,
+    symbols: &'a SymbolTable,
+) -> Option<(u32, &'a VariableEntry)> {
+    class_layout(class_entry, symbols)
+        .into_iter()
+        .rev()
+        .find(|(_, member)| member.ident() == ident)
+}
+
+/// Returns true if the objects of a class hold a pointer to the vtable of their class, at offset 0.
+/// Only the hierarchies where a member function is overridden need dynamic dispatch. All their classes get the pointer,
+/// so the data members of an object are where the member functions of its ancestors expect them.
+pub fn has_vtable(class_entry: &ClassEntry, symbols: &SymbolTable) -> bool {
+    let roots = hierarchy_roots(class_entry, symbols);
+    symbols.scopes().iter().any(|scope| match scope {
+        Scope::Class(ce) => {
+            let (functions, _) = get_class_hierarchy_functions(ce, symbols);
+            // overriding functions share their slot of the method table
+            get_class_method_table(ce, symbols).len() < functions.len()
+                && hierarchy_roots(ce, symbols).iter().any(|root| roots.contains(root))
+        }
+        _ => false,
+    })
+}
+
+/// Returns the classes at the top of the hierarchy of a class
+fn hierarchy_roots<'a>(class_entry: &'a ClassEntry, symbols: &'a SymbolTable) -> Vec<&'a str> {
+    let (mut classes, _) = get_ancestors_for_class(class_entry, symbols);
+    classes.push(class_entry);
+    classes
+        .into_iter()
+        .filter(|ce| ce.inherits().is_empty())
+        .map(|ce| ce.ident())
+        .collect()
+}
+
+/// Returns the label of the vtable of a class
+pub fn vtable_label(class_entry: &ClassEntry) -> String {
+    format!("vt_{}", class_entry.ident())
+}
+
+/// Returns the label of a global variable, which is laid out in the data section
+pub fn global_variable_label(ve: &VariableEntry) -> String {
+    format!("gv_{}", ve.ident())
+}
+
+/// Returns the slot of a member function in the vtables of the classes inheriting it, if its class has a vtable
+pub fn vtable_slot(fe: &FunctionEntry, symbols: &SymbolTable) -> Option<usize> {
+    let class_entry = match fe.member_of().and_then(|ident| symbols.find_scope_by_ident(ident)) {
+        Some(Scope::Class(ce)) if has_vtable(ce, symbols) => ce,
+        _ => return None,
+    };
+    get_class_method_table(class_entry, symbols)
+        .iter()
+        .position(|slot| slot.ident() == fe.ident() && slot.type_sig().0 == fe.type_sig().0)
+}
+
+/// Returns the offsets of the vtable pointers within a value of the given type, along with the vtable they point to.
+/// Objects held by data members and object arrays have their own.
+pub fn vtable_pointers(t: &Type, symbols: &SymbolTable) -> Vec<(u32, String)> {
+    match t {
+        Type::Custom(ident) => {
+            let class_entry = match symbols.find_scope_by_ident(ident) {
+                Some(Scope::Class(ce)) => ce,
+                _ => panic!(),
+            };
+            let mut pointers: Vec<(u32, String)> = Vec::new();
+            if has_vtable(class_entry, symbols) {
+                pointers.push((0, vtable_label(class_entry)));
+            }
+            for (offset, member) in class_layout(class_entry, symbols) {
+                for (member_offset, label) in vtable_pointers(member.var_type(), symbols) {
+                    pointers.push((offset + member_offset, label));
+                }
+            }
+            pointers
+        }
+        Type::CustomArray(ident, dim) => {
+            let element = Type::Custom(ident.clone());
+            let size = sizeof(&element, symbols);
+            let element_pointers = vtable_pointers(&element, symbols);
+            let mut pointers: Vec<(u32, String)> = Vec::new();
+            for i in 0..dim.iter().product::<u32>() {
+                for (offset, label) in &element_pointers {
+                    pointers.push((i * size + offset, label.clone()));
+                }
+            }
+            pointers
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the name the labels of a function are built from (fn_ and its retblock).
+/// Free functions without parameters keep their identifier. The others are mangled so overloads get distinct labels:
+/// identifiers are prefixed by their length, a member function is `{class}_{ident}`, and each parameter type is appended,
+/// e.g. `Point::move(integer, float[])` -> `5Point_4moveifA0_f`.
+pub fn function_label(fe: &FunctionEntry) -> String {
+    let params = &fe.type_sig().0;
+    if fe.member_of().is_none() && params.is_empty() {
+        return fe.ident().to_string();
+    }
+
+    let mut label = String::new();
+    if let Some(class_ident) = fe.member_of() {
+        label.push_str(&format!("{}{}_", class_ident.len(), class_ident));
+    }
+    label.push_str(&format!("{}{}", fe.ident().len(), fe.ident()));
+    for param in params {
+        label.push_str(&mangle_type(param));
+    }
+    label
+}
+
+fn mangle_type(t: &Type) -> String {
+    match t {
+        Type::Integer => "i".to_string(),
+        Type::Float => "f".to_string(),
+        Type::String => "s".to_string(),
+        Type::Bool => "b".to_string(),
+        Type::Custom(ident) | Type::Enum(ident) => format!("{}{}", ident.len(), ident),
+        Type::Void => "v".to_string(),
+        Type::IntegerArray(dim)
+        | Type::FloatArray(dim)
+        | Type::StringArray(dim)
+        | Type::BoolArray(dim)
+        | Type::CustomArray(_, dim)
+        | Type::EnumArray(_, dim) => {
+            let mut mangled: String = dim.iter().map(|d| format!("A{}_", d)).collect();
+            mangled.push_str(&mangle_type(&t.to_simple_type()));
+            mangled
+        }
+    }
+}
+
 pub fn generate_arith_expr_postfix<'a>(arith_expr: &'a Node, acc: &mut Vec<&'a Node>) {
     assert_eq!(
         arith_expr.val(),
@@ -64,6 +275,8 @@ fn post_order_traversal<'a>(root: &'a Node, acc: &mut Vec<&'a Node>) {
         Some(NodeVal::Internal(InternalNodeType::DotOp))
         | Some(NodeVal::Internal(InternalNodeType::SignedFactor))
         | Some(NodeVal::Internal(InternalNodeType::TernaryOperation))
+        | Some(NodeVal::Internal(InternalNodeType::Cast))
+        | Some(NodeVal::Internal(InternalNodeType::Expr))
         | Some(NodeVal::Leaf(_)) => {
             acc.push(root);
             return;
@@ -80,7 +293,7 @@ fn post_order_traversal<'a>(root: &'a Node, acc: &mut Vec<&'a Node>) {
         post_order_traversal(&root.children()[1], acc);
     }
     // add to accumulator
-    if is_arith_operator(root) || is_arith_operand(root) {
+    if is_arith_operator(root) || is_unary_operator(root) || is_arith_operand(root) {
         acc.push(root)
     }
 }
@@ -90,22 +303,27 @@ pub fn is_arith_operator(node: &Node) -> bool {
         Some(NodeVal::Internal(internal)) => match internal {
             InternalNodeType::Add
             | InternalNodeType::Sub
-            | InternalNodeType::Or
             | InternalNodeType::Mult
             | InternalNodeType::Div
-            | InternalNodeType::And => true,
+            | InternalNodeType::Mod => true,
             _ => false,
         },
         _ => false,
     }
 }
 
+pub fn is_unary_operator(node: &Node) -> bool {
+    matches!(node.val(), Some(NodeVal::Internal(InternalNodeType::Negation)))
+}
+
 pub fn is_arith_operand(node: &Node) -> bool {
     match node.val() {
         Some(NodeVal::Internal(internal)) => match internal {
             InternalNodeType::SignedFactor
             | InternalNodeType::DotOp
-            | InternalNodeType::TernaryOperation => true,
+            | InternalNodeType::TernaryOperation
+            | InternalNodeType::Cast
+            | InternalNodeType::Expr => true,
             _ => false,
         },
         Some(NodeVal::Leaf(_)) => true,
@@ -17,6 +17,8 @@ lazy_static! {
         TokenType::IntegerType,
         TokenType::FloatType,
         TokenType::StringType,
+        TokenType::BoolType,
+        TokenType::BoolLit,
         TokenType::Void,
         TokenType::Public,
         TokenType::Private,
@@ -24,13 +26,20 @@ lazy_static! {
         TokenType::Var,
         TokenType::Class,
         TokenType::While,
+        TokenType::For,
+        TokenType::Switch,
+        TokenType::Case,
+        TokenType::Default,
         TokenType::Read,
         TokenType::Write,
         TokenType::Return,
         TokenType::Main,
         TokenType::Inherits,
         TokenType::Break,
-        TokenType::Continue
+        TokenType::Continue,
+        TokenType::Const,
+        TokenType::Import,
+        TokenType::Enum
     ];
     #[doc(hidden)]
     pub static ref OP_PUNCT_TOKENS: Vec<TokenType> = vec![
@@ -44,6 +53,7 @@ lazy_static! {
         TokenType::Minus,
         TokenType::Mult,
         TokenType::Div,
+        TokenType::Mod,
         TokenType::Assignment,
         TokenType::Or,
         TokenType::And,
@@ -66,12 +76,15 @@ lazy_static! {
         TokenType::IntegerLit,
         TokenType::FloatLit,
         TokenType::StringLit,
+        TokenType::BoolLit,
         TokenType::If,
         TokenType::Then,
         TokenType::Else,
         TokenType::IntegerType,
         TokenType::FloatType,
         TokenType::StringType,
+        TokenType::BoolType,
+        TokenType::BoolLit,
         TokenType::Void,
         TokenType::Public,
         TokenType::Private,
@@ -79,6 +92,10 @@ lazy_static! {
         TokenType::Var,
         TokenType::Class,
         TokenType::While,
+        TokenType::For,
+        TokenType::Switch,
+        TokenType::Case,
+        TokenType::Default,
         TokenType::Read,
         TokenType::Write,
         TokenType::Return,
@@ -86,6 +103,9 @@ lazy_static! {
         TokenType::Inherits,
         TokenType::Break,
         TokenType::Continue,
+        TokenType::Const,
+        TokenType::Import,
+        TokenType::Enum,
         TokenType::EqEq,
         TokenType::NotEq,
         TokenType::GreaterThan,
@@ -96,6 +116,7 @@ lazy_static! {
         TokenType::Minus,
         TokenType::Mult,
         TokenType::Div,
+        TokenType::Mod,
         TokenType::Assignment,
         TokenType::Or,
         TokenType::Period,
@@ -121,6 +142,8 @@ pub enum TokenType {
     IntegerLit,
     FloatLit,
     StringLit,
+    /// true | false
+    BoolLit,
 
     // Operators & punctuation
     /// ==
@@ -143,6 +166,8 @@ pub enum TokenType {
     Mult,
     /// /
     Div,
+    /// %
+    Mod,
     /// =
     Assignment,
     /// |
@@ -183,6 +208,7 @@ pub enum TokenType {
     IntegerType,
     FloatType,
     StringType,
+    BoolType,
     Void,
     Public,
     Private,
@@ -190,6 +216,10 @@ pub enum TokenType {
     Var,
     Class,
     While,
+    For,
+    Switch,
+    Case,
+    Default,
     Read,
     Write,
     Return,
@@ -197,6 +227,9 @@ pub enum TokenType {
     Inherits,
     Break,
     Continue,
+    Const,
+    Import,
+    Enum,
 
     // comments
     /// //
@@ -215,6 +248,7 @@ impl TokenType {
             TokenType::IntegerLit => &*INT_LIT,
             TokenType::FloatLit => &*FLOAT_LIT,
             TokenType::StringLit => &*STRING_LIT,
+            TokenType::BoolLit => &*BOOL_LIT,
             TokenType::EqEq => &*EQEQ,
             TokenType::NotEq => &*NOTEQ,
             TokenType::GreaterThan => &*GT,
@@ -225,6 +259,7 @@ impl TokenType {
             TokenType::Minus => &*MINUS,
             TokenType::Mult => &*MULT,
             TokenType::Div => &*FSLASH,
+            TokenType::Mod => &*PERCENT,
             TokenType::Assignment => &*ASSIGN,
             TokenType::Or => &*OR,
             TokenType::And => &*AND,
@@ -247,6 +282,7 @@ impl TokenType {
             TokenType::IntegerType => &*INT_T,
             TokenType::FloatType => &*FLOAT_T,
             TokenType::StringType => &*STRING_T,
+            TokenType::BoolType => &*BOOL_T,
             TokenType::Void => &*VOID,
             TokenType::Public => &*PUBLIC,
             TokenType::Private => &*PRIVATE,
@@ -254,6 +290,10 @@ impl TokenType {
             TokenType::Var => &*VAR,
             TokenType::Class => &*CLASS,
             TokenType::While => &*WHILE,
+            TokenType::For => &*FOR,
+            TokenType::Switch => &*SWITCH,
+            TokenType::Case => &*CASE,
+            TokenType::Default => &*DEFAULT,
             TokenType::Read => &*READ,
             TokenType::Write => &*WRITE,
             TokenType::Return => &*RETURN,
@@ -261,11 +301,60 @@ impl TokenType {
             TokenType::Inherits => &*INHERITS,
             TokenType::Break => &*BREAK,
             TokenType::Continue => &*CONTINUE,
+            TokenType::Const => &*CONST,
+            TokenType::Import => &*IMPORT,
+            TokenType::Enum => &*ENUM,
             TokenType::Error(_) => &*ERROR,
             TokenType::LineComment => &*LINE_COMMENT,
             TokenType::MultilineComment => &*MULTILINE_COMMENT,
         }
     }
+
+    /// Returns a human readable description of this token type, for diagnostics
+    pub fn describe(&self) -> String {
+        match self {
+            TokenType::Id => String::from("identifier"),
+            TokenType::IntegerLit => String::from("integer literal"),
+            TokenType::FloatLit => String::from("float literal"),
+            TokenType::StringLit => String::from("string literal"),
+            TokenType::BoolLit => String::from("boolean literal"),
+            TokenType::EqEq => String::from("'=='"),
+            TokenType::NotEq => String::from("'<>'"),
+            TokenType::GreaterThan => String::from("'>'"),
+            TokenType::LessThan => String::from("'<'"),
+            TokenType::GreaterEqualThan => String::from("'>='"),
+            TokenType::LessEqualThan => String::from("'<='"),
+            TokenType::Plus => String::from("'+'"),
+            TokenType::Minus => String::from("'-'"),
+            TokenType::Mult => String::from("'*'"),
+            TokenType::Div => String::from("'/'"),
+            TokenType::Mod => String::from("'%'"),
+            TokenType::Assignment => String::from("'='"),
+            TokenType::Or => String::from("'|'"),
+            TokenType::And => String::from("'&'"),
+            TokenType::Bang => String::from("'!'"),
+            TokenType::Question => String::from("'?'"),
+            TokenType::OpenParen => String::from("'('"),
+            TokenType::CloseParen => String::from("')'"),
+            TokenType::OpenCurly => String::from("'{'"),
+            TokenType::CloseCurly => String::from("'}'"),
+            TokenType::OpenSquare => String::from("'['"),
+            TokenType::CloseSquare => String::from("']'"),
+            TokenType::SemiColon => String::from("';'"),
+            TokenType::Comma => String::from("','"),
+            TokenType::Period => String::from("'.'"),
+            TokenType::Colon => String::from("':'"),
+            TokenType::DoubleColon => String::from("'::'"),
+            TokenType::IntegerType => String::from("'integer'"),
+            TokenType::FloatType => String::from("'float'"),
+            TokenType::StringType => String::from("'string'"),
+            TokenType::BoolType => String::from("'bool'"),
+            TokenType::LineComment => String::from("line comment"),
+            TokenType::MultilineComment => String::from("multiline comment"),
+            TokenType::Error(invalid) => invalid.to_string().to_lowercase(),
+            keyword => format!("'{}'", format!("{:?}", keyword).to_lowercase()),
+        }
+    }
 }
 
 impl Display for TokenType {
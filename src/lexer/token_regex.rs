@@ -8,7 +8,9 @@ lazy_static! {
     pub static ref INT_LIT: Regex = Regex::new(r"^(([1-9]\d*)|0)$").unwrap();
     pub static ref FLOAT_LIT: Regex =
         Regex::new(r"^((([1-9]\d*)|0)(\.(\d*[1-9]|0))(e(\+|\-)?(([1-9]\d*)|0))?)$").unwrap();
-    pub static ref STRING_LIT: Regex = Regex::new("\"([[:alpha:]]|[0-9]|_|\\s)*\"").unwrap();
+    pub static ref STRING_LIT: Regex =
+        Regex::new("\"([[:alpha:]]|[0-9]|_|\\.|/|-|\\s)*\"").unwrap();
+    pub static ref BOOL_LIT: Regex = Regex::new("^(true|false)$").unwrap();
     pub static ref EQEQ: Regex = Regex::new("^(==)$").unwrap();
     pub static ref NOTEQ: Regex = Regex::new("^(<>)$").unwrap();
     pub static ref LT: Regex = Regex::new("^(<)$").unwrap();
@@ -19,6 +21,7 @@ lazy_static! {
     pub static ref MINUS: Regex = Regex::new(r"^(\-)$").unwrap();
     pub static ref MULT: Regex = Regex::new(r"^(\*)$").unwrap();
     pub static ref FSLASH: Regex = Regex::new("^(/)$").unwrap();
+    pub static ref PERCENT: Regex = Regex::new("^(%)$").unwrap();
     pub static ref ASSIGN: Regex = Regex::new("^(=)$").unwrap();
     pub static ref OR: Regex = Regex::new(r"^(\|)$").unwrap();
     pub static ref AND: Regex = Regex::new("^(&)$").unwrap();
@@ -45,6 +48,7 @@ lazy_static! {
     pub static ref INT_T: Regex = Regex::new("^(integer)$").unwrap();
     pub static ref FLOAT_T: Regex = Regex::new("^(float)$").unwrap();
     pub static ref STRING_T: Regex = Regex::new("^(string)$").unwrap();
+    pub static ref BOOL_T: Regex = Regex::new("^(bool)$").unwrap();
     pub static ref VOID: Regex = Regex::new("^(void)$").unwrap();
     pub static ref PUBLIC: Regex = Regex::new("^(public)$").unwrap();
     pub static ref PRIVATE: Regex = Regex::new("^(private)$").unwrap();
@@ -52,6 +56,10 @@ lazy_static! {
     pub static ref VAR: Regex = Regex::new("^(var)$").unwrap();
     pub static ref CLASS: Regex = Regex::new("^(class)$").unwrap();
     pub static ref WHILE: Regex = Regex::new("^(while)$").unwrap();
+    pub static ref FOR: Regex = Regex::new("^(for)$").unwrap();
+    pub static ref SWITCH: Regex = Regex::new("^(switch)$").unwrap();
+    pub static ref CASE: Regex = Regex::new("^(case)$").unwrap();
+    pub static ref DEFAULT: Regex = Regex::new("^(default)$").unwrap();
     pub static ref READ: Regex = Regex::new("^(read)$").unwrap();
     pub static ref WRITE: Regex = Regex::new("^(write)$").unwrap();
     pub static ref RETURN: Regex = Regex::new("^(return)$").unwrap();
@@ -59,6 +67,9 @@ lazy_static! {
     pub static ref INHERITS: Regex = Regex::new("^(inherits)$").unwrap();
     pub static ref BREAK: Regex = Regex::new("^(break)$").unwrap();
     pub static ref CONTINUE: Regex = Regex::new("^(continue)$").unwrap();
+    pub static ref CONST: Regex = Regex::new("^(const)$").unwrap();
+    pub static ref IMPORT: Regex = Regex::new("^(import)$").unwrap();
+    pub static ref ENUM: Regex = Regex::new("^(enum)$").unwrap();
     pub static ref LINE_COMMENT: Regex = Regex::new("^(//[^\r\n]*)").unwrap();
     pub static ref MULTILINE_COMMENT: Regex = RegexBuilder::new(r"/\*.*?\*/")
         .dot_matches_new_line(true)
@@ -128,6 +139,31 @@ mod test {
         );
     }
 
+    #[test]
+    fn import_match() {
+        assert!(IMPORT.is_match("import"));
+        assert!(!IMPORT.is_match("imports"));
+        assert_eq!(
+            STRING_LIT.find("\"lib/geo-metry.src\"").unwrap().as_str(),
+            "\"lib/geo-metry.src\""
+        );
+    }
+
+    #[test]
+    fn enum_match() {
+        assert!(ENUM.is_match("enum"));
+        assert!(!ENUM.is_match("enums"));
+        assert!(!ENUM.is_match("Enum"));
+    }
+
+    #[test]
+    fn bool_lit_match() {
+        assert!(BOOL_LIT.is_match("true"));
+        assert!(BOOL_LIT.is_match("false"));
+        assert!(!BOOL_LIT.is_match("trueish"));
+        assert!(!BOOL_LIT.is_match("True"));
+    }
+
     #[test]
     fn operator_match() {
         assert!(ASSIGN.is_match("="));
@@ -143,6 +179,7 @@ mod test {
         assert!(MINUS.is_match("-"));
         assert!(MULT.is_match("*"));
         assert!(FSLASH.is_match("/"));
+        assert!(PERCENT.is_match("%"));
         assert!(OR.is_match("|"));
         assert!(AND.is_match("&"));
         assert!(BANG.is_match("!"));
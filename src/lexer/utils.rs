@@ -7,7 +7,7 @@ use regex::Regex;
 #[cfg(windows)]
 pub(crate) const LINE_ENDINGS: &str = "\r\n";
 #[cfg(not(windows))]
-const LINE_ENDINGS: &str = "\n";
+pub(crate) const LINE_ENDINGS: &str = "\n";
 
 lazy_static! {
     #[doc(hidden)]
@@ -21,7 +21,7 @@ pub mod lexer {
     };
     use crate::lexer::token::{Token, TokenFragment, TokenType};
 
-    const VALID_CHARS: &str = "=<>+-*/|&!?(){}[];,.:";
+    const VALID_CHARS: &str = "=<>+-*/%|&!?(){}[];,.:";
 
     /// Checks if a given character is part of some valid characters defined in the lexical specfification
     pub(crate) fn is_valid_character(c: char) -> bool {
@@ -201,8 +201,8 @@ pub mod lexer {
                     TokenFragment::new(TokenType::Colon, ":")
                 }
             }
-            '+' | '-' | '*' | '|' | '&' | '!' | '?' | ';' | ',' | '.' | '(' | ')' | '{' | '}'
-            | '[' | ']' => TokenFragment::from_lexeme(&two_chars[0].to_string()),
+            '+' | '-' | '*' | '%' | '|' | '&' | '!' | '?' | ';' | ',' | '.' | '(' | ')' | '{'
+            | '}' | '[' | ']' => TokenFragment::from_lexeme(&two_chars[0].to_string()),
             _ => TokenFragment::new(
                 TokenType::Error(InvalidCharacter),
                 &two_chars[0].to_string(),
@@ -339,6 +339,7 @@ mod tests {
         assert!(is_valid_character('-'));
         assert!(is_valid_character('*'));
         assert!(is_valid_character('/'));
+        assert!(is_valid_character('%'));
         assert!(is_valid_character('('));
         assert!(is_valid_character(')'));
         assert!(is_valid_character('{'));
@@ -381,6 +382,14 @@ mod tests {
             parse_kw_or_id("abc@"),
             TokenFragment::new(TokenType::Id, "abc")
         );
+        assert_eq!(
+            parse_kw_or_id("const N"),
+            TokenFragment::new(TokenType::Const, "const")
+        );
+        assert_eq!(
+            parse_kw_or_id("constant"),
+            TokenFragment::new(TokenType::Id, "constant")
+        );
     }
 
     #[test]
@@ -481,6 +490,10 @@ mod tests {
             parse_op_or_punct("::"),
             TokenFragment::new(TokenType::DoubleColon, "::")
         );
+        assert_eq!(
+            parse_op_or_punct("% 3"),
+            TokenFragment::new(TokenType::Mod, "%")
+        );
         assert_eq!(
             parse_op_or_punct("// comment"),
             TokenFragment::new(TokenType::LineComment, "// comment")
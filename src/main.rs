@@ -1,12 +1,22 @@
-use crate::codegen::generator::MoonGenerator;
+use crate::codegen::generator::{CodegenOptions, MoonGenerator};
 use crate::codegen::utils::write_moon_code_to_file;
 use crate::lexer::lexer::MyLexerAnalyzer;
 use crate::lexer::utils::lexer_serialize::serialize_lexer_to_file;
-use crate::parser::parse::parse;
-use crate::parser::utils::{serialize_derivation_table_to_file, serialize_tree_to_file};
+use crate::parser::ast::{Node, SemanticStack};
+use crate::parser::grammar::DerivationTable;
+use crate::parser::invariants::check_ast_invariants;
+use crate::parser::modules::{link_modules, load_modules, Module, ModuleError};
+use crate::parser::parse::{parse_with_options, ParseFailure, ParseOptions, ParserBackend};
+use crate::parser::utils::{
+    serialize_derivation_table_to_file, serialize_tree_to_file, write_syntax_errors_to_file,
+    DerivationFormat,
+};
 use crate::semantics::checking::{SemanticError, WarningType};
-use crate::semantics::symbol_table::{check_semantics, generate_symbol_table};
-use crate::semantics::utils::{serialize_symbol_table_to_file, write_semantic_error_to_file};
+use crate::semantics::symbol_table::{check_program_semantics, generate_program_symbol_table};
+use crate::semantics::utils::{
+    serialize_symbol_table_to_file, write_module_errors_to_file,
+    write_program_semantic_errors_to_file,
+};
 use dotenv::dotenv;
 use env_logger;
 use log::{error, info};
@@ -32,6 +42,95 @@ struct Opt {
     symbols: bool,
     #[structopt(short, long)]
     codegen: bool,
+    /// Record the derivation and write it to a file, as md, json or html
+    #[structopt(short, long)]
+    derivation: Option<DerivationFormat>,
+    /// Parser to use, table or recursive-descent
+    #[structopt(long, default_value = "table")]
+    backend: ParserBackend,
+    /// Maximum nesting depth of expressions and statements (recursive-descent parser only)
+    #[structopt(long, default_value = "128")]
+    max_nesting_depth: usize,
+    /// Check array indices at runtime, an out of bounds index halts the program with its line
+    #[structopt(long)]
+    bounds_check: bool,
+}
+
+fn write_derivation_table(
+    table: Option<DerivationTable>,
+    file_name: &str,
+    format: Option<DerivationFormat>,
+) {
+    if let (Some(table), Some(format)) = (table, format) {
+        info!("Writing derivation table to file");
+        serialize_derivation_table_to_file(table, file_name, format)
+            .expect("Failed to serialize derivation table");
+    }
+}
+
+/// Reports every syntax error, and writes the derivation up to where parsing stopped
+fn report_parse_failure(
+    failure: ParseFailure,
+    file_name: &str,
+    format: Option<DerivationFormat>,
+) {
+    error!("Failed to parse token stream for {}", file_name);
+    for err in &failure.errors {
+        error!("{}", err);
+    }
+    write_syntax_errors_to_file(&failure.errors, file_name)
+        .expect("Failed to write syntax errors to file");
+    write_derivation_table(failure.derivation_table, file_name, format);
+}
+
+/// Loads the files imported by the program. Reports the ones that can't be loaded in the program's error file,
+/// the syntax errors of an imported file are also written to its own syntax error file.
+fn load_program(
+    opt: &Opt,
+    root: Node,
+    options: ParseOptions,
+    file_name: &str,
+) -> Option<Vec<Module>> {
+    match load_modules(&opt.file, root, options) {
+        Ok(modules) => Some(modules),
+        Err(errors) => {
+            for err in &errors {
+                error!("{}", err);
+            }
+            write_module_errors_to_file(&errors, file_name)
+                .expect("Failed to write module errors to file");
+            for err in errors {
+                if let ModuleError::Syntax { path, failure } = err {
+                    report_parse_failure(
+                        failure,
+                        path.file_stem().unwrap().to_str().unwrap(),
+                        opt.derivation,
+                    );
+                }
+            }
+            None
+        }
+    }
+}
+
+/// Turns a semantic stack into the root of the AST and checks its structural invariants
+fn build_ast(ast: SemanticStack) -> Option<Node> {
+    let root = ast.into_ast_root();
+    if root.is_err() {
+        log::error!("Failed to generate Abstract Syntax Tree");
+        return None;
+    }
+    let root = root.unwrap();
+
+    let violations = check_ast_invariants(&root);
+    if !violations.is_empty() {
+        for violation in violations {
+            error!("{}", violation);
+        }
+        return None;
+    }
+
+    Some(root)
 }
 
 fn main() {
@@ -41,6 +140,11 @@ fn main() {
 
     let file_name: &str = &opt.file.file_stem().unwrap().to_str().unwrap();
     let my_lexer = MyLexerAnalyzer::from_file(&opt.file);
+    let parse_options = ParseOptions {
+        record_derivation: opt.derivation.is_some(),
+        backend: opt.backend,
+        max_nesting_depth: opt.max_nesting_depth,
+    };
 
     if opt.lexer {
         match serialize_lexer_to_file(my_lexer, file_name) {
@@ -52,77 +156,83 @@ fn main() {
             }
         }
     } else if opt.parser {
-        match parse(my_lexer) {
+        match parse_with_options(my_lexer, parse_options) {
             Ok((table, ast)) => {
                 info!(
                     "Successfully parsed token stream for {}",
                     &opt.file.file_name().unwrap().to_str().unwrap()
                 );
 
-                info!("Writing derivation table and abstract syntax tree to file");
-                serialize_derivation_table_to_file(table, file_name)
-                    .expect("Failed to serialize derivation table");
-                serialize_tree_to_file(ast, file_name).expect("Failed to serialize AST to file");
-            }
-            Err(_) => {
-                error!("Failed to parse token stream for {}", file_name);
+                write_derivation_table(table, file_name, opt.derivation);
+                let root = match build_ast(ast) {
+                    Some(root) => root,
+                    None => return,
+                };
+
+                info!("Writing abstract syntax tree to file");
+                serialize_tree_to_file(root, file_name).expect("Failed to serialize AST to file");
             }
+            Err(failure) => report_parse_failure(failure, file_name, opt.derivation),
         }
     } else if opt.symbols {
-        match parse(my_lexer) {
-            Ok((_, ast)) => {
+        match parse_with_options(my_lexer, parse_options) {
+            Ok((table, ast)) => {
                 info!(
                     "Successfully parsed token stream for {}",
                     &opt.file.file_name().unwrap().to_str().unwrap()
                 );
-                //assert_eq!(ast.0.len(), 1);
-                let root = ast.into_ast_root();
-                if root.is_err() {
-                    log::error!("Failed to generate Abstract Syntax Tree");
-                    return;
-                }
-                let root = root.unwrap();
+                write_derivation_table(table, file_name, opt.derivation);
+                let root = match build_ast(ast) {
+                    Some(root) => root,
+                    None => return,
+                };
+                let modules = match load_program(&opt, root, parse_options, file_name) {
+                    Some(modules) => modules,
+                    None => return,
+                };
 
-                let (symbol_table, mut errors) = generate_symbol_table(&root);
+                let (symbol_table, mut errors) = generate_program_symbol_table(&modules);
 
-                errors.append(&mut check_semantics(&root, &symbol_table));
+                errors.append(&mut check_program_semantics(&modules, &symbol_table));
 
-                write_semantic_error_to_file(errors, file_name);
+                write_program_semantic_errors_to_file(&errors, &modules, file_name);
 
                 info!("Writing symbol tables to file");
                 serialize_symbol_table_to_file(&symbol_table, file_name)
                     .expect("Failed to serialize symbol table to file");
             }
-            Err(_) => {
-                error!("Failed to parse token stream for {}", file_name);
-            }
+            Err(failure) => report_parse_failure(failure, file_name, opt.derivation),
         }
     } else if opt.codegen {
-        match parse(my_lexer) {
-            Ok((_, ast)) => {
+        match parse_with_options(my_lexer, parse_options) {
+            Ok((table, ast)) => {
                 info!(
                     "Successfully parsed token stream for {}",
                     &opt.file.file_name().unwrap().to_str().unwrap()
                 );
-                //assert_eq!(ast.0.len(), 1);
-                let root = ast.into_ast_root();
-                if root.is_err() {
-                    log::error!("Failed to generate Abstract Syntax Tree");
-                    return;
-                }
-                let root = root.unwrap();
+                write_derivation_table(table, file_name, opt.derivation);
+                let root = match build_ast(ast) {
+                    Some(root) => root,
+                    None => return,
+                };
+                let modules = match load_program(&opt, root, parse_options, file_name) {
+                    Some(modules) => modules,
+                    None => return,
+                };
 
-                let (symbol_table, mut errors) = generate_symbol_table(&root);
+                let (symbol_table, mut errors) = generate_program_symbol_table(&modules);
 
-                errors.append(&mut check_semantics(&root, &symbol_table));
+                errors.append(&mut check_program_semantics(&modules, &symbol_table));
 
-                write_semantic_error_to_file(errors, file_name);
+                write_program_semantic_errors_to_file(&errors, &modules, file_name);
 
                 if true // since we get false positive errors
                 {
-                    let mut code_generator = MoonGenerator::new();
+                    let mut code_generator = MoonGenerator::with_options(CodegenOptions {
+                        bounds_check: opt.bounds_check,
+                    });
 
-                    code_generator.generate(&root, &symbol_table);
+                    code_generator.generate(&link_modules(&modules), &symbol_table);
 
                     let output = code_generator.finish();
 
@@ -132,9 +242,7 @@ fn main() {
                     return;
                 }
             }
-            Err(_) => {
-                error!("Failed to parse token stream for {}", file_name);
-            }
+            Err(failure) => report_parse_failure(failure, file_name, opt.derivation),
         }
     }
 }
@@ -8,7 +8,7 @@ use std::fmt::{Debug, Display, Formatter};
 
 /// A node in the abstact syntax tree.
 /// Contains an optional [NodeVal] and a list of children
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub struct Node {
     val: Option<NodeVal>,
     children: Vec<Node>,
@@ -217,11 +217,12 @@ pub enum InternalNodeType {
     MemberDeclaration,
     MemberFuncDeclaration,
     MemberVarDeclaration,
+    MemberConstDeclaration,
     FuncDeclaration,
     VarDeclaration,
+    ConstDeclaration,
     Expr,
     ArithExpr,
-    RelExpr,
     FuncParams,
     FuncParam,
     InheritList,
@@ -230,6 +231,7 @@ pub enum InternalNodeType {
     Negation,
     SignedFactor,
     TernaryOperation,
+    Cast,
     Factor,
     FuncBody,
     StatementList,
@@ -237,8 +239,12 @@ pub enum InternalNodeType {
     Indice,
     Mult,
     Div,
+    Mod,
     And,
     VarBlock,
+    GlobalDeclarations,
+    ImportDeclaration,
+    EnumDeclaration,
     ClassDeclarations,
     FunctionDefinitions,
     Main,
@@ -250,6 +256,10 @@ pub enum InternalNodeType {
     LessEqualThan,
     IfStatement,
     WhileStatement,
+    ForStatement,
+    SwitchStatement,
+    Case,
+    DefaultCase,
     ReadStatement,
     WriteStatement,
     ReturnStatement,
@@ -10,17 +10,31 @@ use crate::parser::grammar::NamedSymbol::*;
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 
-pub const START_FIRST: &'static [GrammarSymbol] =
-    &[Terminal(Class), Terminal(Func), Terminal(Main)];
+pub const START_FIRST: &'static [GrammarSymbol] = &[
+    Terminal(Class),
+    Terminal(Func),
+    Terminal(Main),
+    Terminal(Const),
+    Terminal(Import),
+    Terminal(Enum),
+    Terminal(Id),
+    Terminal(IntegerType),
+    Terminal(FloatType),
+    Terminal(StringType),
+    Terminal(BoolType),
+];
 pub const START_FOLLOW: &'static [GrammarSymbol] = &[STOP];
-pub const ADDOP_FIRST: &'static [GrammarSymbol] = &[Terminal(Plus), Terminal(Minus), Terminal(Or)];
+pub const ADDOP_FIRST: &'static [GrammarSymbol] = &[Terminal(Plus), Terminal(Minus)];
 pub const ADDOP_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Plus),
     Terminal(Minus),
     Terminal(Id),
     Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
     Terminal(FloatLit),
     Terminal(StringLit),
+    Terminal(BoolLit),
     Terminal(OpenParen),
     Terminal(Bang),
     Terminal(Question),
@@ -30,8 +44,11 @@ pub const ARITHEXPR_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Minus),
     Terminal(Id),
     Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
     Terminal(FloatLit),
     Terminal(StringLit),
+    Terminal(BoolLit),
     Terminal(OpenParen),
     Terminal(Bang),
     Terminal(Question),
@@ -48,6 +65,8 @@ pub const ARITHEXPR_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(LessEqualThan),
     Terminal(GreaterEqualThan),
     Terminal(Comma),
+    Terminal(And),
+    Terminal(Or),
 ];
 pub const ARRAYSIZE_FIRST: &'static [GrammarSymbol] = &[Terminal(OpenSquare)];
 pub const ARRAYSIZE_FOLLOW: &'static [GrammarSymbol] = &[
@@ -57,7 +76,7 @@ pub const ARRAYSIZE_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Comma),
 ];
 pub const ARRAYSIZEAMB1_FIRST: &'static [GrammarSymbol] =
-    &[Terminal(IntegerLit), Terminal(OpenSquare)];
+    &[Terminal(IntegerLit), Terminal(OpenSquare), Terminal(Id)];
 pub const ARRAYSIZEAMB1_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(OpenSquare),
     Terminal(SemiColon),
@@ -70,22 +89,107 @@ pub const ASSIGNOP_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Minus),
     Terminal(Id),
     Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
     Terminal(FloatLit),
     Terminal(StringLit),
+    Terminal(BoolLit),
     Terminal(OpenParen),
     Terminal(Bang),
     Terminal(Question),
 ];
+pub const ASSIGNSTAT_FIRST: &'static [GrammarSymbol] = &[Terminal(Id)];
+pub const ASSIGNSTAT_FOLLOW: &'static [GrammarSymbol] = &[Terminal(SemiColon), Terminal(CloseParen)];
 pub const CLASSDECL_FIRST: &'static [GrammarSymbol] = &[Terminal(Class)];
 pub const CLASSDECL_FOLLOW: &'static [GrammarSymbol] =
-    &[Terminal(Class), Terminal(Func), Terminal(Main)];
+    &[Terminal(Class), Terminal(Func), Terminal(Main), STOP];
+pub const COMPARISON_FIRST: &'static [GrammarSymbol] = &[
+    Terminal(Plus),
+    Terminal(Minus),
+    Terminal(Id),
+    Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
+    Terminal(FloatLit),
+    Terminal(StringLit),
+    Terminal(BoolLit),
+    Terminal(OpenParen),
+    Terminal(Bang),
+    Terminal(Question),
+];
+pub const COMPARISON_FOLLOW: &'static [GrammarSymbol] = &[
+    Terminal(And),
+    Terminal(Or),
+    Terminal(CloseParen),
+    Terminal(SemiColon),
+    Terminal(Comma),
+    Terminal(CloseSquare),
+    Terminal(Colon),
+];
+pub const CONJUNCTION_FIRST: &'static [GrammarSymbol] = &[
+    Terminal(Plus),
+    Terminal(Minus),
+    Terminal(Id),
+    Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
+    Terminal(FloatLit),
+    Terminal(StringLit),
+    Terminal(BoolLit),
+    Terminal(OpenParen),
+    Terminal(Bang),
+    Terminal(Question),
+];
+pub const CONJUNCTION_FOLLOW: &'static [GrammarSymbol] = &[
+    Terminal(Or),
+    Terminal(CloseParen),
+    Terminal(SemiColon),
+    Terminal(Comma),
+    Terminal(CloseSquare),
+    Terminal(Colon),
+];
+pub const CONSTDECL_FIRST: &'static [GrammarSymbol] = &[Terminal(Const)];
+pub const CONSTDECL_FOLLOW: &'static [GrammarSymbol] = &[
+    Terminal(Class),
+    Terminal(Func),
+    Terminal(Main),
+    Terminal(Const),
+    Terminal(Import),
+    Terminal(Enum),
+    Terminal(Private),
+    Terminal(Public),
+    Terminal(Id),
+    Terminal(StringType),
+    Terminal(BoolType),
+    Terminal(FloatType),
+    Terminal(IntegerType),
+    Terminal(CloseCurly),
+];
+pub const ENUMDECL_FIRST: &'static [GrammarSymbol] = &[Terminal(Enum)];
+pub const ENUMDECL_FOLLOW: &'static [GrammarSymbol] = &[
+    Terminal(Class),
+    Terminal(Func),
+    Terminal(Main),
+    Terminal(Const),
+    Terminal(Import),
+    Terminal(Enum),
+    Terminal(Id),
+    Terminal(IntegerType),
+    Terminal(FloatType),
+    Terminal(StringType),
+    Terminal(BoolType),
+    STOP,
+];
 pub const EXPR_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Plus),
     Terminal(Minus),
     Terminal(Id),
     Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
     Terminal(FloatLit),
     Terminal(StringLit),
+    Terminal(BoolLit),
     Terminal(OpenParen),
     Terminal(Bang),
     Terminal(Question),
@@ -112,14 +216,19 @@ pub const EXPRAMB1_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(CloseParen),
     Terminal(Colon),
     Terminal(Comma),
+    Terminal(And),
+    Terminal(Or),
 ];
 pub const FACTOR_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Plus),
     Terminal(Minus),
     Terminal(Id),
     Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
     Terminal(FloatLit),
     Terminal(StringLit),
+    Terminal(BoolLit),
     Terminal(OpenParen),
     Terminal(Bang),
     Terminal(Question),
@@ -142,6 +251,7 @@ pub const FACTOR_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(LessEqualThan),
     Terminal(GreaterEqualThan),
     Terminal(Comma),
+    Terminal(Mod),
 ];
 pub const FACTORAMB1_FIRST: &'static [GrammarSymbol] =
     &[Terminal(OpenSquare), Terminal(OpenParen), Terminal(Period)];
@@ -163,6 +273,7 @@ pub const FACTORAMB1_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(LessEqualThan),
     Terminal(GreaterEqualThan),
     Terminal(Comma),
+    Terminal(Mod),
 ];
 pub const FACTORAMB2_FIRST: &'static [GrammarSymbol] = &[Terminal(Period), EPSILON];
 pub const FACTORAMB2_FOLLOW: &'static [GrammarSymbol] = &[
@@ -183,6 +294,7 @@ pub const FACTORAMB2_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(LessEqualThan),
     Terminal(GreaterEqualThan),
     Terminal(Comma),
+    Terminal(Mod),
 ];
 pub const FUNCBODY_FIRST: &'static [GrammarSymbol] = &[Terminal(OpenCurly)];
 pub const FUNCBODY_FOLLOW: &'static [GrammarSymbol] = &[Terminal(Func), Terminal(Main), STOP];
@@ -194,8 +306,10 @@ pub const FUNCDECL_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
     Terminal(Public),
     Terminal(Private),
+    Terminal(Const),
 ];
 pub const FUNCDECLAMB1_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Id),
@@ -203,6 +317,7 @@ pub const FUNCDECLAMB1_FIRST: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
 ];
 pub const FUNCDECLAMB1_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Id),
@@ -211,11 +326,13 @@ pub const FUNCDECLAMB1_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
     Terminal(Public),
     Terminal(Private),
+    Terminal(Const),
 ];
 pub const FUNCDEF_FIRST: &'static [GrammarSymbol] = &[Terminal(Func)];
-pub const FUNCDEF_FOLLOW: &'static [GrammarSymbol] = &[Terminal(Func), Terminal(Main)];
+pub const FUNCDEF_FOLLOW: &'static [GrammarSymbol] = &[Terminal(Func), Terminal(Main), STOP];
 pub const FUNCHEAD_FIRST: &'static [GrammarSymbol] = &[Terminal(Func)];
 pub const FUNCHEAD_FOLLOW: &'static [GrammarSymbol] = &[Terminal(OpenCurly)];
 pub const FUNCHEADAMB1_FIRST: &'static [GrammarSymbol] =
@@ -227,6 +344,7 @@ pub const FUNCHEADAMB2_FIRST: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
 ];
 pub const FUNCHEADAMB2_FOLLOW: &'static [GrammarSymbol] = &[Terminal(OpenCurly)];
 pub const FUNCPARAMS_FIRST: &'static [GrammarSymbol] = &[
@@ -234,9 +352,25 @@ pub const FUNCPARAMS_FIRST: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
     EPSILON,
 ];
 pub const FUNCPARAMS_FOLLOW: &'static [GrammarSymbol] = &[Terminal(CloseParen)];
+pub const IMPORTDECL_FIRST: &'static [GrammarSymbol] = &[Terminal(Import)];
+pub const IMPORTDECL_FOLLOW: &'static [GrammarSymbol] = &[
+    Terminal(Class),
+    Terminal(Func),
+    Terminal(Main),
+    Terminal(Const),
+    Terminal(Import),
+    Terminal(Enum),
+    Terminal(Id),
+    Terminal(IntegerType),
+    Terminal(FloatType),
+    Terminal(StringType),
+    Terminal(BoolType),
+    STOP,
+];
 pub const INDICE_FIRST: &'static [GrammarSymbol] = &[Terminal(OpenSquare)];
 pub const INDICE_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Plus),
@@ -259,6 +393,7 @@ pub const INDICE_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(LessEqualThan),
     Terminal(GreaterEqualThan),
     Terminal(Comma),
+    Terminal(Mod),
 ];
 pub const MEMBERDECL_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Id),
@@ -266,6 +401,8 @@ pub const MEMBERDECL_FIRST: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
+    Terminal(Const),
 ];
 pub const MEMBERDECL_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Id),
@@ -274,29 +411,38 @@ pub const MEMBERDECL_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
     Terminal(Public),
     Terminal(Private),
+    Terminal(Const),
 ];
-pub const MULTOP_FIRST: &'static [GrammarSymbol] = &[Terminal(Mult), Terminal(Div), Terminal(And)];
+pub const MULTOP_FIRST: &'static [GrammarSymbol] = &[Terminal(Mult), Terminal(Div), Terminal(Mod)];
 pub const MULTOP_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Plus),
     Terminal(Minus),
     Terminal(Id),
     Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
     Terminal(FloatLit),
     Terminal(StringLit),
+    Terminal(BoolLit),
     Terminal(OpenParen),
     Terminal(Bang),
     Terminal(Question),
 ];
 pub const OPTCLASSDECL_FIRST: &'static [GrammarSymbol] = &[Terminal(Inherits), EPSILON];
 pub const OPTCLASSDECL_FOLLOW: &'static [GrammarSymbol] = &[Terminal(OpenCurly)];
+pub const OPTPROG_FIRST: &'static [GrammarSymbol] = &[Terminal(Main), EPSILON];
+pub const OPTPROG_FOLLOW: &'static [GrammarSymbol] = &[STOP];
 pub const OPTFUNCBODY_FIRST: &'static [GrammarSymbol] = &[Terminal(Var), EPSILON];
 pub const OPTFUNCBODY_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Id),
     Terminal(CloseCurly),
     Terminal(If),
     Terminal(While),
+    Terminal(For),
+    Terminal(Switch),
     Terminal(Read),
     Terminal(Write),
     Terminal(Return),
@@ -308,28 +454,31 @@ pub const PARAMS_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Minus),
     Terminal(Id),
     Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
     Terminal(FloatLit),
     Terminal(StringLit),
+    Terminal(BoolLit),
     Terminal(OpenParen),
     Terminal(Bang),
     Terminal(Question),
     EPSILON,
 ];
 pub const PARAMS_FOLLOW: &'static [GrammarSymbol] = &[Terminal(CloseParen)];
-pub const PROG_FIRST: &'static [GrammarSymbol] = &[Terminal(Class), Terminal(Func), Terminal(Main)];
-pub const PROG_FOLLOW: &'static [GrammarSymbol] = &[STOP];
-pub const RELEXPR_FIRST: &'static [GrammarSymbol] = &[
-    Terminal(Plus),
-    Terminal(Minus),
+pub const PROG_FIRST: &'static [GrammarSymbol] = &[
+    Terminal(Class),
+    Terminal(Func),
+    Terminal(Main),
+    Terminal(Const),
+    Terminal(Import),
+    Terminal(Enum),
     Terminal(Id),
-    Terminal(IntegerLit),
-    Terminal(FloatLit),
-    Terminal(StringLit),
-    Terminal(OpenParen),
-    Terminal(Bang),
-    Terminal(Question),
+    Terminal(IntegerType),
+    Terminal(FloatType),
+    Terminal(StringType),
+    Terminal(BoolType),
 ];
-pub const RELEXPR_FOLLOW: &'static [GrammarSymbol] = &[Terminal(CloseParen)];
+pub const PROG_FOLLOW: &'static [GrammarSymbol] = &[STOP];
 pub const RELOP_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Assignment),
     Terminal(NotEq),
@@ -343,8 +492,11 @@ pub const RELOP_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Minus),
     Terminal(Id),
     Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
     Terminal(FloatLit),
     Terminal(StringLit),
+    Terminal(BoolLit),
     Terminal(OpenParen),
     Terminal(Bang),
     Terminal(Question),
@@ -355,15 +507,21 @@ pub const REPTCLASSDECL_FIRST: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
     Terminal(Public),
     Terminal(Private),
+    Terminal(Const),
     EPSILON,
 ];
 pub const REPTCLASSDECL_FOLLOW: &'static [GrammarSymbol] = &[Terminal(CloseCurly)];
+pub const REPTENUMDECL_FIRST: &'static [GrammarSymbol] = &[Terminal(Comma), EPSILON];
+pub const REPTENUMDECL_FOLLOW: &'static [GrammarSymbol] = &[Terminal(CloseCurly)];
 pub const REPTFUNCBODY_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Id),
     Terminal(If),
     Terminal(While),
+    Terminal(For),
+    Terminal(Switch),
     Terminal(Read),
     Terminal(Write),
     Terminal(Return),
@@ -387,19 +545,36 @@ pub const REPTOPTFUNCBODY_FIRST: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
+    Terminal(Const),
     EPSILON,
 ];
 pub const REPTOPTFUNCBODY_FOLLOW: &'static [GrammarSymbol] = &[Terminal(CloseCurly)];
 pub const REPTPARAMS_FIRST: &'static [GrammarSymbol] = &[Terminal(Comma), EPSILON];
 pub const REPTPARAMS_FOLLOW: &'static [GrammarSymbol] = &[Terminal(CloseParen)];
 pub const REPTPROG0_FIRST: &'static [GrammarSymbol] = &[Terminal(Class), EPSILON];
-pub const REPTPROG0_FOLLOW: &'static [GrammarSymbol] = &[Terminal(Func), Terminal(Main)];
+pub const REPTPROG0_FOLLOW: &'static [GrammarSymbol] = &[Terminal(Func), Terminal(Main), STOP];
 pub const REPTPROG1_FIRST: &'static [GrammarSymbol] = &[Terminal(Func), EPSILON];
-pub const REPTPROG1_FOLLOW: &'static [GrammarSymbol] = &[Terminal(Main)];
+pub const REPTPROG1_FOLLOW: &'static [GrammarSymbol] = &[Terminal(Main), STOP];
+pub const REPTPROG2_FIRST: &'static [GrammarSymbol] = &[
+    Terminal(Const),
+    Terminal(Import),
+    Terminal(Enum),
+    Terminal(Id),
+    Terminal(IntegerType),
+    Terminal(FloatType),
+    Terminal(StringType),
+    Terminal(BoolType),
+    EPSILON,
+];
+pub const REPTPROG2_FOLLOW: &'static [GrammarSymbol] =
+    &[Terminal(Class), Terminal(Func), Terminal(Main), STOP];
 pub const REPTSTATBLOCK_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Id),
     Terminal(If),
     Terminal(While),
+    Terminal(For),
+    Terminal(Switch),
     Terminal(Read),
     Terminal(Write),
     Terminal(Return),
@@ -407,7 +582,11 @@ pub const REPTSTATBLOCK_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Continue),
     EPSILON,
 ];
-pub const REPTSTATBLOCK_FOLLOW: &'static [GrammarSymbol] = &[Terminal(CloseCurly)];
+pub const REPTSTATBLOCK_FOLLOW: &'static [GrammarSymbol] =
+    &[Terminal(CloseCurly), Terminal(Case), Terminal(Default)];
+pub const REPTSWITCHCASES_FIRST: &'static [GrammarSymbol] =
+    &[Terminal(Case), Terminal(Default), EPSILON];
+pub const REPTSWITCHCASES_FOLLOW: &'static [GrammarSymbol] = &[Terminal(CloseCurly)];
 pub const REPTVARDECL_FIRST: &'static [GrammarSymbol] = &[Terminal(OpenSquare), EPSILON];
 pub const REPTVARDECL_FOLLOW: &'static [GrammarSymbol] = &[Terminal(SemiColon)];
 pub const REPTVARIABLE_FIRST: &'static [GrammarSymbol] = &[Terminal(OpenSquare), EPSILON];
@@ -431,9 +610,10 @@ pub const REPTVARIABLE_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(LessEqualThan),
     Terminal(GreaterEqualThan),
     Terminal(Comma),
+    Terminal(Mod),
 ];
 pub const RIGHTRECARITHEXPR_FIRST: &'static [GrammarSymbol] =
-    &[Terminal(Plus), Terminal(Minus), Terminal(Or), EPSILON];
+    &[Terminal(Plus), Terminal(Minus), EPSILON];
 pub const RIGHTRECARITHEXPR_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(OpenSquare),
     Terminal(SemiColon),
@@ -446,9 +626,28 @@ pub const RIGHTRECARITHEXPR_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(LessEqualThan),
     Terminal(GreaterEqualThan),
     Terminal(Comma),
+    Terminal(And),
+    Terminal(Or),
+];
+pub const RIGHTRECCONJUNCTION_FIRST: &'static [GrammarSymbol] = &[Terminal(And), EPSILON];
+pub const RIGHTRECCONJUNCTION_FOLLOW: &'static [GrammarSymbol] = &[
+    Terminal(Or),
+    Terminal(CloseParen),
+    Terminal(SemiColon),
+    Terminal(Comma),
+    Terminal(CloseSquare),
+    Terminal(Colon),
+];
+pub const RIGHTRECEXPR_FIRST: &'static [GrammarSymbol] = &[Terminal(Or), EPSILON];
+pub const RIGHTRECEXPR_FOLLOW: &'static [GrammarSymbol] = &[
+    Terminal(CloseParen),
+    Terminal(SemiColon),
+    Terminal(Comma),
+    Terminal(CloseSquare),
+    Terminal(Colon),
 ];
 pub const RIGHTRECTERM_FIRST: &'static [GrammarSymbol] =
-    &[Terminal(Mult), Terminal(Div), Terminal(And), EPSILON];
+    &[Terminal(Mult), Terminal(Div), Terminal(Mod), EPSILON];
 pub const RIGHTRECTERM_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Plus),
     Terminal(Minus),
@@ -464,6 +663,7 @@ pub const RIGHTRECTERM_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(LessEqualThan),
     Terminal(GreaterEqualThan),
     Terminal(Comma),
+    Terminal(And),
 ];
 pub const SIGN_FIRST: &'static [GrammarSymbol] = &[Terminal(Plus), Terminal(Minus)];
 pub const SIGN_FOLLOW: &'static [GrammarSymbol] = &[
@@ -471,8 +671,11 @@ pub const SIGN_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Minus),
     Terminal(Id),
     Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
     Terminal(FloatLit),
     Terminal(StringLit),
+    Terminal(BoolLit),
     Terminal(OpenParen),
     Terminal(Bang),
     Terminal(Question),
@@ -482,6 +685,8 @@ pub const STATBLOCK_FIRST: &'static [GrammarSymbol] = &[
     Terminal(OpenCurly),
     Terminal(If),
     Terminal(While),
+    Terminal(For),
+    Terminal(Switch),
     Terminal(Read),
     Terminal(Write),
     Terminal(Return),
@@ -494,6 +699,8 @@ pub const STATEMENT_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Id),
     Terminal(If),
     Terminal(While),
+    Terminal(For),
+    Terminal(Switch),
     Terminal(Read),
     Terminal(Write),
     Terminal(Return),
@@ -507,11 +714,15 @@ pub const STATEMENT_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(If),
     Terminal(Else),
     Terminal(While),
+    Terminal(For),
+    Terminal(Switch),
     Terminal(Read),
     Terminal(Write),
     Terminal(Return),
     Terminal(Break),
     Terminal(Continue),
+    Terminal(Case),
+    Terminal(Default),
 ];
 pub const STATEMENTAMB1_FIRST: &'static [GrammarSymbol] = &[
     Terminal(OpenSquare),
@@ -526,11 +737,15 @@ pub const STATEMENTAMB1_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(If),
     Terminal(Else),
     Terminal(While),
+    Terminal(For),
+    Terminal(Switch),
     Terminal(Read),
     Terminal(Write),
     Terminal(Return),
     Terminal(Break),
     Terminal(Continue),
+    Terminal(Case),
+    Terminal(Default),
 ];
 pub const STATEMENTAMB2_FIRST: &'static [GrammarSymbol] = &[Terminal(EqEq), Terminal(Period)];
 pub const STATEMENTAMB2_FOLLOW: &'static [GrammarSymbol] = &[
@@ -540,11 +755,15 @@ pub const STATEMENTAMB2_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(If),
     Terminal(Else),
     Terminal(While),
+    Terminal(For),
+    Terminal(Switch),
     Terminal(Read),
     Terminal(Write),
     Terminal(Return),
     Terminal(Break),
     Terminal(Continue),
+    Terminal(Case),
+    Terminal(Default),
 ];
 pub const STATEMENTAMB3_FIRST: &'static [GrammarSymbol] = &[Terminal(SemiColon), Terminal(Period)];
 pub const STATEMENTAMB3_FOLLOW: &'static [GrammarSymbol] = &[
@@ -554,19 +773,26 @@ pub const STATEMENTAMB3_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(If),
     Terminal(Else),
     Terminal(While),
+    Terminal(For),
+    Terminal(Switch),
     Terminal(Read),
     Terminal(Write),
     Terminal(Return),
     Terminal(Break),
     Terminal(Continue),
+    Terminal(Case),
+    Terminal(Default),
 ];
 pub const TERM_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Plus),
     Terminal(Minus),
     Terminal(Id),
     Terminal(IntegerLit),
+    Terminal(IntegerType),
+    Terminal(FloatType),
     Terminal(FloatLit),
     Terminal(StringLit),
+    Terminal(BoolLit),
     Terminal(OpenParen),
     Terminal(Bang),
     Terminal(Question),
@@ -586,12 +812,14 @@ pub const TERM_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(LessEqualThan),
     Terminal(GreaterEqualThan),
     Terminal(Comma),
+    Terminal(And),
 ];
 pub const TYPE_FIRST: &'static [GrammarSymbol] = &[
     Terminal(Id),
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
 ];
 pub const TYPE_FOLLOW: &'static [GrammarSymbol] =
     &[Terminal(Id), Terminal(OpenCurly), Terminal(SemiColon)];
@@ -600,16 +828,23 @@ pub const VARDECL_FIRST: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
 ];
 pub const VARDECL_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(Id),
     Terminal(CloseCurly),
     Terminal(Func),
+    Terminal(Class),
+    Terminal(Main),
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
     Terminal(Public),
     Terminal(Private),
+    Terminal(Const),
+    Terminal(Import),
+    Terminal(Enum),
 ];
 pub const VARIABLE_FIRST: &'static [GrammarSymbol] = &[Terminal(Id)];
 pub const VARIABLE_FOLLOW: &'static [GrammarSymbol] = &[Terminal(CloseParen)];
@@ -628,6 +863,8 @@ pub const VISIBILITY_FOLLOW: &'static [GrammarSymbol] = &[
     Terminal(IntegerType),
     Terminal(FloatType),
     Terminal(StringType),
+    Terminal(BoolType),
+    Terminal(Const),
 ];
 
 lazy_static! {
@@ -664,6 +901,86 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(Start), Terminal(Const)),
+            GrammarRule {
+                lhs: NonTerminal(Start),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Root)),
+                    NonTerminal(Prog),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Start), Terminal(Import)),
+            GrammarRule {
+                lhs: NonTerminal(Start),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Root)),
+                    NonTerminal(Prog),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Start), Terminal(Enum)),
+            GrammarRule {
+                lhs: NonTerminal(Start),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Root)),
+                    NonTerminal(Prog),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Start), Terminal(Id)),
+            GrammarRule {
+                lhs: NonTerminal(Start),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Root)),
+                    NonTerminal(Prog),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Start), Terminal(IntegerType)),
+            GrammarRule {
+                lhs: NonTerminal(Start),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Root)),
+                    NonTerminal(Prog),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Start), Terminal(FloatType)),
+            GrammarRule {
+                lhs: NonTerminal(Start),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Root)),
+                    NonTerminal(Prog),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Start), Terminal(StringType)),
+            GrammarRule {
+                lhs: NonTerminal(Start),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Root)),
+                    NonTerminal(Prog),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Start), Terminal(BoolType)),
+            GrammarRule {
+                lhs: NonTerminal(Start),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Root)),
+                    NonTerminal(Prog),
+                ],
+            },
+        );
 
         table.insert(
             (NonTerminal(AddOp), Terminal(Minus)),
@@ -685,16 +1002,6 @@ lazy_static! {
                 ],
             },
         );
-        table.insert(
-            (NonTerminal(AddOp), Terminal(Or)),
-            GrammarRule {
-                lhs: NonTerminal(AddOp),
-                rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Or)),
-                    Terminal(Or),
-                ],
-            },
-        );
 
         table.insert(
             (NonTerminal(ArithExpr), Terminal(Id)),
@@ -780,6 +1087,18 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(ArithExpr), Terminal(BoolLit)),
+            GrammarRule {
+                lhs: NonTerminal(ArithExpr),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ArithExpr)),
+                    NonTerminal(Term),
+                    NonTerminal(RightRecArithExpr),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(ArithExpr), Terminal(FloatLit)),
             GrammarRule {
@@ -804,6 +1123,30 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(ArithExpr), Terminal(IntegerType)),
+            GrammarRule {
+                lhs: NonTerminal(ArithExpr),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ArithExpr)),
+                    NonTerminal(Term),
+                    NonTerminal(RightRecArithExpr),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ArithExpr), Terminal(FloatType)),
+            GrammarRule {
+                lhs: NonTerminal(ArithExpr),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ArithExpr)),
+                    NonTerminal(Term),
+                    NonTerminal(RightRecArithExpr),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
 
         table.insert(
             (NonTerminal(ArraySize), Terminal(OpenSquare)),
@@ -831,6 +1174,17 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(ArraySizeAmb1), Terminal(Id)),
+            GrammarRule {
+                lhs: NonTerminal(ArraySizeAmb1),
+                rhs: vec![
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(Id),
+                    Terminal(CloseSquare),
+                ],
+            },
+        );
 
         table.insert(
             (NonTerminal(AssignOp), Terminal(Assignment)),
@@ -867,24 +1221,216 @@ lazy_static! {
         );
 
         table.insert(
-            (NonTerminal(Expr), Terminal(Id)),
+            (NonTerminal(Comparison), Terminal(Id)),
             GrammarRule {
-                lhs: NonTerminal(Expr),
-                rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(ExprAmb1),
-                ],
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
             },
         );
         table.insert(
-            (NonTerminal(Expr), Terminal(OpenParen)),
+            (NonTerminal(Comparison), Terminal(OpenParen)),
             GrammarRule {
-                lhs: NonTerminal(Expr),
-                rhs: vec![
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Comparison), Terminal(Minus)),
+            GrammarRule {
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Comparison), Terminal(Plus)),
+            GrammarRule {
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Comparison), Terminal(Question)),
+            GrammarRule {
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Comparison), Terminal(Bang)),
+            GrammarRule {
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Comparison), Terminal(StringLit)),
+            GrammarRule {
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Comparison), Terminal(BoolLit)),
+            GrammarRule {
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Comparison), Terminal(FloatLit)),
+            GrammarRule {
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Comparison), Terminal(IntegerLit)),
+            GrammarRule {
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Comparison), Terminal(IntegerType)),
+            GrammarRule {
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Comparison), Terminal(FloatType)),
+            GrammarRule {
+                lhs: NonTerminal(Comparison),
+                rhs: vec![NonTerminal(ArithExpr), NonTerminal(ExprAmb1)],
+            },
+        );
+
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(Id)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(OpenParen)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(Minus)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(Plus)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(Question)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(Bang)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(StringLit)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(BoolLit)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(FloatLit)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(IntegerLit)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(IntegerType)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+        table.insert(
+            (NonTerminal(Conjunction), Terminal(FloatType)),
+            GrammarRule {
+                lhs: NonTerminal(Conjunction),
+                rhs: vec![NonTerminal(Comparison), NonTerminal(RightRecConjunction)],
+            },
+        );
+
+        table.insert(
+            (NonTerminal(ConstDecl), Terminal(Const)),
+            GrammarRule {
+                lhs: NonTerminal(ConstDecl),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ConstDeclaration)),
+                    Terminal(Const),
+                    NonTerminal(Type),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(Id),
+                    SemanticActionType(AddChild),
+                    Terminal(Assignment),
+                    NonTerminal(Expr),
+                    SemanticActionType(AddChild),
+                    Terminal(SemiColon),
+                ],
+            },
+        );
+
+        table.insert(
+            (NonTerminal(Expr), Terminal(Id)),
+            GrammarRule {
+                lhs: NonTerminal(Expr),
+                rhs: vec![
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(ExprAmb1),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Expr), Terminal(OpenParen)),
+            GrammarRule {
+                lhs: NonTerminal(Expr),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -894,8 +1440,9 @@ lazy_static! {
                 lhs: NonTerminal(Expr),
                 rhs: vec![
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(ExprAmb1),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -905,8 +1452,9 @@ lazy_static! {
                 lhs: NonTerminal(Expr),
                 rhs: vec![
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(ExprAmb1),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -916,8 +1464,9 @@ lazy_static! {
                 lhs: NonTerminal(Expr),
                 rhs: vec![
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(ExprAmb1),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -927,8 +1476,9 @@ lazy_static! {
                 lhs: NonTerminal(Expr),
                 rhs: vec![
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(ExprAmb1),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -938,8 +1488,21 @@ lazy_static! {
                 lhs: NonTerminal(Expr),
                 rhs: vec![
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(ExprAmb1),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Expr), Terminal(BoolLit)),
+            GrammarRule {
+                lhs: NonTerminal(Expr),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -949,8 +1512,9 @@ lazy_static! {
                 lhs: NonTerminal(Expr),
                 rhs: vec![
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(ExprAmb1),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -960,8 +1524,33 @@ lazy_static! {
                 lhs: NonTerminal(Expr),
                 rhs: vec![
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(ExprAmb1),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Expr), Terminal(IntegerType)),
+            GrammarRule {
+                lhs: NonTerminal(Expr),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Expr), Terminal(FloatType)),
+            GrammarRule {
+                lhs: NonTerminal(Expr),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Expr)),
+                    NonTerminal(Conjunction),
+                    NonTerminal(RightRecExpr),
+                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -970,21 +1559,21 @@ lazy_static! {
             (NonTerminal(ExprAmb1), Terminal(CloseParen)),
             GrammarRule {
                 lhs: NonTerminal(ExprAmb1),
-                rhs: vec![EPSILON, SemanticActionType(AddChild)],
+                rhs: vec![EPSILON],
             },
         );
         table.insert(
             (NonTerminal(ExprAmb1), Terminal(SemiColon)),
             GrammarRule {
                 lhs: NonTerminal(ExprAmb1),
-                rhs: vec![EPSILON, SemanticActionType(AddChild)],
+                rhs: vec![EPSILON],
             },
         );
         table.insert(
             (NonTerminal(ExprAmb1), Terminal(Comma)),
             GrammarRule {
                 lhs: NonTerminal(ExprAmb1),
-                rhs: vec![EPSILON, SemanticActionType(AddChild)],
+                rhs: vec![EPSILON],
             },
         );
         table.insert(
@@ -995,7 +1584,6 @@ lazy_static! {
                     NonTerminal(RelOp),
                     NonTerminal(ArithExpr),
                     SemanticActionType(MakeRelativeOperation),
-                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -1007,7 +1595,6 @@ lazy_static! {
                     NonTerminal(RelOp),
                     NonTerminal(ArithExpr),
                     SemanticActionType(MakeRelativeOperation),
-                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -1019,7 +1606,6 @@ lazy_static! {
                     NonTerminal(RelOp),
                     NonTerminal(ArithExpr),
                     SemanticActionType(MakeRelativeOperation),
-                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -1031,7 +1617,6 @@ lazy_static! {
                     NonTerminal(RelOp),
                     NonTerminal(ArithExpr),
                     SemanticActionType(MakeRelativeOperation),
-                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -1043,7 +1628,6 @@ lazy_static! {
                     NonTerminal(RelOp),
                     NonTerminal(ArithExpr),
                     SemanticActionType(MakeRelativeOperation),
-                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -1055,7 +1639,6 @@ lazy_static! {
                     NonTerminal(RelOp),
                     NonTerminal(ArithExpr),
                     SemanticActionType(MakeRelativeOperation),
-                    SemanticActionType(AddChild),
                 ],
             },
         );
@@ -1063,14 +1646,28 @@ lazy_static! {
             (NonTerminal(ExprAmb1), Terminal(CloseSquare)),
             GrammarRule {
                 lhs: NonTerminal(ExprAmb1),
-                rhs: vec![EPSILON, SemanticActionType(AddChild)],
+                rhs: vec![EPSILON],
             },
         );
         table.insert(
             (NonTerminal(ExprAmb1), Terminal(Colon)),
             GrammarRule {
                 lhs: NonTerminal(ExprAmb1),
-                rhs: vec![EPSILON, SemanticActionType(AddChild)],
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(ExprAmb1), Terminal(And)),
+            GrammarRule {
+                lhs: NonTerminal(ExprAmb1),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(ExprAmb1), Terminal(Or)),
+            GrammarRule {
+                lhs: NonTerminal(ExprAmb1),
+                rhs: vec![EPSILON],
             },
         );
 
@@ -1091,11 +1688,7 @@ lazy_static! {
             (NonTerminal(Factor), Terminal(OpenParen)),
             GrammarRule {
                 lhs: NonTerminal(Factor),
-                rhs: vec![
-                    Terminal(OpenParen),
-                    NonTerminal(ArithExpr),
-                    Terminal(CloseParen),
-                ],
+                rhs: vec![Terminal(OpenParen), NonTerminal(Expr), Terminal(CloseParen)],
             },
         );
         table.insert(
@@ -1163,6 +1756,13 @@ lazy_static! {
                 rhs: vec![SemanticActionType(MakeTerminalNode), Terminal(StringLit)],
             },
         );
+        table.insert(
+            (NonTerminal(Factor), Terminal(BoolLit)),
+            GrammarRule {
+                lhs: NonTerminal(Factor),
+                rhs: vec![SemanticActionType(MakeTerminalNode), Terminal(BoolLit)],
+            },
+        );
         table.insert(
             (NonTerminal(Factor), Terminal(FloatLit)),
             GrammarRule {
@@ -1177,6 +1777,38 @@ lazy_static! {
                 rhs: vec![SemanticActionType(MakeTerminalNode), Terminal(IntegerLit)],
             },
         );
+        table.insert(
+            (NonTerminal(Factor), Terminal(IntegerType)),
+            GrammarRule {
+                lhs: NonTerminal(Factor),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Cast)),
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(IntegerType),
+                    SemanticActionType(AddChild),
+                    Terminal(OpenParen),
+                    NonTerminal(Expr),
+                    SemanticActionType(AddChild),
+                    Terminal(CloseParen),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Factor), Terminal(FloatType)),
+            GrammarRule {
+                lhs: NonTerminal(Factor),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Cast)),
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(FloatType),
+                    SemanticActionType(AddChild),
+                    Terminal(OpenParen),
+                    NonTerminal(Expr),
+                    SemanticActionType(AddChild),
+                    Terminal(CloseParen),
+                ],
+            },
+        );
 
         table.insert(
             (NonTerminal(FactorAmb1), Terminal(Plus)),
@@ -1268,6 +1900,13 @@ lazy_static! {
                 rhs: vec![NonTerminal(ReptVariable), NonTerminal(FactorAmb2)],
             },
         );
+        table.insert(
+            (NonTerminal(FactorAmb1), Terminal(Mod)),
+            GrammarRule {
+                lhs: NonTerminal(FactorAmb1),
+                rhs: vec![NonTerminal(ReptVariable), NonTerminal(FactorAmb2)],
+            },
+        );
         table.insert(
             (NonTerminal(FactorAmb1), Terminal(And)),
             GrammarRule {
@@ -1402,6 +2041,13 @@ lazy_static! {
                 rhs: vec![EPSILON],
             },
         );
+        table.insert(
+            (NonTerminal(FactorAmb2), Terminal(Mod)),
+            GrammarRule {
+                lhs: NonTerminal(FactorAmb2),
+                rhs: vec![EPSILON],
+            },
+        );
         table.insert(
             (NonTerminal(FactorAmb2), Terminal(And)),
             GrammarRule {
@@ -1511,6 +2157,13 @@ lazy_static! {
                 rhs: vec![NonTerminal(Type), Terminal(SemiColon)],
             },
         );
+        table.insert(
+            (NonTerminal(FuncDeclAmb1), Terminal(BoolType)),
+            GrammarRule {
+                lhs: NonTerminal(FuncDeclAmb1),
+                rhs: vec![NonTerminal(Type), Terminal(SemiColon)],
+            },
+        );
         table.insert(
             (NonTerminal(FuncDeclAmb1), Terminal(IntegerType)),
             GrammarRule {
@@ -1615,6 +2268,13 @@ lazy_static! {
                 rhs: vec![NonTerminal(Type)],
             },
         );
+        table.insert(
+            (NonTerminal(FuncHeadAmb2), Terminal(BoolType)),
+            GrammarRule {
+                lhs: NonTerminal(FuncHeadAmb2),
+                rhs: vec![NonTerminal(Type)],
+            },
+        );
         table.insert(
             (NonTerminal(FuncHeadAmb2), Terminal(FloatType)),
             GrammarRule {
@@ -1690,7 +2350,7 @@ lazy_static! {
             },
         );
         table.insert(
-            (NonTerminal(FuncParams), Terminal(FloatType)),
+            (NonTerminal(FuncParams), Terminal(BoolType)),
             GrammarRule {
                 lhs: NonTerminal(FuncParams),
                 rhs: vec![
@@ -1710,7 +2370,7 @@ lazy_static! {
             },
         );
         table.insert(
-            (NonTerminal(FuncParams), Terminal(IntegerType)),
+            (NonTerminal(FuncParams), Terminal(FloatType)),
             GrammarRule {
                 lhs: NonTerminal(FuncParams),
                 rhs: vec![
@@ -1729,11 +2389,67 @@ lazy_static! {
                 ],
             },
         );
-
         table.insert(
-            (NonTerminal(Indice), Terminal(OpenSquare)),
+            (NonTerminal(FuncParams), Terminal(IntegerType)),
             GrammarRule {
-                lhs: NonTerminal(Indice),
+                lhs: NonTerminal(FuncParams),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FuncParams)),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FuncParam)),
+                    NonTerminal(Type),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(Id),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ArrayDim)),
+                    NonTerminal(ReptFuncParams0),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptFuncParams1),
+                ],
+            },
+        );
+
+        table.insert(
+            (NonTerminal(EnumDecl), Terminal(Enum)),
+            GrammarRule {
+                lhs: NonTerminal(EnumDecl),
+                rhs: vec![
+                    Terminal(Enum),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::EnumDeclaration)),
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(Id),
+                    SemanticActionType(AddChild),
+                    Terminal(OpenCurly),
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(Id),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptEnumDecl),
+                    Terminal(CloseCurly),
+                    Terminal(SemiColon),
+                ],
+            },
+        );
+
+        table.insert(
+            (NonTerminal(ImportDecl), Terminal(Import)),
+            GrammarRule {
+                lhs: NonTerminal(ImportDecl),
+                rhs: vec![
+                    Terminal(Import),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ImportDeclaration)),
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(StringLit),
+                    SemanticActionType(AddChild),
+                    Terminal(SemiColon),
+                ],
+            },
+        );
+
+        table.insert(
+            (NonTerminal(Indice), Terminal(OpenSquare)),
+            GrammarRule {
+                lhs: NonTerminal(Indice),
                 rhs: vec![
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Indice)),
                     Terminal(OpenSquare),
@@ -1766,6 +2482,17 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(MemberDecl), Terminal(BoolType)),
+            GrammarRule {
+                lhs: NonTerminal(MemberDecl),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::MemberVarDeclaration)),
+                    NonTerminal(VarDecl),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(MemberDecl), Terminal(FloatType)),
             GrammarRule {
@@ -1799,17 +2526,18 @@ lazy_static! {
                 ],
             },
         );
-
         table.insert(
-            (NonTerminal(MultOp), Terminal(And)),
+            (NonTerminal(MemberDecl), Terminal(Const)),
             GrammarRule {
-                lhs: NonTerminal(MultOp),
+                lhs: NonTerminal(MemberDecl),
                 rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::And)),
-                    Terminal(And),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::MemberConstDeclaration)),
+                    NonTerminal(ConstDecl),
+                    SemanticActionType(AddChild),
                 ],
             },
         );
+
         table.insert(
             (NonTerminal(MultOp), Terminal(Div)),
             GrammarRule {
@@ -1820,6 +2548,16 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(MultOp), Terminal(Mod)),
+            GrammarRule {
+                lhs: NonTerminal(MultOp),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Mod)),
+                    Terminal(Mod),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(MultOp), Terminal(Mult)),
             GrammarRule {
@@ -1936,6 +2674,30 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(OptFuncBody), Terminal(For)),
+            GrammarRule {
+                lhs: NonTerminal(OptFuncBody),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::VarBlock)),
+                    SemanticActionType(MakeEmptyNode),
+                    SemanticActionType(AddChild),
+                    EPSILON,
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(OptFuncBody), Terminal(Switch)),
+            GrammarRule {
+                lhs: NonTerminal(OptFuncBody),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::VarBlock)),
+                    SemanticActionType(MakeEmptyNode),
+                    SemanticActionType(AddChild),
+                    EPSILON,
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(OptFuncBody), Terminal(If)),
             GrammarRule {
@@ -1974,6 +2736,14 @@ lazy_static! {
             },
         );
 
+        table.insert(
+            (NonTerminal(OptProg), Terminal(Main)),
+            GrammarRule {
+                lhs: NonTerminal(OptProg),
+                rhs: vec![Terminal(Main), NonTerminal(FuncBody), SemanticActionType(AddChild)],
+            },
+        );
+
         table.insert(
             (NonTerminal(Params), Terminal(Id)),
             GrammarRule {
@@ -2070,6 +2840,18 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(Params), Terminal(BoolLit)),
+            GrammarRule {
+                lhs: NonTerminal(Params),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FuncCallParams)),
+                    NonTerminal(Expr),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptParams),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(Params), Terminal(FloatLit)),
             GrammarRule {
@@ -2094,22 +2876,47 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(Params), Terminal(IntegerType)),
+            GrammarRule {
+                lhs: NonTerminal(Params),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FuncCallParams)),
+                    NonTerminal(Expr),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptParams),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Params), Terminal(FloatType)),
+            GrammarRule {
+                lhs: NonTerminal(Params),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FuncCallParams)),
+                    NonTerminal(Expr),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptParams),
+                ],
+            },
+        );
 
         table.insert(
             (NonTerminal(Prog), Terminal(Main)),
             GrammarRule {
                 lhs: NonTerminal(Prog),
                 rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
+                    SemanticActionType(AddChild),
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
                     NonTerminal(ReptProg0),
                     SemanticActionType(AddChild),
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
                     NonTerminal(ReptProg1),
                     SemanticActionType(AddChild),
-                    Terminal(Main),
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
-                    NonTerminal(FuncBody),
-                    SemanticActionType(AddChild),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
@@ -2119,16 +2926,17 @@ lazy_static! {
             GrammarRule {
                 lhs: NonTerminal(Prog),
                 rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
+                    SemanticActionType(AddChild),
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
                     NonTerminal(ReptProg0),
                     SemanticActionType(AddChild),
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
                     NonTerminal(ReptProg1),
                     SemanticActionType(AddChild),
-                    Terminal(Main),
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
-                    NonTerminal(FuncBody),
-                    SemanticActionType(AddChild),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
@@ -2138,148 +2946,183 @@ lazy_static! {
             GrammarRule {
                 lhs: NonTerminal(Prog),
                 rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
+                    SemanticActionType(AddChild),
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
                     NonTerminal(ReptProg0),
                     SemanticActionType(AddChild),
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
                     NonTerminal(ReptProg1),
                     SemanticActionType(AddChild),
-                    Terminal(Main),
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
-                    NonTerminal(FuncBody),
-                    SemanticActionType(AddChild),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
         );
-
         table.insert(
-            (NonTerminal(RelExpr), Terminal(Id)),
+            (NonTerminal(Prog), Terminal(Const)),
             GrammarRule {
-                lhs: NonTerminal(RelExpr),
+                lhs: NonTerminal(Prog),
                 rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::RelExpr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(RelOp),
-                    NonTerminal(ArithExpr),
-                    SemanticActionType(MakeRelativeOperation),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
                     SemanticActionType(AddChild),
-                ],
-            },
-        );
-        table.insert(
-            (NonTerminal(RelExpr), Terminal(OpenParen)),
-            GrammarRule {
-                lhs: NonTerminal(RelExpr),
-                rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::RelExpr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(RelOp),
-                    NonTerminal(ArithExpr),
-                    SemanticActionType(MakeRelativeOperation),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
+                    NonTerminal(ReptProg0),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
+                    NonTerminal(ReptProg1),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
         );
         table.insert(
-            (NonTerminal(RelExpr), Terminal(Minus)),
+            (NonTerminal(Prog), Terminal(Import)),
             GrammarRule {
-                lhs: NonTerminal(RelExpr),
+                lhs: NonTerminal(Prog),
                 rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::RelExpr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(RelOp),
-                    NonTerminal(ArithExpr),
-                    SemanticActionType(MakeRelativeOperation),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
+                    NonTerminal(ReptProg0),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
+                    NonTerminal(ReptProg1),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
         );
         table.insert(
-            (NonTerminal(RelExpr), Terminal(Plus)),
+            (NonTerminal(Prog), Terminal(Enum)),
             GrammarRule {
-                lhs: NonTerminal(RelExpr),
+                lhs: NonTerminal(Prog),
                 rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::RelExpr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(RelOp),
-                    NonTerminal(ArithExpr),
-                    SemanticActionType(MakeRelativeOperation),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
+                    NonTerminal(ReptProg0),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
+                    NonTerminal(ReptProg1),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
         );
         table.insert(
-            (NonTerminal(RelExpr), Terminal(Question)),
+            (NonTerminal(Prog), Terminal(Id)),
             GrammarRule {
-                lhs: NonTerminal(RelExpr),
+                lhs: NonTerminal(Prog),
                 rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::RelExpr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(RelOp),
-                    NonTerminal(ArithExpr),
-                    SemanticActionType(MakeRelativeOperation),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
+                    NonTerminal(ReptProg0),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
+                    NonTerminal(ReptProg1),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
         );
         table.insert(
-            (NonTerminal(RelExpr), Terminal(Bang)),
+            (NonTerminal(Prog), Terminal(IntegerType)),
             GrammarRule {
-                lhs: NonTerminal(RelExpr),
+                lhs: NonTerminal(Prog),
                 rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::RelExpr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(RelOp),
-                    NonTerminal(ArithExpr),
-                    SemanticActionType(MakeRelativeOperation),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
+                    NonTerminal(ReptProg0),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
+                    NonTerminal(ReptProg1),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
         );
         table.insert(
-            (NonTerminal(RelExpr), Terminal(StringLit)),
+            (NonTerminal(Prog), Terminal(FloatType)),
             GrammarRule {
-                lhs: NonTerminal(RelExpr),
+                lhs: NonTerminal(Prog),
                 rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::RelExpr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(RelOp),
-                    NonTerminal(ArithExpr),
-                    SemanticActionType(MakeRelativeOperation),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
+                    NonTerminal(ReptProg0),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
+                    NonTerminal(ReptProg1),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
         );
         table.insert(
-            (NonTerminal(RelExpr), Terminal(FloatLit)),
+            (NonTerminal(Prog), Terminal(StringType)),
             GrammarRule {
-                lhs: NonTerminal(RelExpr),
+                lhs: NonTerminal(Prog),
                 rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::RelExpr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(RelOp),
-                    NonTerminal(ArithExpr),
-                    SemanticActionType(MakeRelativeOperation),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
+                    NonTerminal(ReptProg0),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
+                    NonTerminal(ReptProg1),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
         );
         table.insert(
-            (NonTerminal(RelExpr), Terminal(IntegerLit)),
+            (NonTerminal(Prog), Terminal(BoolType)),
             GrammarRule {
-                lhs: NonTerminal(RelExpr),
+                lhs: NonTerminal(Prog),
                 rhs: vec![
-                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::RelExpr)),
-                    NonTerminal(ArithExpr),
-                    NonTerminal(RelOp),
-                    NonTerminal(ArithExpr),
-                    SemanticActionType(MakeRelativeOperation),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::GlobalDeclarations)),
+                    NonTerminal(ReptProg2),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ClassDeclarations)),
+                    NonTerminal(ReptProg0),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::FunctionDefinitions)),
+                    NonTerminal(ReptProg1),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Main)),
+                    NonTerminal(OptProg),
                     SemanticActionType(AddChild),
                 ],
             },
         );
 
+
         table.insert(
             (NonTerminal(RelOp), Terminal(GreaterEqualThan)),
             GrammarRule {
@@ -2401,6 +3244,21 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(ReptClassDecl), Terminal(BoolType)),
+            GrammarRule {
+                lhs: NonTerminal(ReptClassDecl),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::MemberDeclaration)),
+                    NonTerminal(Visibility),
+                    SemanticActionType(AddChild),
+                    NonTerminal(MemberDecl),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptClassDecl),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(ReptClassDecl), Terminal(FloatType)),
             GrammarRule {
@@ -2453,6 +3311,21 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(ReptClassDecl), Terminal(Const)),
+            GrammarRule {
+                lhs: NonTerminal(ReptClassDecl),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::MemberDeclaration)),
+                    NonTerminal(Visibility),
+                    SemanticActionType(AddChild),
+                    NonTerminal(MemberDecl),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptClassDecl),
+                ],
+            },
+        );
 
         table.insert(
             (NonTerminal(ReptFuncBody), Terminal(Id)),
@@ -2531,6 +3404,28 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(ReptFuncBody), Terminal(For)),
+            GrammarRule {
+                lhs: NonTerminal(ReptFuncBody),
+                rhs: vec![
+                    NonTerminal(Statement),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptFuncBody),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptFuncBody), Terminal(Switch)),
+            GrammarRule {
+                lhs: NonTerminal(ReptFuncBody),
+                rhs: vec![
+                    NonTerminal(Statement),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptFuncBody),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(ReptFuncBody), Terminal(If)),
             GrammarRule {
@@ -2630,6 +3525,27 @@ lazy_static! {
             },
         );
 
+        table.insert(
+            (NonTerminal(ReptEnumDecl), Terminal(CloseCurly)),
+            GrammarRule {
+                lhs: NonTerminal(ReptEnumDecl),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptEnumDecl), Terminal(Comma)),
+            GrammarRule {
+                lhs: NonTerminal(ReptEnumDecl),
+                rhs: vec![
+                    Terminal(Comma),
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(Id),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptEnumDecl),
+                ],
+            },
+        );
+
         table.insert(
             (NonTerminal(ReptOptClassDecl), Terminal(OpenCurly)),
             GrammarRule {
@@ -2673,6 +3589,17 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(ReptOptFuncBody), Terminal(BoolType)),
+            GrammarRule {
+                lhs: NonTerminal(ReptOptFuncBody),
+                rhs: vec![
+                    NonTerminal(VarDecl),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptOptFuncBody),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(ReptOptFuncBody), Terminal(FloatType)),
             GrammarRule {
@@ -2702,6 +3629,17 @@ lazy_static! {
                 rhs: vec![EPSILON],
             },
         );
+        table.insert(
+            (NonTerminal(ReptOptFuncBody), Terminal(Const)),
+            GrammarRule {
+                lhs: NonTerminal(ReptOptFuncBody),
+                rhs: vec![
+                    NonTerminal(ConstDecl),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptOptFuncBody),
+                ],
+            },
+        );
 
         table.insert(
             (NonTerminal(ReptParams), Terminal(CloseParen)),
@@ -2768,6 +3706,116 @@ lazy_static! {
             },
         );
 
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(Const)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![
+                    NonTerminal(ConstDecl),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptProg2),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(Import)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![
+                    NonTerminal(ImportDecl),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptProg2),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(Enum)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![
+                    NonTerminal(EnumDecl),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptProg2),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(Id)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![
+                    NonTerminal(VarDecl),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptProg2),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(IntegerType)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![
+                    NonTerminal(VarDecl),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptProg2),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(FloatType)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![
+                    NonTerminal(VarDecl),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptProg2),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(StringType)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![
+                    NonTerminal(VarDecl),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptProg2),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(BoolType)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![
+                    NonTerminal(VarDecl),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptProg2),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(Main)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(Func)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptProg2), Terminal(Class)),
+            GrammarRule {
+                lhs: NonTerminal(ReptProg2),
+                rhs: vec![EPSILON],
+            },
+        );
+
         table.insert(
             (NonTerminal(ReptStatBlock), Terminal(Id)),
             GrammarRule {
@@ -2845,6 +3893,28 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(ReptStatBlock), Terminal(For)),
+            GrammarRule {
+                lhs: NonTerminal(ReptStatBlock),
+                rhs: vec![
+                    NonTerminal(Statement),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptStatBlock),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptStatBlock), Terminal(Switch)),
+            GrammarRule {
+                lhs: NonTerminal(ReptStatBlock),
+                rhs: vec![
+                    NonTerminal(Statement),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptStatBlock),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(ReptStatBlock), Terminal(If)),
             GrammarRule {
@@ -2863,6 +3933,20 @@ lazy_static! {
                 rhs: vec![EPSILON],
             },
         );
+        table.insert(
+            (NonTerminal(ReptStatBlock), Terminal(Case)),
+            GrammarRule {
+                lhs: NonTerminal(ReptStatBlock),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptStatBlock), Terminal(Default)),
+            GrammarRule {
+                lhs: NonTerminal(ReptStatBlock),
+                rhs: vec![EPSILON],
+            },
+        );
 
         table.insert(
             (NonTerminal(ReptVarDecl), Terminal(SemiColon)),
@@ -2968,14 +4052,21 @@ lazy_static! {
             },
         );
         table.insert(
-            (NonTerminal(ReptVariable), Terminal(And)),
+            (NonTerminal(ReptVariable), Terminal(And)),
+            GrammarRule {
+                lhs: NonTerminal(ReptVariable),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptVariable), Terminal(Div)),
             GrammarRule {
                 lhs: NonTerminal(ReptVariable),
                 rhs: vec![EPSILON],
             },
         );
         table.insert(
-            (NonTerminal(ReptVariable), Terminal(Div)),
+            (NonTerminal(ReptVariable), Terminal(Mod)),
             GrammarRule {
                 lhs: NonTerminal(ReptVariable),
                 rhs: vec![EPSILON],
@@ -3133,14 +4224,121 @@ lazy_static! {
             (NonTerminal(RightRecArithExpr), Terminal(Or)),
             GrammarRule {
                 lhs: NonTerminal(RightRecArithExpr),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecArithExpr), Terminal(And)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecArithExpr),
+                rhs: vec![EPSILON],
+            },
+        );
+
+        table.insert(
+            (NonTerminal(RightRecConjunction), Terminal(And)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecConjunction),
                 rhs: vec![
-                    NonTerminal(AddOp),
-                    NonTerminal(Term),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::And)),
+                    Terminal(And),
+                    NonTerminal(Comparison),
                     SemanticActionType(MakeRelativeOperation),
-                    NonTerminal(RightRecArithExpr),
+                    NonTerminal(RightRecConjunction),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecConjunction), Terminal(CloseParen)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecConjunction),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecConjunction), Terminal(SemiColon)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecConjunction),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecConjunction), Terminal(Comma)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecConjunction),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecConjunction), Terminal(CloseSquare)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecConjunction),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecConjunction), Terminal(Colon)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecConjunction),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecConjunction), Terminal(Or)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecConjunction),
+                rhs: vec![EPSILON],
+            },
+        );
+
+        table.insert(
+            (NonTerminal(RightRecExpr), Terminal(Or)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecExpr),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Or)),
+                    Terminal(Or),
+                    NonTerminal(Conjunction),
+                    SemanticActionType(MakeRelativeOperation),
+                    NonTerminal(RightRecExpr),
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(RightRecExpr), Terminal(CloseParen)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecExpr),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecExpr), Terminal(SemiColon)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecExpr),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecExpr), Terminal(Comma)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecExpr),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecExpr), Terminal(CloseSquare)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecExpr),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecExpr), Terminal(Colon)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecExpr),
+                rhs: vec![EPSILON],
+            },
+        );
 
         table.insert(
             (NonTerminal(RightRecTerm), Terminal(CloseParen)),
@@ -3221,6 +4419,13 @@ lazy_static! {
         );
         table.insert(
             (NonTerminal(RightRecTerm), Terminal(And)),
+            GrammarRule {
+                lhs: NonTerminal(RightRecTerm),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(RightRecTerm), Terminal(Div)),
             GrammarRule {
                 lhs: NonTerminal(RightRecTerm),
                 rhs: vec![
@@ -3232,7 +4437,7 @@ lazy_static! {
             },
         );
         table.insert(
-            (NonTerminal(RightRecTerm), Terminal(Div)),
+            (NonTerminal(RightRecTerm), Terminal(Mod)),
             GrammarRule {
                 lhs: NonTerminal(RightRecTerm),
                 rhs: vec![
@@ -3381,6 +4586,28 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(StatBlock), Terminal(For)),
+            GrammarRule {
+                lhs: NonTerminal(StatBlock),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::StatBlock)),
+                    NonTerminal(Statement),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(StatBlock), Terminal(Switch)),
+            GrammarRule {
+                lhs: NonTerminal(StatBlock),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::StatBlock)),
+                    NonTerminal(Statement),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(StatBlock), Terminal(Else)),
             GrammarRule {
@@ -3505,7 +4732,30 @@ lazy_static! {
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::WhileStatement)),
                     Terminal(While),
                     Terminal(OpenParen),
-                    NonTerminal(RelExpr),
+                    NonTerminal(Expr),
+                    SemanticActionType(AddChild),
+                    Terminal(CloseParen),
+                    NonTerminal(StatBlock),
+                    SemanticActionType(AddChild),
+                    Terminal(SemiColon),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Statement), Terminal(For)),
+            GrammarRule {
+                lhs: NonTerminal(Statement),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ForStatement)),
+                    Terminal(For),
+                    Terminal(OpenParen),
+                    NonTerminal(AssignStat),
+                    SemanticActionType(AddChild),
+                    Terminal(SemiColon),
+                    NonTerminal(Expr),
+                    SemanticActionType(AddChild),
+                    Terminal(SemiColon),
+                    NonTerminal(AssignStat),
                     SemanticActionType(AddChild),
                     Terminal(CloseParen),
                     NonTerminal(StatBlock),
@@ -3514,6 +4764,79 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(Statement), Terminal(Switch)),
+            GrammarRule {
+                lhs: NonTerminal(Statement),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::SwitchStatement)),
+                    Terminal(Switch),
+                    Terminal(OpenParen),
+                    NonTerminal(Expr),
+                    SemanticActionType(AddChild),
+                    Terminal(CloseParen),
+                    Terminal(OpenCurly),
+                    NonTerminal(ReptSwitchCases),
+                    Terminal(CloseCurly),
+                    Terminal(SemiColon),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptSwitchCases), Terminal(Case)),
+            GrammarRule {
+                lhs: NonTerminal(ReptSwitchCases),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Case)),
+                    Terminal(Case),
+                    NonTerminal(ArithExpr),
+                    SemanticActionType(AddChild),
+                    Terminal(Colon),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::StatBlock)),
+                    NonTerminal(ReptStatBlock),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptSwitchCases),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptSwitchCases), Terminal(Default)),
+            GrammarRule {
+                lhs: NonTerminal(ReptSwitchCases),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::DefaultCase)),
+                    Terminal(Default),
+                    Terminal(Colon),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::StatBlock)),
+                    NonTerminal(ReptStatBlock),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(AddChild),
+                    NonTerminal(ReptSwitchCases),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(ReptSwitchCases), Terminal(CloseCurly)),
+            GrammarRule {
+                lhs: NonTerminal(ReptSwitchCases),
+                rhs: vec![EPSILON],
+            },
+        );
+        table.insert(
+            (NonTerminal(AssignStat), Terminal(Id)),
+            GrammarRule {
+                lhs: NonTerminal(AssignStat),
+                rhs: vec![
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(Id),
+                    NonTerminal(ReptVariable),
+                    NonTerminal(AssignOp),
+                    NonTerminal(Expr),
+                    SemanticActionType(MakeRelativeOperation),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(Statement), Terminal(If)),
             GrammarRule {
@@ -3522,7 +4845,7 @@ lazy_static! {
                     SemanticActionType(MakeFamilyRootNode(InternalNodeType::IfStatement)),
                     Terminal(If),
                     Terminal(OpenParen),
-                    NonTerminal(RelExpr),
+                    NonTerminal(Expr),
                     SemanticActionType(AddChild),
                     Terminal(CloseParen),
                     Terminal(Then),
@@ -3720,6 +5043,18 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(Term), Terminal(BoolLit)),
+            GrammarRule {
+                lhs: NonTerminal(Term),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Term)),
+                    NonTerminal(Factor),
+                    NonTerminal(RightRecTerm),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(Term), Terminal(FloatLit)),
             GrammarRule {
@@ -3744,6 +5079,30 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(Term), Terminal(IntegerType)),
+            GrammarRule {
+                lhs: NonTerminal(Term),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Term)),
+                    NonTerminal(Factor),
+                    NonTerminal(RightRecTerm),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
+        table.insert(
+            (NonTerminal(Term), Terminal(FloatType)),
+            GrammarRule {
+                lhs: NonTerminal(Term),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::Term)),
+                    NonTerminal(Factor),
+                    NonTerminal(RightRecTerm),
+                    SemanticActionType(AddChild),
+                ],
+            },
+        );
 
         table.insert(
             (NonTerminal(Type), Terminal(Id)),
@@ -3759,6 +5118,13 @@ lazy_static! {
                 rhs: vec![SemanticActionType(MakeTerminalNode), Terminal(StringType)],
             },
         );
+        table.insert(
+            (NonTerminal(Type), Terminal(BoolType)),
+            GrammarRule {
+                lhs: NonTerminal(Type),
+                rhs: vec![SemanticActionType(MakeTerminalNode), Terminal(BoolType)],
+            },
+        );
         table.insert(
             (NonTerminal(Type), Terminal(FloatType)),
             GrammarRule {
@@ -3810,6 +5176,24 @@ lazy_static! {
                 ],
             },
         );
+        table.insert(
+            (NonTerminal(VarDecl), Terminal(BoolType)),
+            GrammarRule {
+                lhs: NonTerminal(VarDecl),
+                rhs: vec![
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::VarDeclaration)),
+                    NonTerminal(Type),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeTerminalNode),
+                    Terminal(Id),
+                    SemanticActionType(AddChild),
+                    SemanticActionType(MakeFamilyRootNode(InternalNodeType::ArrayDim)),
+                    NonTerminal(ReptVarDecl),
+                    SemanticActionType(AddChild),
+                    Terminal(SemiColon),
+                ],
+            },
+        );
         table.insert(
             (NonTerminal(VarDecl), Terminal(FloatType)),
             GrammarRule {
@@ -3945,6 +5329,13 @@ lazy_static! {
                 rhs: vec![SemanticActionType(MakeEmptyNode), EPSILON],
             },
         );
+        table.insert(
+            (NonTerminal(Visibility), Terminal(BoolType)),
+            GrammarRule {
+                lhs: NonTerminal(Visibility),
+                rhs: vec![SemanticActionType(MakeEmptyNode), EPSILON],
+            },
+        );
         table.insert(
             (NonTerminal(Visibility), Terminal(FloatType)),
             GrammarRule {
@@ -3966,6 +5357,13 @@ lazy_static! {
                 rhs: vec![SemanticActionType(MakeEmptyNode), EPSILON],
             },
         );
+        table.insert(
+            (NonTerminal(Visibility), Terminal(Const)),
+            GrammarRule {
+                lhs: NonTerminal(Visibility),
+                rhs: vec![SemanticActionType(MakeEmptyNode), EPSILON],
+            },
+        );
 
         table
     };
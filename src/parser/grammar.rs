@@ -42,7 +42,12 @@ pub enum NamedSymbol {
     ArraySize,
     ArraySizeAmb1,
     AssignOp,
+    AssignStat,
     ClassDecl,
+    Comparison,
+    Conjunction,
+    ConstDecl,
+    EnumDecl,
     Expr,
     ExprAmb1,
     Factor,
@@ -56,16 +61,18 @@ pub enum NamedSymbol {
     FuncHeadAmb1,
     FuncHeadAmb2,
     FuncParams,
+    ImportDecl,
     Indice,
     MemberDecl,
     MultOp,
     OptClassDecl,
     OptFuncBody,
+    OptProg,
     Params,
     Prog,
-    RelExpr,
     RelOp,
     ReptClassDecl,
+    ReptEnumDecl,
     ReptFuncBody,
     ReptFuncParams0,
     ReptFuncParams1,
@@ -75,10 +82,14 @@ pub enum NamedSymbol {
     ReptParams,
     ReptProg0,
     ReptProg1,
+    ReptProg2,
     ReptStatBlock,
+    ReptSwitchCases,
     ReptVarDecl,
     ReptVariable,
     RightRecArithExpr,
+    RightRecConjunction,
+    RightRecExpr,
     RightRecTerm,
     Sign,
     StatBlock,
@@ -104,7 +115,12 @@ impl NamedSymbol {
             ArraySize => ARRAYSIZE_FIRST,
             ArraySizeAmb1 => ARRAYSIZEAMB1_FIRST,
             AssignOp => ASSIGNOP_FIRST,
+            AssignStat => ASSIGNSTAT_FIRST,
             ClassDecl => CLASSDECL_FIRST,
+            Comparison => COMPARISON_FIRST,
+            Conjunction => CONJUNCTION_FIRST,
+            ConstDecl => CONSTDECL_FIRST,
+            EnumDecl => ENUMDECL_FIRST,
             Expr => EXPR_FIRST,
             ExprAmb1 => EXPRAMB1_FIRST,
             Factor => FACTOR_FIRST,
@@ -118,16 +134,18 @@ impl NamedSymbol {
             FuncHeadAmb1 => FUNCHEADAMB1_FIRST,
             FuncHeadAmb2 => FUNCHEADAMB2_FIRST,
             FuncParams => FUNCPARAMS_FIRST,
+            ImportDecl => IMPORTDECL_FIRST,
             Indice => INDICE_FIRST,
             MemberDecl => MEMBERDECL_FIRST,
             MultOp => MULTOP_FIRST,
             OptClassDecl => OPTCLASSDECL_FIRST,
             OptFuncBody => OPTFUNCBODY_FIRST,
+            OptProg => OPTPROG_FIRST,
             Params => PARAMS_FIRST,
             Prog => PROG_FIRST,
-            RelExpr => RELEXPR_FIRST,
             RelOp => RELOP_FIRST,
             ReptClassDecl => REPTCLASSDECL_FIRST,
+            ReptEnumDecl => REPTENUMDECL_FIRST,
             ReptFuncBody => REPTFUNCBODY_FIRST,
             ReptFuncParams0 => REPTFUNCPARAMS0_FIRST,
             ReptFuncParams1 => REPTFUNCPARAMS1_FIRST,
@@ -137,10 +155,14 @@ impl NamedSymbol {
             ReptParams => REPTPARAMS_FIRST,
             ReptProg0 => REPTPROG0_FIRST,
             ReptProg1 => REPTPROG1_FIRST,
+            ReptProg2 => REPTPROG2_FIRST,
             ReptStatBlock => REPTSTATBLOCK_FIRST,
+            ReptSwitchCases => REPTSWITCHCASES_FIRST,
             ReptVarDecl => REPTVARDECL_FIRST,
             ReptVariable => REPTVARIABLE_FIRST,
             RightRecArithExpr => RIGHTRECARITHEXPR_FIRST,
+            RightRecConjunction => RIGHTRECCONJUNCTION_FIRST,
+            RightRecExpr => RIGHTRECEXPR_FIRST,
             RightRecTerm => RIGHTRECTERM_FIRST,
             Sign => SIGN_FIRST,
             StatBlock => STATBLOCK_FIRST,
@@ -165,7 +187,12 @@ impl NamedSymbol {
             ArraySize => ARRAYSIZE_FOLLOW,
             ArraySizeAmb1 => ARRAYSIZEAMB1_FOLLOW,
             AssignOp => ASSIGNOP_FOLLOW,
+            AssignStat => ASSIGNSTAT_FOLLOW,
             ClassDecl => CLASSDECL_FOLLOW,
+            Comparison => COMPARISON_FOLLOW,
+            Conjunction => CONJUNCTION_FOLLOW,
+            ConstDecl => CONSTDECL_FOLLOW,
+            EnumDecl => ENUMDECL_FOLLOW,
             Expr => EXPR_FOLLOW,
             ExprAmb1 => EXPRAMB1_FOLLOW,
             Factor => FACTOR_FOLLOW,
@@ -179,16 +206,18 @@ impl NamedSymbol {
             FuncHeadAmb1 => FUNCHEADAMB1_FOLLOW,
             FuncHeadAmb2 => FUNCHEADAMB2_FOLLOW,
             FuncParams => FUNCPARAMS_FOLLOW,
+            ImportDecl => IMPORTDECL_FOLLOW,
             Indice => INDICE_FOLLOW,
             MemberDecl => MEMBERDECL_FOLLOW,
             MultOp => MULTOP_FOLLOW,
             OptClassDecl => OPTCLASSDECL_FOLLOW,
             OptFuncBody => OPTFUNCBODY_FOLLOW,
+            OptProg => OPTPROG_FOLLOW,
             Params => PARAMS_FOLLOW,
             Prog => PROG_FOLLOW,
-            RelExpr => RELEXPR_FOLLOW,
             RelOp => RELOP_FOLLOW,
             ReptClassDecl => REPTCLASSDECL_FOLLOW,
+            ReptEnumDecl => REPTENUMDECL_FOLLOW,
             ReptFuncBody => REPTFUNCBODY_FOLLOW,
             ReptFuncParams0 => REPTFUNCPARAMS0_FOLLOW,
             ReptFuncParams1 => REPTFUNCPARAMS1_FOLLOW,
@@ -198,10 +227,14 @@ impl NamedSymbol {
             ReptParams => REPTPARAMS_FOLLOW,
             ReptProg0 => REPTPROG0_FOLLOW,
             ReptProg1 => REPTPROG1_FOLLOW,
+            ReptProg2 => REPTPROG2_FOLLOW,
             ReptStatBlock => REPTSTATBLOCK_FOLLOW,
+            ReptSwitchCases => REPTSWITCHCASES_FOLLOW,
             ReptVarDecl => REPTVARDECL_FOLLOW,
             ReptVariable => REPTVARIABLE_FOLLOW,
             RightRecArithExpr => RIGHTRECARITHEXPR_FOLLOW,
+            RightRecConjunction => RIGHTRECCONJUNCTION_FOLLOW,
+            RightRecExpr => RIGHTRECEXPR_FOLLOW,
             RightRecTerm => RIGHTRECTERM_FOLLOW,
             Sign => SIGN_FOLLOW,
             StatBlock => STATBLOCK_FOLLOW,
@@ -220,17 +253,57 @@ impl NamedSymbol {
 }
 
 /// A Derivation table keeps track of the derivation steps
-#[derive(Debug)]
-pub struct DerivationTable(pub(crate) Vec<DerivationRecord>);
+#[derive(Debug, Default)]
+pub struct DerivationTable {
+    records: Vec<DerivationRecord>,
+    matched_tokens: Vec<Token>,
+}
 
 impl DerivationTable {
     /// Adds a [DerivationRecord] to the table
     pub fn add_record(&mut self, record: DerivationRecord) {
-        self.0.push(record);
+        self.records.push(record);
+    }
+
+    /// Records a token that was matched against a terminal on top of the parsing stack
+    pub fn add_matched_token(&mut self, token: &Token) {
+        self.matched_tokens.push(token.clone());
     }
+
     /// Creates a new empty DerivationTable
     pub fn new() -> Self {
-        DerivationTable(Vec::new())
+        Self::default()
+    }
+
+    /// Returns the number of tokens matched so far
+    pub fn matched_count(&self) -> usize {
+        self.matched_tokens.len()
+    }
+
+    /// Returns the recorded derivation steps, in order
+    pub fn records(&self) -> &Vec<DerivationRecord> {
+        &self.records
+    }
+
+    /// Returns the leftmost sentential form at a given step.
+    /// Matched input comes first (as lexemes), followed by the symbols still on the parsing stack.
+    pub fn sentential_form(&self, record: &DerivationRecord) -> Vec<String> {
+        let mut form: Vec<String> = self.matched_tokens[..record.matched_count]
+            .iter()
+            .map(|token| token.lexeme().to_string())
+            .collect();
+
+        for symbol in record.stack_state.iter().rev() {
+            match symbol {
+                GrammarSymbol::Terminal(token_type) => form.push(format!("'{:?}'", token_type)),
+                GrammarSymbol::NonTerminal(named_symbol) => {
+                    form.push(format!("<{:?}>", named_symbol))
+                }
+                _ => {}
+            }
+        }
+
+        form
     }
 }
 
@@ -240,6 +313,8 @@ pub struct DerivationRecord {
     pub stack_state: Vec<GrammarSymbol>,
     pub lookahead_token: Option<Token>,
     pub derived_rule: Option<GrammarRule>,
+    /// Number of tokens matched so far
+    pub matched_count: usize,
 }
 
 impl DerivationRecord {
@@ -247,11 +322,13 @@ impl DerivationRecord {
         stack_state: &Vec<GrammarSymbol>,
         lookahead_token: &Option<Token>,
         derived_rule: Option<&GrammarRule>,
+        matched_count: usize,
     ) -> Self {
         Self {
             stack_state: stack_state.clone(),
             lookahead_token: lookahead_token.clone(),
             derived_rule: derived_rule.cloned(),
+            matched_count,
         }
     }
 }
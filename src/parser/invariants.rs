@@ -0,0 +1,682 @@
+//! Structural invariants of the Abstract Syntax Tree
+//!
+//! Every [InternalNodeType] has a [NodeSchema] describing the children the grammar's semantic actions give it.
+//! Later phases rely on these shapes, so a tree is checked right after it is built and any
+//! violation is reported as an [InternalCompilerError] naming the grammar rule that builds the node.
+
+use crate::lexer::token::TokenType;
+use crate::parser::ast::{InternalNodeType, Node, NodeVal};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+/// A node that doesn't match its [NodeSchema]. This is always a bug in the compiler, not in the source program.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InternalCompilerError {
+    /// Line of the first token under the offending node, if any
+    pub line_num: Option<usize>,
+    /// The offending node
+    pub node: String,
+    /// The grammar rule responsible for building the node
+    pub rule: &'static str,
+    pub message: String,
+}
+
+impl Display for InternalCompilerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.line_num {
+            None => write!(f, "Internal compiler error: ")?,
+            Some(line_num) => write!(f, "Internal compiler error at line {}: ", line_num)?,
+        }
+        write!(
+            f,
+            "{} in {} (built by {})",
+            self.message, self.node, self.rule
+        )
+    }
+}
+
+/// What a single child of a node may be
+#[derive(Debug, Copy, Clone)]
+pub enum ChildKind {
+    /// An internal node of the given type
+    Internal(InternalNodeType),
+    /// A leaf made from a token of the given type
+    Leaf(TokenType),
+    /// An empty node
+    Empty,
+}
+
+/// Expected children of a node. Each slot lists the [ChildKind]s allowed in it.
+#[derive(Debug, Copy, Clone)]
+pub enum Shape {
+    /// Exactly one child per slot, in order
+    Sequence(&'static [&'static [ChildKind]]),
+    /// Any number of children, each matching the slot
+    List(&'static [ChildKind]),
+    /// Either a single empty node, or any number of children matching the slot
+    EmptyOrList(&'static [ChildKind]),
+    /// A [Shape::Sequence] followed by any number of children matching the trailing slot
+    SequenceThenList(&'static [&'static [ChildKind]], &'static [ChildKind]),
+}
+
+/// Expected [Shape] of a node, and the grammar rule that builds it
+#[derive(Debug, Copy, Clone)]
+pub struct NodeSchema {
+    pub rule: &'static str,
+    pub shape: Shape,
+}
+
+use ChildKind::{Empty, Internal, Leaf};
+use InternalNodeType as Nt;
+
+const TYPE: &[ChildKind] = &[
+    Leaf(TokenType::Id),
+    Leaf(TokenType::IntegerType),
+    Leaf(TokenType::FloatType),
+    Leaf(TokenType::StringType),
+    Leaf(TokenType::BoolType),
+];
+
+const RETURN_TYPE: &[ChildKind] = &[
+    Leaf(TokenType::Id),
+    Leaf(TokenType::IntegerType),
+    Leaf(TokenType::FloatType),
+    Leaf(TokenType::StringType),
+    Leaf(TokenType::BoolType),
+    Leaf(TokenType::Void),
+];
+
+const STATEMENT: &[ChildKind] = &[
+    Internal(Nt::GenericStatement),
+    Internal(Nt::IfStatement),
+    Internal(Nt::WhileStatement),
+    Internal(Nt::ForStatement),
+    Internal(Nt::SwitchStatement),
+    Internal(Nt::ReadStatement),
+    Internal(Nt::WriteStatement),
+    Internal(Nt::ReturnStatement),
+    Internal(Nt::BreakStatement),
+    Internal(Nt::ContinueStatement),
+];
+
+/// An identifier, possibly followed by member accesses
+const VARIABLE: &[ChildKind] = &[Leaf(TokenType::Id), Internal(Nt::DotOp)];
+
+const COMPARISON: &[ChildKind] = &[
+    Internal(Nt::ArithExpr),
+    Internal(Nt::Equal),
+    Internal(Nt::NotEqual),
+    Internal(Nt::LessThan),
+    Internal(Nt::GreaterThan),
+    Internal(Nt::LessEqualThan),
+    Internal(Nt::GreaterEqualThan),
+];
+
+const CONJUNCTION_OPERAND: &[ChildKind] = &[
+    Internal(Nt::ArithExpr),
+    Internal(Nt::Equal),
+    Internal(Nt::NotEqual),
+    Internal(Nt::LessThan),
+    Internal(Nt::GreaterThan),
+    Internal(Nt::LessEqualThan),
+    Internal(Nt::GreaterEqualThan),
+    Internal(Nt::And),
+];
+
+const EXPR_OPERAND: &[ChildKind] = &[
+    Internal(Nt::ArithExpr),
+    Internal(Nt::Equal),
+    Internal(Nt::NotEqual),
+    Internal(Nt::LessThan),
+    Internal(Nt::GreaterThan),
+    Internal(Nt::LessEqualThan),
+    Internal(Nt::GreaterEqualThan),
+    Internal(Nt::And),
+    Internal(Nt::Or),
+];
+
+const ARITH_OPERAND: &[ChildKind] = &[
+    Internal(Nt::Term),
+    Internal(Nt::Add),
+    Internal(Nt::Sub),
+];
+
+const FACTOR: &[ChildKind] = &[
+    Internal(Nt::Factor),
+    Internal(Nt::Expr),
+    Internal(Nt::SignedFactor),
+    Internal(Nt::Negation),
+    Internal(Nt::TernaryOperation),
+    Internal(Nt::Cast),
+    Leaf(TokenType::IntegerLit),
+    Leaf(TokenType::FloatLit),
+    Leaf(TokenType::StringLit),
+    Leaf(TokenType::BoolLit),
+];
+
+const TERM_OPERAND: &[ChildKind] = &[
+    Internal(Nt::Factor),
+    Internal(Nt::Expr),
+    Internal(Nt::SignedFactor),
+    Internal(Nt::Negation),
+    Internal(Nt::TernaryOperation),
+    Internal(Nt::Cast),
+    Leaf(TokenType::IntegerLit),
+    Leaf(TokenType::FloatLit),
+    Leaf(TokenType::StringLit),
+    Leaf(TokenType::BoolLit),
+    Internal(Nt::Mult),
+    Internal(Nt::Div),
+    Internal(Nt::Mod),
+];
+
+/// Returns the [NodeSchema] of a given [InternalNodeType]
+pub fn node_schema(node_t: InternalNodeType) -> NodeSchema {
+    let (rule, shape) = match node_t {
+        Nt::Root => (
+            "Start -> Prog",
+            Shape::Sequence(&[
+                &[Internal(Nt::GlobalDeclarations)],
+                &[Internal(Nt::ClassDeclarations)],
+                &[Internal(Nt::FunctionDefinitions)],
+                &[Internal(Nt::Main)],
+            ]),
+        ),
+        Nt::GlobalDeclarations => (
+            "Prog -> ReptProg2 ReptProg0 ReptProg1 OptProg",
+            Shape::List(&[
+                Internal(Nt::ConstDeclaration),
+                Internal(Nt::VarDeclaration),
+                Internal(Nt::ImportDeclaration),
+                Internal(Nt::EnumDeclaration),
+            ]),
+        ),
+        Nt::ImportDeclaration => (
+            "ImportDecl -> 'import' 'stringLit' ';'",
+            Shape::Sequence(&[&[Leaf(TokenType::StringLit)]]),
+        ),
+        // the enum's identifier, then its enumerators
+        Nt::EnumDeclaration => (
+            "EnumDecl -> 'enum' 'id' '{' 'id' ReptEnumDecl '}' ';'",
+            Shape::SequenceThenList(
+                &[&[Leaf(TokenType::Id)], &[Leaf(TokenType::Id)]],
+                &[Leaf(TokenType::Id)],
+            ),
+        ),
+        Nt::ClassDeclarations => (
+            "Prog -> ReptProg2 ReptProg0 ReptProg1 OptProg",
+            Shape::List(&[Internal(Nt::ClassDeclaration)]),
+        ),
+        Nt::FunctionDefinitions => (
+            "Prog -> ReptProg2 ReptProg0 ReptProg1 OptProg",
+            Shape::List(&[Internal(Nt::FuncDef)]),
+        ),
+        // an imported file has no main function
+        Nt::Main => (
+            "OptProg -> 'main' FuncBody",
+            Shape::List(&[Internal(Nt::FuncBody)]),
+        ),
+        Nt::ClassDeclaration => (
+            "ClassDecl -> 'class' 'id' OptClassDecl '{' ReptClassDecl '}' ';'",
+            Shape::Sequence(&[
+                &[Leaf(TokenType::Id)],
+                &[Internal(Nt::InheritList)],
+                &[Internal(Nt::MemberList)],
+            ]),
+        ),
+        Nt::InheritList => (
+            "OptClassDecl -> 'inherits' 'id' ReptOptClassDecl",
+            Shape::List(&[Leaf(TokenType::Id)]),
+        ),
+        Nt::MemberList => (
+            "ReptClassDecl -> Visibility MemberDecl ReptClassDecl",
+            Shape::List(&[Internal(Nt::MemberDeclaration)]),
+        ),
+        Nt::MemberDeclaration => (
+            "ReptClassDecl -> Visibility MemberDecl ReptClassDecl",
+            Shape::Sequence(&[
+                &[Leaf(TokenType::Public), Leaf(TokenType::Private), Empty],
+                &[
+                    Internal(Nt::MemberVarDeclaration),
+                    Internal(Nt::MemberFuncDeclaration),
+                    Internal(Nt::MemberConstDeclaration),
+                ],
+            ]),
+        ),
+        Nt::MemberVarDeclaration => (
+            "MemberDecl -> VarDecl",
+            Shape::Sequence(&[&[Internal(Nt::VarDeclaration)]]),
+        ),
+        Nt::MemberConstDeclaration => (
+            "MemberDecl -> ConstDecl",
+            Shape::Sequence(&[&[Internal(Nt::ConstDeclaration)]]),
+        ),
+        Nt::MemberFuncDeclaration => (
+            "MemberDecl -> FuncDecl",
+            Shape::Sequence(&[&[Internal(Nt::FuncDeclaration)]]),
+        ),
+        Nt::FuncDeclaration => (
+            "FuncDecl -> 'func' 'id' '(' FuncParams ')' ':' FuncDeclAmb1",
+            Shape::Sequence(&[
+                &[Leaf(TokenType::Id)],
+                &[Internal(Nt::FuncParams)],
+                RETURN_TYPE,
+            ]),
+        ),
+        Nt::VarDeclaration => (
+            "VarDecl -> Type 'id' ReptVarDecl ';'",
+            Shape::Sequence(&[TYPE, &[Leaf(TokenType::Id)], &[Internal(Nt::ArrayDim)]]),
+        ),
+        Nt::ConstDeclaration => (
+            "ConstDecl -> 'const' Type 'id' '=' Expr ';'",
+            Shape::Sequence(&[TYPE, &[Leaf(TokenType::Id)], &[Internal(Nt::Expr)]]),
+        ),
+        Nt::ArrayDim => (
+            "ArraySize -> '[' ArraySizeAmb1",
+            Shape::List(&[Leaf(TokenType::IntegerLit), Leaf(TokenType::Id), Empty]),
+        ),
+        Nt::FuncDef => (
+            "FuncDef -> FuncHead FuncBody",
+            Shape::Sequence(&[
+                &[Leaf(TokenType::Id)],
+                &[Leaf(TokenType::Id), Empty],
+                &[Internal(Nt::FuncParams)],
+                RETURN_TYPE,
+                &[Internal(Nt::FuncBody)],
+            ]),
+        ),
+        Nt::FuncParams => (
+            "FuncParams -> Type 'id' ReptFuncParams0 ReptFuncParams1",
+            Shape::EmptyOrList(&[Internal(Nt::FuncParam)]),
+        ),
+        Nt::FuncParam => (
+            "FuncParams -> Type 'id' ReptFuncParams0 ReptFuncParams1",
+            Shape::Sequence(&[TYPE, &[Leaf(TokenType::Id)], &[Internal(Nt::ArrayDim)]]),
+        ),
+        Nt::FuncBody => (
+            "FuncBody -> '{' OptFuncBody ReptFuncBody '}'",
+            Shape::Sequence(&[&[Internal(Nt::VarBlock)], &[Internal(Nt::StatementList)]]),
+        ),
+        Nt::VarBlock => (
+            "OptFuncBody -> 'var' '{' ReptOptFuncBody '}'",
+            Shape::EmptyOrList(&[
+                Internal(Nt::VarDeclaration),
+                Internal(Nt::ConstDeclaration),
+            ]),
+        ),
+        Nt::StatementList => (
+            "FuncBody -> '{' OptFuncBody ReptFuncBody '}'",
+            Shape::List(STATEMENT),
+        ),
+        Nt::StatBlock => (
+            "StatBlock -> '{' ReptStatBlock '}' | Statement | EPSILON",
+            Shape::EmptyOrList(STATEMENT),
+        ),
+        Nt::GenericStatement => (
+            "Statement -> 'id' StatementAmb1",
+            Shape::Sequence(&[&[
+                Internal(Nt::Assignment),
+                Leaf(TokenType::Id),
+                Internal(Nt::DotOp),
+            ]]),
+        ),
+        Nt::Assignment => (
+            "StatementAmb1 | AssignStat -> AssignOp Expr",
+            Shape::Sequence(&[VARIABLE, &[Internal(Nt::Expr)]]),
+        ),
+        Nt::IfStatement => (
+            "Statement -> 'if' '(' Expr ')' 'then' StatBlock 'else' StatBlock ';'",
+            Shape::Sequence(&[
+                &[Internal(Nt::Expr)],
+                &[Internal(Nt::StatBlock)],
+                &[Internal(Nt::StatBlock)],
+            ]),
+        ),
+        Nt::WhileStatement => (
+            "Statement -> 'while' '(' Expr ')' StatBlock ';'",
+            Shape::Sequence(&[&[Internal(Nt::Expr)], &[Internal(Nt::StatBlock)]]),
+        ),
+        Nt::ForStatement => (
+            "Statement -> 'for' '(' AssignStat ';' Expr ';' AssignStat ')' StatBlock ';'",
+            Shape::Sequence(&[
+                &[Internal(Nt::Assignment)],
+                &[Internal(Nt::Expr)],
+                &[Internal(Nt::Assignment)],
+                &[Internal(Nt::StatBlock)],
+            ]),
+        ),
+        Nt::SwitchStatement => (
+            "Statement -> 'switch' '(' Expr ')' '{' ReptSwitchCases '}' ';'",
+            Shape::SequenceThenList(
+                &[&[Internal(Nt::Expr)]],
+                &[Internal(Nt::Case), Internal(Nt::DefaultCase)],
+            ),
+        ),
+        Nt::Case => (
+            "ReptSwitchCases -> 'case' ArithExpr ':' ReptStatBlock ReptSwitchCases",
+            Shape::Sequence(&[&[Internal(Nt::ArithExpr)], &[Internal(Nt::StatBlock)]]),
+        ),
+        Nt::DefaultCase => (
+            "ReptSwitchCases -> 'default' ':' ReptStatBlock ReptSwitchCases",
+            Shape::Sequence(&[&[Internal(Nt::StatBlock)]]),
+        ),
+        Nt::ReadStatement => (
+            "Statement -> 'read' '(' Variable ')' ';'",
+            Shape::Sequence(&[&[Internal(Nt::Variable)]]),
+        ),
+        Nt::WriteStatement => (
+            "Statement -> 'write' '(' Expr ')' ';'",
+            Shape::Sequence(&[&[Internal(Nt::Expr)]]),
+        ),
+        Nt::ReturnStatement => (
+            "Statement -> 'return' '(' Expr ')' ';'",
+            Shape::Sequence(&[&[Internal(Nt::Expr)]]),
+        ),
+        Nt::BreakStatement => ("Statement -> 'break' ';'", Shape::Sequence(&[])),
+        Nt::ContinueStatement => ("Statement -> 'continue' ';'", Shape::Sequence(&[])),
+        Nt::Variable => (
+            "Variable -> 'id' VariableAmb1",
+            Shape::Sequence(&[VARIABLE]),
+        ),
+        Nt::DotOp => (
+            "FactorAmb2 | StatementAmb1 | VariableAmb1 -> '.' 'id' ...",
+            Shape::SequenceThenList(
+                &[VARIABLE, &[Leaf(TokenType::Id)]],
+                &[Internal(Nt::Indice), Internal(Nt::FuncCallParams)],
+            ),
+        ),
+        Nt::Expr => (
+            "Expr -> Conjunction RightRecExpr",
+            Shape::Sequence(&[EXPR_OPERAND]),
+        ),
+        Nt::Or => (
+            "RightRecExpr -> '|' Conjunction RightRecExpr",
+            Shape::Sequence(&[EXPR_OPERAND, CONJUNCTION_OPERAND]),
+        ),
+        Nt::And => (
+            "RightRecConjunction -> '&' Comparison RightRecConjunction",
+            Shape::Sequence(&[CONJUNCTION_OPERAND, COMPARISON]),
+        ),
+        Nt::Equal
+        | Nt::NotEqual
+        | Nt::LessThan
+        | Nt::GreaterThan
+        | Nt::LessEqualThan
+        | Nt::GreaterEqualThan => (
+            "ExprAmb1 -> RelOp ArithExpr",
+            Shape::Sequence(&[&[Internal(Nt::ArithExpr)], &[Internal(Nt::ArithExpr)]]),
+        ),
+        Nt::ArithExpr => (
+            "ArithExpr -> Term RightRecArithExpr",
+            Shape::Sequence(&[ARITH_OPERAND]),
+        ),
+        Nt::Add | Nt::Sub => (
+            "RightRecArithExpr -> AddOp Term RightRecArithExpr",
+            Shape::Sequence(&[ARITH_OPERAND, &[Internal(Nt::Term)]]),
+        ),
+        Nt::Term => (
+            "Term -> Factor RightRecTerm",
+            Shape::Sequence(&[TERM_OPERAND]),
+        ),
+        Nt::Mult | Nt::Div | Nt::Mod => (
+            "RightRecTerm -> MultOp Factor RightRecTerm",
+            Shape::Sequence(&[TERM_OPERAND, FACTOR]),
+        ),
+        Nt::Factor => ("Factor -> 'id' FactorAmb1", Shape::Sequence(&[VARIABLE])),
+        Nt::SignedFactor => (
+            "Factor -> Sign Factor",
+            Shape::Sequence(&[&[Leaf(TokenType::Plus), Leaf(TokenType::Minus)], FACTOR]),
+        ),
+        Nt::Negation => ("Factor -> '!' Factor", Shape::Sequence(&[FACTOR])),
+        Nt::TernaryOperation => (
+            "Factor -> '?' '[' Expr ':' Expr ':' Expr ']'",
+            Shape::Sequence(&[
+                &[Internal(Nt::Expr)],
+                &[Internal(Nt::Expr)],
+                &[Internal(Nt::Expr)],
+            ]),
+        ),
+        Nt::Cast => (
+            "Factor -> Type '(' Expr ')'",
+            Shape::Sequence(&[
+                &[Leaf(TokenType::IntegerType), Leaf(TokenType::FloatType)],
+                &[Internal(Nt::Expr)],
+            ]),
+        ),
+        Nt::Indice => (
+            "Indice -> '[' ArithExpr ']'",
+            Shape::Sequence(&[&[Internal(Nt::ArithExpr)]]),
+        ),
+        Nt::FuncCallParams => (
+            "Params -> Expr ReptParams",
+            Shape::EmptyOrList(&[Internal(Nt::Expr)]),
+        ),
+    };
+
+    NodeSchema { rule, shape }
+}
+
+/// Identifier leaves may carry indices or call parameters
+const ID_LEAF_RULE: &str = "ReptVariable -> Indice ReptVariable | FactorAmb1 -> '(' Params ')'";
+
+/// Checks every node of the tree against its [NodeSchema]
+pub fn check_ast_invariants(root: &Node) -> Vec<InternalCompilerError> {
+    let mut errors = Vec::new();
+    check_node(root, &mut errors);
+    errors
+}
+
+fn check_node(node: &Node, errors: &mut Vec<InternalCompilerError>) {
+    match node.val() {
+        None => {
+            if !node.children().is_empty() {
+                errors.push(error_at(
+                    node,
+                    "MakeEmptyNode",
+                    String::from("empty node has children"),
+                ));
+            }
+        }
+        Some(NodeVal::Leaf(token)) => {
+            let allowed: &[ChildKind] = match token.token_type() {
+                TokenType::Id => &[Internal(Nt::Indice), Internal(Nt::FuncCallParams)],
+                _ => &[],
+            };
+            for child in node.children() {
+                if !matches_any(child, allowed) {
+                    errors.push(error_at(
+                        node,
+                        ID_LEAF_RULE,
+                        format!("unexpected child {}", child),
+                    ));
+                }
+            }
+        }
+        Some(NodeVal::Internal(node_t)) => {
+            let schema = node_schema(*node_t);
+            if let Some(message) = check_shape(node.children(), &schema.shape) {
+                errors.push(error_at(node, schema.rule, message));
+            }
+        }
+    }
+
+    for child in node.children() {
+        check_node(child, errors);
+    }
+}
+
+/// Returns a description of how the children don't match the shape, if they don't
+fn check_shape(children: &[Node], shape: &Shape) -> Option<String> {
+    match shape {
+        Shape::Sequence(slots) => {
+            if children.len() != slots.len() {
+                return Some(format!(
+                    "expected {} children, found {}",
+                    slots.len(),
+                    children.len()
+                ));
+            }
+            for (idx, (child, slot)) in children.iter().zip(slots.iter()).enumerate() {
+                if !matches_any(child, slot) {
+                    return Some(format!(
+                        "child {} is {}, expected {}",
+                        idx,
+                        child,
+                        describe_slot(slot)
+                    ));
+                }
+            }
+            None
+        }
+        Shape::List(slot) => check_list(children, slot, 0),
+        Shape::SequenceThenList(slots, rest) => {
+            if children.len() < slots.len() {
+                return Some(format!(
+                    "expected at least {} children, found {}",
+                    slots.len(),
+                    children.len()
+                ));
+            }
+            check_shape(&children[..slots.len()], &Shape::Sequence(slots))
+                .or_else(|| check_list(&children[slots.len()..], rest, slots.len()))
+        }
+        Shape::EmptyOrList(slot) => {
+            if children.len() == 1 && children[0].val().is_none() {
+                None
+            } else {
+                check_list(children, slot, 0)
+            }
+        }
+    }
+}
+
+/// `offset` is the index of the first child in its parent
+fn check_list(children: &[Node], slot: &[ChildKind], offset: usize) -> Option<String> {
+    children
+        .iter()
+        .enumerate()
+        .find(|(_, child)| !matches_any(child, slot))
+        .map(|(idx, child)| {
+            format!(
+                "child {} is {}, expected {}",
+                idx + offset,
+                child,
+                describe_slot(slot)
+            )
+        })
+}
+
+fn matches_any(node: &Node, slot: &[ChildKind]) -> bool {
+    slot.iter().any(|kind| match (kind, node.val()) {
+        (Empty, None) => true,
+        (Leaf(token_t), Some(NodeVal::Leaf(token))) => *token_t == token.token_type(),
+        (Internal(node_t), Some(NodeVal::Internal(internal))) => node_t == internal,
+        _ => false,
+    })
+}
+
+fn describe_slot(slot: &[ChildKind]) -> String {
+    slot.iter()
+        .map(|kind| match kind {
+            Internal(node_t) => format!("{:?}", node_t),
+            Leaf(token_t) => token_t.describe(),
+            Empty => String::from("an empty node"),
+        })
+        .collect::<Vec<String>>()
+        .join(" or ")
+}
+
+fn error_at(node: &Node, rule: &'static str, message: String) -> InternalCompilerError {
+    InternalCompilerError {
+        line_num: first_line(node),
+        node: node.to_string(),
+        rule,
+        message,
+    }
+}
+
+fn first_line(node: &Node) -> Option<usize> {
+    match node.val() {
+        Some(NodeVal::Leaf(token)) => Some(token.line_num()),
+        _ => node.children().iter().find_map(first_line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::token::{Token, TokenFragment};
+
+    fn leaf(token_t: TokenType, lexeme: &str) -> Node {
+        Node::new_with_val(NodeVal::Leaf(Token::new(
+            TokenFragment::new(token_t, lexeme),
+            3,
+        )))
+    }
+
+    fn internal(node_t: InternalNodeType, children: Vec<Node>) -> Node {
+        let mut node = Node::new_with_val(NodeVal::Internal(node_t));
+        for child in children {
+            node.add_child(child);
+        }
+        node
+    }
+
+    #[test]
+    fn well_formed_var_declaration() {
+        let decl = internal(
+            Nt::VarDeclaration,
+            vec![
+                leaf(TokenType::IntegerType, "integer"),
+                leaf(TokenType::Id, "x"),
+                internal(Nt::ArrayDim, vec![leaf(TokenType::IntegerLit, "2")]),
+            ],
+        );
+
+        assert!(check_ast_invariants(&decl).is_empty());
+    }
+
+    #[test]
+    fn wrong_arity_names_rule() {
+        let decl = internal(
+            Nt::VarDeclaration,
+            vec![
+                leaf(TokenType::IntegerType, "integer"),
+                leaf(TokenType::Id, "x"),
+            ],
+        );
+
+        let errors = check_ast_invariants(&decl);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rule, "VarDecl -> Type 'id' ReptVarDecl ';'");
+        assert_eq!(errors[0].line_num, Some(3));
+        assert_eq!(errors[0].message, "expected 3 children, found 2");
+    }
+
+    #[test]
+    fn wrong_child_kind() {
+        let write = internal(
+            Nt::WriteStatement,
+            vec![internal(Nt::ArithExpr, vec![internal(Nt::Term, vec![leaf(
+                TokenType::IntegerLit,
+                "1",
+            )])])],
+        );
+
+        let errors = check_ast_invariants(&write);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].message, "child 0 is ArithExpr, expected Expr");
+    }
+
+    #[test]
+    fn empty_or_list() {
+        let empty = internal(Nt::FuncCallParams, vec![Node::new_empty()]);
+        assert!(check_ast_invariants(&empty).is_empty());
+
+        let mixed = internal(
+            Nt::FuncCallParams,
+            vec![Node::new_empty(), Node::new_empty()],
+        );
+        assert_eq!(check_ast_invariants(&mixed).len(), 1);
+    }
+}
@@ -1,5 +1,8 @@
 pub mod ast;
 pub mod data;
 pub mod grammar;
+pub mod invariants;
+pub mod modules;
 pub mod parse;
+pub mod recursive_descent;
 pub mod utils;
@@ -0,0 +1,227 @@
+//! Programs made of several files
+//!
+//! A file imports another one with `import "path";`, the path being relative to the importing file.
+//! Every file of a program is parsed once, even when several files import it, into a [Module].
+
+use crate::lexer::lexer::MyLexerAnalyzer;
+use crate::lexer::token::Token;
+use crate::parser::ast::{InternalNodeType, Node, NodeVal};
+use crate::parser::invariants::{check_ast_invariants, InternalCompilerError};
+use crate::parser::parse::{parse_with_options, ParseFailure, ParseOptions};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// Identifies a file of a program, the file given to the compiler is 0
+pub type FileId = usize;
+
+/// A file of a program, and its AST
+#[derive(Debug, Clone)]
+pub struct Module {
+    file_id: FileId,
+    path: PathBuf,
+    root: Node,
+}
+
+impl Module {
+    pub fn new(file_id: FileId, path: &Path, root: Node) -> Self {
+        Self {
+            file_id,
+            path: path.to_path_buf(),
+            root,
+        }
+    }
+
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
+    pub fn root(&self) -> &Node {
+        &self.root
+    }
+
+    /// Name of the file, for diagnostics
+    pub fn file_name(&self) -> String {
+        file_name(&self.path)
+    }
+}
+
+/// Why an imported file couldn't be loaded
+#[derive(Debug)]
+pub enum ModuleError {
+    /// The imported file can't be found
+    MissingFile {
+        path: PathBuf,
+        importer: PathBuf,
+        line_num: usize,
+    },
+    /// The imported file has syntax errors
+    Syntax {
+        path: PathBuf,
+        failure: ParseFailure,
+    },
+    /// The AST of the imported file is malformed. This is always a bug in the compiler.
+    InvalidAst {
+        path: PathBuf,
+        violations: Vec<InternalCompilerError>,
+    },
+}
+
+impl Display for ModuleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ModuleError::MissingFile {
+                path,
+                importer,
+                line_num,
+            } => write!(
+                f,
+                "{}: Line {}: cannot find imported file {}",
+                file_name(importer),
+                line_num,
+                path.display()
+            ),
+            ModuleError::Syntax { path, failure } => write!(
+                f,
+                "{}: {} syntax error(s)",
+                file_name(path),
+                failure.errors.len()
+            ),
+            ModuleError::InvalidAst { path, violations } => {
+                write!(
+                    f,
+                    "{}: failed to generate Abstract Syntax Tree",
+                    file_name(path)
+                )?;
+                for violation in violations {
+                    write!(f, "\n{}", violation)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map_or_else(|| path.to_string_lossy(), |name| name.to_string_lossy())
+        .into_owned()
+}
+
+/// Returns the import declarations of a file: the path of each imported file, relative to
+/// the directory of `path`, and the token naming it
+pub fn imports<'a>(path: &Path, root: &'a Node) -> Vec<(PathBuf, &'a Token)> {
+    let directory = path.parent().unwrap_or_else(|| Path::new(""));
+    root.children()[0]
+        .children()
+        .iter()
+        .filter(|n| n.val() == Some(&NodeVal::Internal(InternalNodeType::ImportDeclaration)))
+        .filter_map(|n| match n.children()[0].val() {
+            Some(NodeVal::Leaf(token)) => {
+                Some((directory.join(token.lexeme().trim_matches('"')), token))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Loads every file imported by a program, directly or not. `root` is the AST of the program's own file.
+/// Returns the modules of the program, each file after the ones it imports, so the program itself is last.
+pub fn load_modules(
+    path: &Path,
+    root: Node,
+    options: ParseOptions,
+) -> Result<Vec<Module>, Vec<ModuleError>> {
+    // files are told apart by their canonical path, the index of a file is its id
+    let mut loaded: Vec<PathBuf> = vec![path.canonicalize().unwrap_or_else(|_| path.to_path_buf())];
+    let mut modules: Vec<Module> = Vec::new();
+    let mut errors: Vec<ModuleError> = Vec::new();
+
+    load_imports(path, &root, options, &mut loaded, &mut modules, &mut errors);
+    modules.push(Module::new(0, path, root));
+
+    if errors.is_empty() {
+        Ok(modules)
+    } else {
+        Err(errors)
+    }
+}
+
+/// Loads the files imported by a file that aren't loaded yet, and their own imports first
+fn load_imports(
+    importer: &Path,
+    root: &Node,
+    options: ParseOptions,
+    loaded: &mut Vec<PathBuf>,
+    modules: &mut Vec<Module>,
+    errors: &mut Vec<ModuleError>,
+) {
+    for (path, token) in imports(importer, root) {
+        let canonical_path = match path.canonicalize() {
+            Ok(canonical_path) => canonical_path,
+            Err(_) => {
+                errors.push(ModuleError::MissingFile {
+                    path,
+                    importer: importer.to_path_buf(),
+                    line_num: token.line_num(),
+                });
+                continue;
+            }
+        };
+        if loaded.contains(&canonical_path) {
+            continue;
+        }
+        let file_id = loaded.len();
+        loaded.push(canonical_path);
+
+        log::info!("Loading imported file {:?}", path);
+        let lexer = MyLexerAnalyzer::from_file(&path);
+        let ast = match parse_with_options(lexer, options) {
+            Ok((_, ast)) => ast,
+            Err(failure) => {
+                errors.push(ModuleError::Syntax { path, failure });
+                continue;
+            }
+        };
+        let module_root = match ast.into_ast_root() {
+            Ok(module_root) => module_root,
+            Err(_) => {
+                errors.push(ModuleError::InvalidAst {
+                    path,
+                    violations: Vec::new(),
+                });
+                continue;
+            }
+        };
+        let violations = check_ast_invariants(&module_root);
+        if !violations.is_empty() {
+            errors.push(ModuleError::InvalidAst { path, violations });
+            continue;
+        }
+
+        load_imports(&path, &module_root, options, loaded, modules, errors);
+        modules.push(Module::new(file_id, &path, module_root));
+    }
+}
+
+/// Links the modules of a program into the AST of a single file, for code generation.
+/// Its declarations are those of every module, each module being in the program once.
+pub fn link_modules(modules: &[Module]) -> Node {
+    let mut root = Node::new_with_val(NodeVal::Internal(InternalNodeType::Root));
+    let sections = [
+        InternalNodeType::GlobalDeclarations,
+        InternalNodeType::ClassDeclarations,
+        InternalNodeType::FunctionDefinitions,
+        InternalNodeType::Main,
+    ];
+    for (idx, section) in sections.iter().enumerate() {
+        let mut node = Node::new_with_val(NodeVal::Internal(*section));
+        for module in modules {
+            for child in module.root().children()[idx].children() {
+                node.add_child(child.clone());
+            }
+        }
+        root.add_child(node);
+    }
+    root
+}
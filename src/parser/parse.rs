@@ -2,25 +2,125 @@
 
 //https://courses.cs.vt.edu/cs3304/Fall16/meng/lecture_notes/cs3304-7.pdf
 use crate::lexer::lexer::LexerAnalyzer;
-use crate::lexer::token::Token;
 use crate::lexer::token::TokenType::{LineComment, MultilineComment};
+use crate::lexer::token::{Token, TokenType};
 use crate::parser::ast::{SemanticAction, SemanticStack};
 use crate::parser::data::PARSING_TABLE;
 use crate::parser::grammar::DerivationTable;
 use crate::parser::grammar::GrammarSymbol::*;
 use crate::parser::grammar::NamedSymbol::Start;
-use crate::parser::grammar::{DerivationRecord, GrammarSymbol};
+use crate::parser::grammar::{DerivationRecord, GrammarRule, GrammarSymbol};
+use crate::parser::recursive_descent;
+use crate::parser::recursive_descent::DEFAULT_MAX_NESTING_DEPTH;
 use log::{trace, warn};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-/// Parses a token stream and produces either a DerivationTable and an AST or an error
-pub fn parse<T>(lexer: T) -> Result<(DerivationTable, SemanticStack), ()>
+/// A syntax error and the line it was found on
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SyntaxError {
+    pub line_num: usize,
+    pub message: String,
+}
+
+impl SyntaxError {
+    pub fn new(line_num: usize, message: String) -> Self {
+        Self { line_num, message }
+    }
+}
+
+impl Display for SyntaxError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: {}", self.line_num, self.message)
+    }
+}
+
+/// Why a token stream failed to parse
+#[derive(Debug)]
+pub struct ParseFailure {
+    /// Every syntax error found, in order
+    pub errors: Vec<SyntaxError>,
+    /// The derivation up to where parsing stopped, if one was being recorded
+    pub derivation_table: Option<DerivationTable>,
+}
+
+/// The parser implementations available
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParserBackend {
+    /// Table-driven LL(1) parser
+    Table,
+    /// Hand-written recursive-descent parser, see [crate::parser::recursive_descent]
+    RecursiveDescent,
+}
+
+impl FromStr for ParserBackend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(ParserBackend::Table),
+            "rd" | "recursive-descent" => Ok(ParserBackend::RecursiveDescent),
+            _ => Err(format!(
+                "Unknown parser backend '{}', expected table or recursive-descent",
+                s
+            )),
+        }
+    }
+}
+
+/// Options controlling how a token stream is parsed
+#[derive(Debug, Clone, Copy)]
+pub struct ParseOptions {
+    /// Record every derivation step in a [DerivationTable]. Off by default since it is costly.
+    /// Only the table-driven parser records derivations.
+    pub record_derivation: bool,
+    /// Which parser to use
+    pub backend: ParserBackend,
+    /// How deeply expressions and statements may be nested (recursive-descent parser only)
+    pub max_nesting_depth: usize,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            record_derivation: false,
+            backend: ParserBackend::Table,
+            max_nesting_depth: DEFAULT_MAX_NESTING_DEPTH,
+        }
+    }
+}
+
+/// Parses a token stream and produces either an optional DerivationTable and an AST,
+/// or every syntax error found in the token stream
+pub fn parse_with_options<T>(
+    lexer: T,
+    options: ParseOptions,
+) -> Result<(Option<DerivationTable>, SemanticStack), ParseFailure>
 where
     T: LexerAnalyzer<TokenOutput = Token> + IntoIterator<Item = <T as LexerAnalyzer>::TokenOutput>,
 {
+    if options.backend == ParserBackend::RecursiveDescent {
+        if options.record_derivation {
+            warn!("The recursive-descent parser does not record derivations");
+        }
+        return match recursive_descent::parse(lexer, options.max_nesting_depth) {
+            Ok(semantic_stack) => Ok((None, semantic_stack)),
+            Err(errors) => Err(ParseFailure {
+                errors,
+                derivation_table: None,
+            }),
+        };
+    }
+
     trace!("Initializing parsing table.");
     lazy_static::initialize(&PARSING_TABLE);
 
-    let mut derivation_table = DerivationTable::new();
+    let mut derivation_table = if options.record_derivation {
+        Some(DerivationTable::new())
+    } else {
+        None
+    };
 
     let mut parsing_stack: Vec<GrammarSymbol> = Vec::new();
     parsing_stack.push(STOP);
@@ -32,9 +132,13 @@ where
 
     let mut next_token: Option<Token> = token_stream.next();
 
-    let mut error = false;
+    let mut errors: Vec<SyntaxError> = Vec::new();
+    // after an error, further errors aren't reported until the parser is back in sync
+    let mut recovering = false;
+    // a missing token is reported on the line of the last consumed one
+    let mut last_line_num = next_token.as_ref().map_or(1, |token| token.line_num());
 
-    derivation_table.add_record(DerivationRecord::new(&parsing_stack, &next_token, None));
+    record_step(&mut derivation_table, &parsing_stack, &next_token, None);
 
     while *parsing_stack.last().unwrap() != STOP {
         let top_symbol = parsing_stack.last().unwrap().clone();
@@ -51,18 +155,32 @@ where
             Terminal(token_t) => {
                 if next_token.is_some() && token_t == next_token.as_ref().unwrap().token_type() {
                     parsing_stack.pop();
+                    if let Some(table) = derivation_table.as_mut() {
+                        table.add_matched_token(next_token.as_ref().unwrap());
+                    }
+                    last_line_num = next_token.as_ref().unwrap().line_num();
                     next_token = token_stream.next();
-                    derivation_table.add_record(DerivationRecord::new(
-                        &parsing_stack,
-                        &next_token,
-                        None,
-                    ))
+                    recovering = false;
+                    record_step(&mut derivation_table, &parsing_stack, &next_token, None);
                 } else {
                     warn!(
                         "~ Mistmatch! Expected token of type {:?}, but got {:?} instead.",
                         token_t, next_token
                     );
-                    error = true;
+                    if !recovering {
+                        errors.push(SyntaxError::new(
+                            last_line_num,
+                            format!(
+                                "expected {}, but found {}",
+                                token_t.describe(),
+                                describe_found(&next_token)
+                            ),
+                        ));
+                        recovering = true;
+                    }
+                    if next_token.is_none() {
+                        break;
+                    }
                     while next_token.is_some()
                         && next_token.as_ref().unwrap().token_type() != token_t
                     {
@@ -72,6 +190,15 @@ where
             }
             NonTerminal(named_symbol) => {
                 if next_token.is_none() {
+                    // at the end of the input, only the symbols that can end the program derive EPSILON
+                    if named_symbol.first_set().contains(&EPSILON)
+                        && named_symbol.follow_set().contains(&STOP)
+                    {
+                        trace!("Applying Epsilon at the end of the input");
+                        parsing_stack.pop();
+                        record_step(&mut derivation_table, &parsing_stack, &next_token, None);
+                        continue;
+                    }
                     break;
                 } else {
                     match PARSING_TABLE.get(&(
@@ -80,15 +207,27 @@ where
                     )) {
                         None => {
                             warn!("~ No rule found for ({:?}, {:?})", named_symbol, next_token);
-                            error = true;
                             let first = named_symbol.first_set();
                             let follow = named_symbol.follow_set();
+                            if !recovering {
+                                errors.push(SyntaxError::new(
+                                    last_line_num,
+                                    format!(
+                                        "unexpected {} while parsing {:?}, expected {}",
+                                        describe_found(&next_token),
+                                        named_symbol,
+                                        describe_expected(expected_terminals(first, follow))
+                                    ),
+                                ));
+                                recovering = true;
+                            }
                             // pop
                             if next_token.is_none()
                                 || follow
                                     .contains(&Terminal(next_token.as_ref().unwrap().token_type()))
                             {
-                                warn!("~ Popped: {:?}", parsing_stack.pop());
+                                let popped = parsing_stack.pop();
+                                warn!("~ Popped: {:?}", popped);
                             }
                             // scan
                             else {
@@ -121,11 +260,12 @@ where
                             for rhs_symbol in rule.rhs.iter().rev() {
                                 parsing_stack.push(*rhs_symbol);
                             }
-                            derivation_table.add_record(DerivationRecord::new(
+                            record_step(
+                                &mut derivation_table,
                                 &parsing_stack,
                                 &next_token,
                                 Some(rule),
-                            ));
+                            );
                         }
                     }
                 }
@@ -134,11 +274,7 @@ where
                 trace!("Applying Epsilon");
                 parsing_stack.pop();
 
-                derivation_table.add_record(DerivationRecord::new(
-                    &parsing_stack,
-                    &next_token,
-                    None,
-                ));
+                record_step(&mut derivation_table, &parsing_stack, &next_token, None);
                 continue;
             }
             STOP => {
@@ -151,10 +287,9 @@ where
                         semantic_stack.make_family_root(ty);
                     }
                     SemanticAction::MakeTerminalNode => {
-                        if next_token.is_none() {
-                            panic!("Tried to make terminal node with None next_token");
-                        } else {
-                            semantic_stack.make_terminal_node(next_token.as_ref().unwrap());
+                        // at the end of file, the terminal that follows reports the error
+                        if let Some(token) = next_token.as_ref() {
+                            semantic_stack.make_terminal_node(token);
                         }
                     }
                     SemanticAction::MakeRelativeOperation => {
@@ -171,16 +306,96 @@ where
         };
     }
 
-    return if next_token.is_some() {
+    if next_token.is_some() {
         log::error!("Next Token is some");
-        Err(())
-    } else if parsing_stack.len() > 1 {
+        if !recovering {
+            errors.push(SyntaxError::new(
+                next_token.as_ref().unwrap().line_num(),
+                format!(
+                    "unexpected {} after the end of the program",
+                    describe_found(&next_token)
+                ),
+            ));
+        }
+    } else if parsing_stack.len() > 1 && !recovering {
         log::error!("Parsing stack is not empty");
-        Err(())
-    } else if error {
-        log::error!("Parsing error");
-        Err(())
-    } else {
+        errors.push(SyntaxError::new(
+            last_line_num,
+            String::from("unexpected end of file"),
+        ));
+    }
+
+    if errors.is_empty() {
         Ok((derivation_table, semantic_stack))
-    };
+    } else {
+        log::error!("Parsing error");
+        Err(ParseFailure {
+            errors,
+            derivation_table,
+        })
+    }
+}
+
+/// Terminals that would have been accepted in place of a symbol, for diagnostics
+fn expected_terminals(first: &[GrammarSymbol], follow: &[GrammarSymbol]) -> Vec<TokenType> {
+    let mut expected: Vec<TokenType> = Vec::new();
+    let nullable = first.contains(&EPSILON);
+    for symbol in first.iter().chain(follow.iter().filter(|_| nullable)) {
+        if let Terminal(token_t) = symbol {
+            if !expected.contains(token_t) {
+                expected.push(*token_t);
+            }
+        }
+    }
+    expected
+}
+
+/// Describes the lookahead token, for diagnostics
+pub(crate) fn describe_found(token: &Option<Token>) -> String {
+    match token {
+        None => String::from("end of file"),
+        Some(token) => match token.token_type() {
+            TokenType::Error(_) => {
+                format!("{} '{}'", token.token_type().describe(), token.lexeme())
+            }
+            _ => format!("'{}'", token.lexeme()),
+        },
+    }
+}
+
+/// Describes a set of expected token types, for diagnostics
+pub(crate) fn describe_expected(expected: Vec<TokenType>) -> String {
+    let mut expected: Vec<String> = expected.iter().fold(Vec::new(), |mut unique, token_t| {
+        let description = token_t.describe();
+        if !unique.contains(&description) {
+            unique.push(description);
+        }
+        unique
+    });
+    match expected.len() {
+        0 => String::from("nothing"),
+        1 => expected.pop().unwrap(),
+        _ => {
+            let last = expected.pop().unwrap();
+            format!("one of {} or {}", expected.join(", "), last)
+        }
+    }
+}
+
+/// Adds a step to the derivation table, if one is being recorded
+fn record_step(
+    derivation_table: &mut Option<DerivationTable>,
+    parsing_stack: &Vec<GrammarSymbol>,
+    next_token: &Option<Token>,
+    rule: Option<&GrammarRule>,
+) {
+    if let Some(table) = derivation_table.as_mut() {
+        let matched_count = table.matched_count();
+        table.add_record(DerivationRecord::new(
+            parsing_stack,
+            next_token,
+            rule,
+            matched_count,
+        ));
+    }
 }
@@ -0,0 +1,1386 @@
+//! Hand-written recursive-descent parser
+//!
+//! Follows the LL(1) grammar of [crate::parser::data] and fires the same semantic actions in the
+//! same order as the table-driven parser, so both produce identical ASTs.
+//! Each non-terminal has a method of the same name, and lookahead sets are copied from the parsing table.
+//! Tail-recursive `Rept*` non-terminals are written as loops.
+
+use crate::lexer::lexer::LexerAnalyzer;
+use crate::lexer::token::TokenType::{LineComment, MultilineComment};
+use crate::lexer::token::{Token, TokenType};
+use crate::parser::ast::{InternalNodeType, SemanticStack};
+use crate::parser::parse::{describe_expected, describe_found, SyntaxError};
+use log::trace;
+
+/// Default limit on how deeply expressions and statements can be nested
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 128;
+
+const TYPE_FIRST: &[TokenType] = &[
+    TokenType::Id,
+    TokenType::StringType,
+    TokenType::BoolType,
+    TokenType::FloatType,
+    TokenType::IntegerType,
+];
+
+const EXPR_FIRST: &[TokenType] = &[
+    TokenType::Id,
+    TokenType::OpenParen,
+    TokenType::Minus,
+    TokenType::Plus,
+    TokenType::Question,
+    TokenType::Bang,
+    TokenType::StringLit,
+    TokenType::BoolLit,
+    TokenType::FloatLit,
+    TokenType::IntegerLit,
+    TokenType::IntegerType,
+    TokenType::FloatType,
+];
+
+const STATEMENT_FIRST: &[TokenType] = &[
+    TokenType::Id,
+    TokenType::Continue,
+    TokenType::Break,
+    TokenType::Return,
+    TokenType::Write,
+    TokenType::Read,
+    TokenType::While,
+    TokenType::For,
+    TokenType::Switch,
+    TokenType::If,
+];
+
+const REL_OPS: &[TokenType] = &[
+    TokenType::GreaterEqualThan,
+    TokenType::LessEqualThan,
+    TokenType::GreaterThan,
+    TokenType::LessThan,
+    TokenType::NotEq,
+    TokenType::EqEq,
+];
+
+const ADD_OPS: &[TokenType] = &[TokenType::Minus, TokenType::Plus];
+
+const MULT_OPS: &[TokenType] = &[TokenType::Div, TokenType::Mult, TokenType::Mod];
+
+const LOGICAL_OPS: &[TokenType] = &[TokenType::And, TokenType::Or];
+
+/// Tokens that may follow an expression
+const EXPR_FOLLOW: &[TokenType] = &[
+    TokenType::CloseParen,
+    TokenType::SemiColon,
+    TokenType::Comma,
+    TokenType::CloseSquare,
+    TokenType::Colon,
+];
+
+/// Parses a token stream into an AST, or returns every syntax error found
+///
+/// After an error in a statement the parser skips ahead to the end of that statement,
+/// and after an error in a class or function it skips ahead to the next declaration.
+/// # Arguments
+/// * `lexer` - the token stream
+/// * `max_nesting_depth` - how deeply factors and statements may be nested
+pub fn parse<T>(lexer: T, max_nesting_depth: usize) -> Result<SemanticStack, Vec<SyntaxError>>
+where
+    T: LexerAnalyzer<TokenOutput = Token> + IntoIterator<Item = <T as LexerAnalyzer>::TokenOutput>,
+{
+    let tokens: Vec<Token> = lexer
+        .into_iter()
+        .filter(|token| token.token_type() != LineComment && token.token_type() != MultilineComment)
+        .collect();
+
+    let mut parser = RecursiveDescentParser {
+        tokens,
+        idx: 0,
+        semantic_stack: SemanticStack::new(),
+        depth: 0,
+        max_nesting_depth,
+        errors: Vec::new(),
+    };
+
+    match parser.start() {
+        Ok(()) => {
+            if parser.idx < parser.tokens.len() {
+                let err = SyntaxError::new(
+                    parser.tokens[parser.idx].line_num(),
+                    String::from("unexpected input after the end of the main function"),
+                );
+                parser.errors.push(err);
+            }
+        }
+        Err(err) => parser.errors.push(err),
+    }
+
+    if parser.errors.is_empty() {
+        Ok(parser.semantic_stack)
+    } else {
+        Err(parser.errors)
+    }
+}
+
+struct RecursiveDescentParser {
+    tokens: Vec<Token>,
+    idx: usize,
+    semantic_stack: SemanticStack,
+    depth: usize,
+    max_nesting_depth: usize,
+    errors: Vec<SyntaxError>,
+}
+
+impl RecursiveDescentParser {
+    // ----- helpers -----
+
+    fn lookahead(&self) -> Option<TokenType> {
+        self.tokens.get(self.idx).map(|token| token.token_type())
+    }
+
+    fn at(&self, token_types: &[TokenType]) -> bool {
+        match self.lookahead() {
+            None => false,
+            Some(token_type) => token_types.contains(&token_type),
+        }
+    }
+
+    /// Line of the last consumed token, a missing token is reported right after it
+    fn line_num(&self) -> usize {
+        self.idx
+            .checked_sub(1)
+            .and_then(|idx| self.tokens.get(idx))
+            .or_else(|| self.tokens.get(self.idx))
+            .map_or(1, |token| token.line_num())
+    }
+
+    fn error(&self, message: String) -> SyntaxError {
+        SyntaxError::new(self.line_num(), message)
+    }
+
+    fn found(&self) -> String {
+        describe_found(&self.tokens.get(self.idx).cloned())
+    }
+
+    /// Error for a lookahead that doesn't start any alternative of a non-terminal
+    fn unexpected(&self, context: &str, expected: &[&[TokenType]]) -> SyntaxError {
+        let expected = expected
+            .iter()
+            .flat_map(|types| types.iter())
+            .copied()
+            .collect();
+        self.error(format!(
+            "unexpected {} in {}, expected {}",
+            self.found(),
+            context,
+            describe_expected(expected)
+        ))
+    }
+
+    /// Consumes the lookahead if it is of the given type
+    fn expect(&mut self, token_type: TokenType, context: &str) -> Result<(), SyntaxError> {
+        if self.lookahead() == Some(token_type) {
+            trace!("Matched {:?}", token_type);
+            self.idx += 1;
+            Ok(())
+        } else {
+            Err(self.error(format!(
+                "expected {} in {}, but found {}",
+                token_type.describe(),
+                context,
+                self.found()
+            )))
+        }
+    }
+
+    /// Makes a terminal node from the lookahead, then consumes it
+    fn terminal(&mut self, token_type: TokenType, context: &str) -> Result<(), SyntaxError> {
+        if self.lookahead() == Some(token_type) {
+            self.semantic_stack
+                .make_terminal_node(&self.tokens[self.idx]);
+        }
+        self.expect(token_type, context)
+    }
+
+    fn make_family_root(&mut self, node_t: InternalNodeType) {
+        self.semantic_stack.make_family_root(node_t);
+    }
+
+    fn add_child(&mut self) {
+        self.semantic_stack.add_child();
+    }
+
+    fn make_relative_operation(&mut self) {
+        self.semantic_stack.make_relative_operation();
+    }
+
+    fn make_empty_node(&mut self) {
+        self.semantic_stack.make_empty_node();
+    }
+
+    /// The depth is only increased when it is within the limit, since callers don't exit on an error
+    fn enter_nesting(&mut self) -> Result<(), SyntaxError> {
+        if self.depth >= self.max_nesting_depth {
+            return Err(self.error(format!(
+                "nesting is too deep, the maximum depth is {}",
+                self.max_nesting_depth
+            )));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn exit_nesting(&mut self) {
+        self.depth -= 1;
+    }
+
+    /// Skips past the rest of a broken statement: up to and including its `;`,
+    /// or up to the `}` closing the enclosing block
+    fn skip_statement(&mut self, start: usize) {
+        let mut braces = 0;
+        while let Some(token_type) = self.lookahead() {
+            match token_type {
+                TokenType::OpenCurly => braces += 1,
+                TokenType::CloseCurly if braces == 0 => break,
+                TokenType::CloseCurly => braces -= 1,
+                TokenType::SemiColon if braces == 0 => {
+                    self.idx += 1;
+                    break;
+                }
+                _ => {}
+            }
+            self.idx += 1;
+        }
+        if self.idx == start && self.lookahead().is_some() {
+            self.idx += 1;
+        }
+    }
+
+    /// Skips past the rest of a broken class or function, up to the next declaration
+    fn skip_declaration(&mut self, start: usize) {
+        if self.idx == start {
+            self.idx += 1;
+        }
+        while self.lookahead().is_some()
+            && !self.at(&[TokenType::Class, TokenType::Func, TokenType::Main])
+        {
+            self.idx += 1;
+        }
+    }
+
+    // ----- program structure -----
+
+    fn start(&mut self) -> Result<(), SyntaxError> {
+        const FIRST: &[TokenType] = &[
+            TokenType::Main,
+            TokenType::Func,
+            TokenType::Class,
+            TokenType::Const,
+            TokenType::Import,
+            TokenType::Enum,
+        ];
+        if !self.at(FIRST) && !self.at(TYPE_FIRST) {
+            return Err(self.unexpected("program", &[FIRST, TYPE_FIRST]));
+        }
+        self.make_family_root(InternalNodeType::Root);
+        self.prog()
+    }
+
+    fn prog(&mut self) -> Result<(), SyntaxError> {
+        self.make_family_root(InternalNodeType::GlobalDeclarations);
+        self.rept_prog2()?;
+        self.add_child();
+        self.make_family_root(InternalNodeType::ClassDeclarations);
+        self.rept_prog0()?;
+        self.add_child();
+        self.make_family_root(InternalNodeType::FunctionDefinitions);
+        self.rept_prog1()?;
+        self.add_child();
+        // an imported file has no main function
+        self.make_family_root(InternalNodeType::Main);
+        if self.at(&[TokenType::Main]) {
+            self.expect(TokenType::Main, "program")?;
+            self.func_body()?;
+            self.add_child();
+        }
+        self.add_child();
+        Ok(())
+    }
+
+    fn rept_prog0(&mut self) -> Result<(), SyntaxError> {
+        const FOLLOW: &[TokenType] = &[TokenType::Main, TokenType::Func];
+        while self.at(&[TokenType::Class]) {
+            let start = self.idx;
+            match self.class_decl() {
+                Ok(()) => self.add_child(),
+                Err(err) => {
+                    self.errors.push(err);
+                    self.skip_declaration(start);
+                }
+            }
+        }
+        if self.lookahead().is_some() && !self.at(FOLLOW) {
+            return Err(self.unexpected("program", &[&[TokenType::Class], FOLLOW]));
+        }
+        Ok(())
+    }
+
+    fn rept_prog1(&mut self) -> Result<(), SyntaxError> {
+        while self.at(&[TokenType::Func]) {
+            let start = self.idx;
+            match self.func_def() {
+                Ok(()) => self.add_child(),
+                Err(err) => {
+                    self.errors.push(err);
+                    self.skip_declaration(start);
+                }
+            }
+        }
+        if self.lookahead().is_some() && !self.at(&[TokenType::Main]) {
+            return Err(self.unexpected("program", &[&[TokenType::Func, TokenType::Main]]));
+        }
+        Ok(())
+    }
+
+    fn rept_prog2(&mut self) -> Result<(), SyntaxError> {
+        const FOLLOW: &[TokenType] = &[TokenType::Main, TokenType::Func, TokenType::Class];
+        const FIRST: &[TokenType] = &[TokenType::Const, TokenType::Import, TokenType::Enum];
+        while self.at(FIRST) || self.at(TYPE_FIRST) {
+            let start = self.idx;
+            let result = if self.at(&[TokenType::Const]) {
+                self.const_decl()
+            } else if self.at(&[TokenType::Import]) {
+                self.import_decl()
+            } else if self.at(&[TokenType::Enum]) {
+                self.enum_decl()
+            } else {
+                self.var_decl()
+            };
+            match result {
+                Ok(()) => self.add_child(),
+                Err(err) => {
+                    self.errors.push(err);
+                    self.skip_statement(start);
+                }
+            }
+        }
+        if self.lookahead().is_some() && !self.at(FOLLOW) {
+            return Err(self.unexpected("program", &[FIRST, TYPE_FIRST, FOLLOW]));
+        }
+        Ok(())
+    }
+
+    fn import_decl(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "import declaration";
+        self.expect(TokenType::Import, CONTEXT)?;
+        self.make_family_root(InternalNodeType::ImportDeclaration);
+        self.terminal(TokenType::StringLit, CONTEXT)?;
+        self.add_child();
+        self.expect(TokenType::SemiColon, CONTEXT)
+    }
+
+    fn enum_decl(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "enum declaration";
+        self.expect(TokenType::Enum, CONTEXT)?;
+        self.make_family_root(InternalNodeType::EnumDeclaration);
+        self.terminal(TokenType::Id, CONTEXT)?;
+        self.add_child();
+        self.expect(TokenType::OpenCurly, CONTEXT)?;
+        self.terminal(TokenType::Id, CONTEXT)?;
+        self.add_child();
+        while self.at(&[TokenType::Comma]) {
+            self.expect(TokenType::Comma, CONTEXT)?;
+            self.terminal(TokenType::Id, CONTEXT)?;
+            self.add_child();
+        }
+        self.expect(TokenType::CloseCurly, CONTEXT)?;
+        self.expect(TokenType::SemiColon, CONTEXT)
+    }
+
+    // ----- classes -----
+
+    fn class_decl(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "class declaration";
+        self.make_family_root(InternalNodeType::ClassDeclaration);
+        self.expect(TokenType::Class, CONTEXT)?;
+        self.terminal(TokenType::Id, CONTEXT)?;
+        self.add_child();
+        self.make_family_root(InternalNodeType::InheritList);
+        self.opt_class_decl()?;
+        self.add_child();
+        self.expect(TokenType::OpenCurly, CONTEXT)?;
+        self.make_family_root(InternalNodeType::MemberList);
+        self.rept_class_decl()?;
+        self.add_child();
+        self.expect(TokenType::CloseCurly, CONTEXT)?;
+        self.expect(TokenType::SemiColon, CONTEXT)
+    }
+
+    fn opt_class_decl(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "inheritance list";
+        match self.lookahead() {
+            Some(TokenType::OpenCurly) => Ok(()),
+            Some(TokenType::Inherits) => {
+                self.expect(TokenType::Inherits, CONTEXT)?;
+                self.terminal(TokenType::Id, CONTEXT)?;
+                self.add_child();
+                while self.at(&[TokenType::Comma]) {
+                    self.expect(TokenType::Comma, CONTEXT)?;
+                    self.terminal(TokenType::Id, CONTEXT)?;
+                    self.add_child();
+                }
+                if !self.at(&[TokenType::OpenCurly]) {
+                    return Err(
+                        self.unexpected(CONTEXT, &[&[TokenType::Comma, TokenType::OpenCurly]])
+                    );
+                }
+                Ok(())
+            }
+            _ => Err(self.unexpected(
+                "class declaration",
+                &[&[TokenType::Inherits, TokenType::OpenCurly]],
+            )),
+        }
+    }
+
+    fn rept_class_decl(&mut self) -> Result<(), SyntaxError> {
+        const FIRST: &[TokenType] = &[
+            TokenType::Private,
+            TokenType::Public,
+            TokenType::Id,
+            TokenType::StringType,
+            TokenType::BoolType,
+            TokenType::FloatType,
+            TokenType::IntegerType,
+            TokenType::Func,
+            TokenType::Const,
+        ];
+        while self.at(FIRST) {
+            self.make_family_root(InternalNodeType::MemberDeclaration);
+            self.visibility()?;
+            self.add_child();
+            self.member_decl()?;
+            self.add_child();
+            self.add_child();
+        }
+        if !self.at(&[TokenType::CloseCurly]) {
+            return Err(self.unexpected("class member list", &[FIRST, &[TokenType::CloseCurly]]));
+        }
+        Ok(())
+    }
+
+    fn visibility(&mut self) -> Result<(), SyntaxError> {
+        match self.lookahead() {
+            Some(TokenType::Private) => self.terminal(TokenType::Private, "member declaration"),
+            Some(TokenType::Public) => self.terminal(TokenType::Public, "member declaration"),
+            _ => {
+                self.make_empty_node();
+                Ok(())
+            }
+        }
+    }
+
+    fn member_decl(&mut self) -> Result<(), SyntaxError> {
+        match self.lookahead() {
+            Some(TokenType::Func) => {
+                self.make_family_root(InternalNodeType::MemberFuncDeclaration);
+                self.func_decl()?;
+                self.add_child();
+                Ok(())
+            }
+            Some(token_type) if TYPE_FIRST.contains(&token_type) => {
+                self.make_family_root(InternalNodeType::MemberVarDeclaration);
+                self.var_decl()?;
+                self.add_child();
+                Ok(())
+            }
+            Some(TokenType::Const) => {
+                self.make_family_root(InternalNodeType::MemberConstDeclaration);
+                self.const_decl()?;
+                self.add_child();
+                Ok(())
+            }
+            _ => Err(self.unexpected(
+                "member declaration",
+                &[TYPE_FIRST, &[TokenType::Func, TokenType::Const]],
+            )),
+        }
+    }
+
+    fn func_decl(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "member function declaration";
+        self.expect(TokenType::Func, CONTEXT)?;
+        self.make_family_root(InternalNodeType::FuncDeclaration);
+        self.terminal(TokenType::Id, CONTEXT)?;
+        self.add_child();
+        self.expect(TokenType::OpenParen, CONTEXT)?;
+        self.func_params()?;
+        self.add_child();
+        self.expect(TokenType::CloseParen, CONTEXT)?;
+        self.expect(TokenType::Colon, CONTEXT)?;
+        self.return_type(CONTEXT)?;
+        self.expect(TokenType::SemiColon, CONTEXT)?;
+        self.add_child();
+        Ok(())
+    }
+
+    // ----- functions -----
+
+    fn func_def(&mut self) -> Result<(), SyntaxError> {
+        self.make_family_root(InternalNodeType::FuncDef);
+        self.func_head()?;
+        self.func_body()?;
+        self.add_child();
+        Ok(())
+    }
+
+    fn func_head(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "function definition";
+        self.expect(TokenType::Func, CONTEXT)?;
+        self.terminal(TokenType::Id, CONTEXT)?;
+        self.add_child();
+        match self.lookahead() {
+            Some(TokenType::OpenParen) => {
+                self.make_empty_node();
+                self.add_child();
+            }
+            Some(TokenType::DoubleColon) => {
+                self.expect(TokenType::DoubleColon, CONTEXT)?;
+                self.terminal(TokenType::Id, CONTEXT)?;
+                self.add_child();
+            }
+            _ => {
+                return Err(
+                    self.unexpected(CONTEXT, &[&[TokenType::OpenParen, TokenType::DoubleColon]])
+                )
+            }
+        }
+        self.expect(TokenType::OpenParen, CONTEXT)?;
+        self.func_params()?;
+        self.add_child();
+        self.expect(TokenType::CloseParen, CONTEXT)?;
+        self.expect(TokenType::Colon, CONTEXT)?;
+        self.return_type(CONTEXT)?;
+        self.add_child();
+        Ok(())
+    }
+
+    /// FuncHeadAmb2 and FuncDeclAmb1 without the semicolon
+    fn return_type(&mut self, context: &str) -> Result<(), SyntaxError> {
+        if self.at(&[TokenType::Void]) {
+            self.terminal(TokenType::Void, context)
+        } else if self.at(TYPE_FIRST) {
+            self.type_(context)
+        } else {
+            Err(self.unexpected(context, &[TYPE_FIRST, &[TokenType::Void]]))
+        }
+    }
+
+    fn func_params(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "parameter list";
+        self.make_family_root(InternalNodeType::FuncParams);
+        if self.at(&[TokenType::CloseParen]) {
+            self.make_empty_node();
+            self.add_child();
+            return Ok(());
+        }
+        if !self.at(TYPE_FIRST) {
+            return Err(self.unexpected(CONTEXT, &[TYPE_FIRST, &[TokenType::CloseParen]]));
+        }
+        self.func_param()?;
+        while self.at(&[TokenType::Comma]) {
+            self.make_family_root(InternalNodeType::FuncParam);
+            self.expect(TokenType::Comma, CONTEXT)?;
+            self.func_param_tail()?;
+        }
+        if !self.at(&[TokenType::CloseParen]) {
+            return Err(self.unexpected(CONTEXT, &[&[TokenType::Comma, TokenType::CloseParen]]));
+        }
+        Ok(())
+    }
+
+    fn func_param(&mut self) -> Result<(), SyntaxError> {
+        self.make_family_root(InternalNodeType::FuncParam);
+        self.func_param_tail()
+    }
+
+    fn func_param_tail(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "parameter";
+        self.type_(CONTEXT)?;
+        self.add_child();
+        self.terminal(TokenType::Id, CONTEXT)?;
+        self.add_child();
+        self.make_family_root(InternalNodeType::ArrayDim);
+        while self.at(&[TokenType::OpenSquare]) {
+            self.array_size()?;
+            self.add_child();
+        }
+        if !self.at(&[TokenType::Comma, TokenType::CloseParen]) {
+            return Err(self.unexpected(
+                CONTEXT,
+                &[&[
+                    TokenType::OpenSquare,
+                    TokenType::Comma,
+                    TokenType::CloseParen,
+                ]],
+            ));
+        }
+        self.add_child();
+        self.add_child();
+        Ok(())
+    }
+
+    fn func_body(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "function body";
+        self.make_family_root(InternalNodeType::FuncBody);
+        self.expect(TokenType::OpenCurly, CONTEXT)?;
+        self.opt_func_body()?;
+        self.add_child();
+        self.make_family_root(InternalNodeType::StatementList);
+        self.statement_list(CONTEXT, &[TokenType::CloseCurly])?;
+        self.add_child();
+        self.expect(TokenType::CloseCurly, CONTEXT)
+    }
+
+    fn opt_func_body(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "variable block";
+        self.make_family_root(InternalNodeType::VarBlock);
+        if self.at(&[TokenType::Var]) {
+            self.expect(TokenType::Var, CONTEXT)?;
+            self.expect(TokenType::OpenCurly, CONTEXT)?;
+            loop {
+                if self.at(TYPE_FIRST) {
+                    self.var_decl()?;
+                } else if self.at(&[TokenType::Const]) {
+                    self.const_decl()?;
+                } else {
+                    break;
+                }
+                self.add_child();
+            }
+            self.expect(TokenType::CloseCurly, CONTEXT)
+        } else if self.at(STATEMENT_FIRST) || self.at(&[TokenType::CloseCurly]) {
+            self.make_empty_node();
+            self.add_child();
+            Ok(())
+        } else {
+            Err(self.unexpected(
+                "function body",
+                &[&[TokenType::Var], STATEMENT_FIRST, &[TokenType::CloseCurly]],
+            ))
+        }
+    }
+
+    fn var_decl(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "variable declaration";
+        self.make_family_root(InternalNodeType::VarDeclaration);
+        self.type_(CONTEXT)?;
+        self.add_child();
+        self.terminal(TokenType::Id, CONTEXT)?;
+        self.add_child();
+        self.make_family_root(InternalNodeType::ArrayDim);
+        while self.at(&[TokenType::OpenSquare]) {
+            self.array_size()?;
+            self.add_child();
+        }
+        if !self.at(&[TokenType::SemiColon]) {
+            return Err(self.unexpected(CONTEXT, &[&[TokenType::OpenSquare, TokenType::SemiColon]]));
+        }
+        self.add_child();
+        self.expect(TokenType::SemiColon, CONTEXT)
+    }
+
+    fn const_decl(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "constant declaration";
+        self.make_family_root(InternalNodeType::ConstDeclaration);
+        self.expect(TokenType::Const, CONTEXT)?;
+        self.type_(CONTEXT)?;
+        self.add_child();
+        self.terminal(TokenType::Id, CONTEXT)?;
+        self.add_child();
+        self.expect(TokenType::Assignment, CONTEXT)?;
+        self.expr()?;
+        self.add_child();
+        self.expect(TokenType::SemiColon, CONTEXT)
+    }
+
+    fn array_size(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "array size";
+        self.expect(TokenType::OpenSquare, CONTEXT)?;
+        match self.lookahead() {
+            Some(TokenType::CloseSquare) => self.make_empty_node(),
+            Some(TokenType::IntegerLit) => self.terminal(TokenType::IntegerLit, CONTEXT)?,
+            Some(TokenType::Id) => self.terminal(TokenType::Id, CONTEXT)?,
+            _ => {
+                return Err(self.unexpected(
+                    CONTEXT,
+                    &[&[TokenType::IntegerLit, TokenType::Id, TokenType::CloseSquare]],
+                ))
+            }
+        }
+        self.expect(TokenType::CloseSquare, CONTEXT)
+    }
+
+    fn type_(&mut self, context: &str) -> Result<(), SyntaxError> {
+        match self.lookahead() {
+            Some(token_type) if TYPE_FIRST.contains(&token_type) => {
+                self.terminal(token_type, context)
+            }
+            _ => Err(self.unexpected(context, &[TYPE_FIRST])),
+        }
+    }
+
+    // ----- statements -----
+
+    /// ReptFuncBody and ReptStatBlock
+    /// Statements up to one of the `end` tokens, which isn't consumed
+    fn statement_list(&mut self, context: &str, end: &[TokenType]) -> Result<(), SyntaxError> {
+        loop {
+            let start = self.idx;
+            if self.at(STATEMENT_FIRST) {
+                match self.statement() {
+                    Ok(()) => self.add_child(),
+                    Err(err) => {
+                        self.errors.push(err);
+                        self.skip_statement(start);
+                    }
+                }
+            } else if self.at(end) {
+                return Ok(());
+            } else {
+                let err = self.unexpected(context, &[STATEMENT_FIRST, end]);
+                if self.lookahead().is_none() {
+                    return Err(err);
+                }
+                self.errors.push(err);
+                self.skip_statement(start);
+            }
+        }
+    }
+
+    fn stat_block(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "statement block";
+        self.make_family_root(InternalNodeType::StatBlock);
+        match self.lookahead() {
+            Some(TokenType::OpenCurly) => {
+                self.expect(TokenType::OpenCurly, CONTEXT)?;
+                self.statement_list(CONTEXT, &[TokenType::CloseCurly])?;
+                self.expect(TokenType::CloseCurly, CONTEXT)
+            }
+            Some(TokenType::SemiColon) | Some(TokenType::Else) => {
+                self.make_empty_node();
+                self.add_child();
+                Ok(())
+            }
+            Some(token_type) if STATEMENT_FIRST.contains(&token_type) => {
+                self.statement()?;
+                self.add_child();
+                Ok(())
+            }
+            _ => Err(self.unexpected(
+                CONTEXT,
+                &[
+                    &[TokenType::OpenCurly],
+                    STATEMENT_FIRST,
+                    &[TokenType::SemiColon, TokenType::Else],
+                ],
+            )),
+        }
+    }
+
+    fn statement(&mut self) -> Result<(), SyntaxError> {
+        self.enter_nesting()?;
+        let res = self.statement_inner();
+        self.exit_nesting();
+        res
+    }
+
+    fn statement_inner(&mut self) -> Result<(), SyntaxError> {
+        match self.lookahead() {
+            Some(TokenType::Id) => {
+                self.make_family_root(InternalNodeType::GenericStatement);
+                self.terminal(TokenType::Id, "statement")?;
+                self.statement_amb1()?;
+                self.add_child();
+                Ok(())
+            }
+            Some(TokenType::Continue) => {
+                self.make_family_root(InternalNodeType::ContinueStatement);
+                self.expect(TokenType::Continue, "continue statement")?;
+                self.expect(TokenType::SemiColon, "continue statement")
+            }
+            Some(TokenType::Break) => {
+                self.make_family_root(InternalNodeType::BreakStatement);
+                self.expect(TokenType::Break, "break statement")?;
+                self.expect(TokenType::SemiColon, "break statement")
+            }
+            Some(TokenType::Return) => self.keyword_expr_statement(
+                InternalNodeType::ReturnStatement,
+                TokenType::Return,
+                "return statement",
+            ),
+            Some(TokenType::Write) => self.keyword_expr_statement(
+                InternalNodeType::WriteStatement,
+                TokenType::Write,
+                "write statement",
+            ),
+            Some(TokenType::Read) => {
+                const CONTEXT: &str = "read statement";
+                self.make_family_root(InternalNodeType::ReadStatement);
+                self.expect(TokenType::Read, CONTEXT)?;
+                self.expect(TokenType::OpenParen, CONTEXT)?;
+                self.variable()?;
+                self.add_child();
+                self.expect(TokenType::CloseParen, CONTEXT)?;
+                self.expect(TokenType::SemiColon, CONTEXT)
+            }
+            Some(TokenType::While) => {
+                const CONTEXT: &str = "while statement";
+                self.make_family_root(InternalNodeType::WhileStatement);
+                self.expect(TokenType::While, CONTEXT)?;
+                self.expect(TokenType::OpenParen, CONTEXT)?;
+                self.expr()?;
+                self.add_child();
+                self.expect(TokenType::CloseParen, CONTEXT)?;
+                self.stat_block()?;
+                self.add_child();
+                self.expect(TokenType::SemiColon, CONTEXT)
+            }
+            Some(TokenType::For) => {
+                const CONTEXT: &str = "for statement";
+                self.make_family_root(InternalNodeType::ForStatement);
+                self.expect(TokenType::For, CONTEXT)?;
+                self.expect(TokenType::OpenParen, CONTEXT)?;
+                self.assign_stat()?;
+                self.add_child();
+                self.expect(TokenType::SemiColon, CONTEXT)?;
+                self.expr()?;
+                self.add_child();
+                self.expect(TokenType::SemiColon, CONTEXT)?;
+                self.assign_stat()?;
+                self.add_child();
+                self.expect(TokenType::CloseParen, CONTEXT)?;
+                self.stat_block()?;
+                self.add_child();
+                self.expect(TokenType::SemiColon, CONTEXT)
+            }
+            Some(TokenType::Switch) => {
+                const CONTEXT: &str = "switch statement";
+                self.make_family_root(InternalNodeType::SwitchStatement);
+                self.expect(TokenType::Switch, CONTEXT)?;
+                self.expect(TokenType::OpenParen, CONTEXT)?;
+                self.expr()?;
+                self.add_child();
+                self.expect(TokenType::CloseParen, CONTEXT)?;
+                self.expect(TokenType::OpenCurly, CONTEXT)?;
+                self.switch_cases()?;
+                self.expect(TokenType::CloseCurly, CONTEXT)?;
+                self.expect(TokenType::SemiColon, CONTEXT)
+            }
+            Some(TokenType::If) => {
+                const CONTEXT: &str = "if statement";
+                self.make_family_root(InternalNodeType::IfStatement);
+                self.expect(TokenType::If, CONTEXT)?;
+                self.expect(TokenType::OpenParen, CONTEXT)?;
+                self.expr()?;
+                self.add_child();
+                self.expect(TokenType::CloseParen, CONTEXT)?;
+                self.expect(TokenType::Then, CONTEXT)?;
+                self.stat_block()?;
+                self.add_child();
+                self.expect(TokenType::Else, CONTEXT)?;
+                self.stat_block()?;
+                self.add_child();
+                self.expect(TokenType::SemiColon, CONTEXT)
+            }
+            _ => Err(self.unexpected("statement", &[STATEMENT_FIRST])),
+        }
+    }
+
+    /// `keyword ( <Expr> ) ;`
+    fn keyword_expr_statement(
+        &mut self,
+        node_t: InternalNodeType,
+        keyword: TokenType,
+        context: &str,
+    ) -> Result<(), SyntaxError> {
+        self.make_family_root(node_t);
+        self.expect(keyword, context)?;
+        self.expect(TokenType::OpenParen, context)?;
+        self.expr()?;
+        self.add_child();
+        self.expect(TokenType::CloseParen, context)?;
+        self.expect(TokenType::SemiColon, context)
+    }
+
+    /// Rest of a statement starting with an identifier: a function call or an assignment
+    fn statement_amb1(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "statement";
+        match self.lookahead() {
+            Some(TokenType::OpenParen) => {
+                self.expect(TokenType::OpenParen, "function call")?;
+                self.params()?;
+                self.add_child();
+                self.expect(TokenType::CloseParen, "function call")?;
+                match self.lookahead() {
+                    Some(TokenType::Period) => {
+                        self.make_family_root(InternalNodeType::DotOp);
+                        self.expect(TokenType::Period, CONTEXT)?;
+                        self.statement_amb1()?;
+                        self.make_relative_operation();
+                        Ok(())
+                    }
+                    Some(TokenType::SemiColon) => self.expect(TokenType::SemiColon, CONTEXT),
+                    _ => Err(self.unexpected(
+                        "function call statement",
+                        &[&[TokenType::Period, TokenType::SemiColon]],
+                    )),
+                }
+            }
+            Some(TokenType::OpenSquare) => {
+                self.indice()?;
+                self.add_child();
+                self.rept_variable()?;
+                match self.lookahead() {
+                    Some(TokenType::Period) => self.dot_statement(),
+                    Some(TokenType::Assignment) => self.assignment(),
+                    _ => {
+                        Err(self
+                            .unexpected(CONTEXT, &[&[TokenType::Period, TokenType::Assignment]]))
+                    }
+                }
+            }
+            Some(TokenType::Assignment) => self.assignment(),
+            Some(TokenType::Period) => self.dot_statement(),
+            _ => Err(self.unexpected(
+                CONTEXT,
+                &[&[
+                    TokenType::OpenParen,
+                    TokenType::OpenSquare,
+                    TokenType::Assignment,
+                    TokenType::Period,
+                ]],
+            )),
+        }
+    }
+
+    /// `. id` followed by the rest of a statement
+    fn dot_statement(&mut self) -> Result<(), SyntaxError> {
+        self.make_family_root(InternalNodeType::DotOp);
+        self.expect(TokenType::Period, "member access")?;
+        self.terminal(TokenType::Id, "member access")?;
+        self.make_relative_operation();
+        self.statement_amb1()
+    }
+
+    /// `= <Expr> ;`
+    fn assignment(&mut self) -> Result<(), SyntaxError> {
+        self.make_family_root(InternalNodeType::Assignment);
+        self.expect(TokenType::Assignment, "assignment")?;
+        self.expr()?;
+        self.make_relative_operation();
+        self.expect(TokenType::SemiColon, "assignment")
+    }
+
+    /// The `case` and `default` labels of a switch statement, each with the statements up to the next one
+    fn switch_cases(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "switch statement";
+        const CASE_END: &[TokenType] =
+            &[TokenType::Case, TokenType::Default, TokenType::CloseCurly];
+        loop {
+            match self.lookahead() {
+                Some(TokenType::Case) => {
+                    self.make_family_root(InternalNodeType::Case);
+                    self.expect(TokenType::Case, CONTEXT)?;
+                    self.arith_expr()?;
+                    self.add_child();
+                    self.expect(TokenType::Colon, CONTEXT)?;
+                }
+                Some(TokenType::Default) => {
+                    self.make_family_root(InternalNodeType::DefaultCase);
+                    self.expect(TokenType::Default, CONTEXT)?;
+                    self.expect(TokenType::Colon, CONTEXT)?;
+                }
+                Some(TokenType::CloseCurly) => return Ok(()),
+                _ => return Err(self.unexpected(CONTEXT, &[CASE_END])),
+            }
+            self.make_family_root(InternalNodeType::StatBlock);
+            self.statement_list(CONTEXT, CASE_END)?;
+            self.add_child();
+            self.add_child();
+        }
+    }
+
+    /// `id <ReptVariable> = <Expr>`, the initializer and step of a for loop
+    fn assign_stat(&mut self) -> Result<(), SyntaxError> {
+        self.terminal(TokenType::Id, "assignment")?;
+        self.rept_variable()?;
+        self.make_family_root(InternalNodeType::Assignment);
+        self.expect(TokenType::Assignment, "assignment")?;
+        self.expr()?;
+        self.make_relative_operation();
+        Ok(())
+    }
+
+    fn variable(&mut self) -> Result<(), SyntaxError> {
+        self.make_family_root(InternalNodeType::Variable);
+        self.terminal(TokenType::Id, "variable")?;
+        self.variable_amb1()?;
+        self.add_child();
+        Ok(())
+    }
+
+    fn variable_amb1(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "variable";
+        match self.lookahead() {
+            Some(TokenType::CloseParen) => return Ok(()),
+            Some(TokenType::Period) | Some(TokenType::OpenSquare) => {
+                self.rept_variable()?;
+            }
+            Some(TokenType::OpenParen) => {
+                self.expect(TokenType::OpenParen, "function call")?;
+                self.params()?;
+                self.add_child();
+                self.expect(TokenType::CloseParen, "function call")?;
+            }
+            _ => {
+                return Err(self.unexpected(
+                    CONTEXT,
+                    &[&[
+                        TokenType::Period,
+                        TokenType::OpenSquare,
+                        TokenType::OpenParen,
+                        TokenType::CloseParen,
+                    ]],
+                ))
+            }
+        }
+        self.make_family_root(InternalNodeType::DotOp);
+        self.expect(TokenType::Period, CONTEXT)?;
+        self.terminal(TokenType::Id, CONTEXT)?;
+        self.make_relative_operation();
+        self.variable_amb1()
+    }
+
+    fn rept_variable(&mut self) -> Result<(), SyntaxError> {
+        const FOLLOW: &[TokenType] = &[
+            TokenType::Period,
+            TokenType::CloseParen,
+            TokenType::SemiColon,
+            TokenType::Minus,
+            TokenType::Plus,
+            TokenType::Comma,
+            TokenType::GreaterEqualThan,
+            TokenType::LessEqualThan,
+            TokenType::GreaterThan,
+            TokenType::LessThan,
+            TokenType::NotEq,
+            TokenType::EqEq,
+            TokenType::And,
+            TokenType::Div,
+            TokenType::Mult,
+            TokenType::Mod,
+            TokenType::CloseSquare,
+            TokenType::Colon,
+            TokenType::Assignment,
+            TokenType::Or,
+        ];
+        while self.at(&[TokenType::OpenSquare]) {
+            self.indice()?;
+            self.add_child();
+        }
+        if !self.at(FOLLOW) {
+            return Err(self.unexpected("variable", &[&[TokenType::OpenSquare], FOLLOW]));
+        }
+        Ok(())
+    }
+
+    fn indice(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "array index";
+        self.make_family_root(InternalNodeType::Indice);
+        self.expect(TokenType::OpenSquare, CONTEXT)?;
+        self.arith_expr()?;
+        self.add_child();
+        self.expect(TokenType::CloseSquare, CONTEXT)
+    }
+
+    // ----- expressions -----
+
+    fn expr(&mut self) -> Result<(), SyntaxError> {
+        if !self.at(EXPR_FIRST) {
+            return Err(self.unexpected("expression", &[EXPR_FIRST]));
+        }
+        self.make_family_root(InternalNodeType::Expr);
+        self.conjunction()?;
+        while self.at(&[TokenType::Or]) {
+            self.make_family_root(InternalNodeType::Or);
+            self.idx += 1;
+            self.conjunction()?;
+            self.make_relative_operation();
+        }
+        if !self.at(EXPR_FOLLOW) {
+            return Err(self.unexpected("expression", &[LOGICAL_OPS, EXPR_FOLLOW]));
+        }
+        self.add_child();
+        Ok(())
+    }
+
+    /// `&` binds tighter than `|`, and both bind looser than the relational operators
+    fn conjunction(&mut self) -> Result<(), SyntaxError> {
+        self.comparison()?;
+        while self.at(&[TokenType::And]) {
+            self.make_family_root(InternalNodeType::And);
+            self.idx += 1;
+            self.comparison()?;
+            self.make_relative_operation();
+        }
+        Ok(())
+    }
+
+    fn comparison(&mut self) -> Result<(), SyntaxError> {
+        self.arith_expr()?;
+        if self.at(REL_OPS) {
+            self.rel_op()?;
+            self.arith_expr()?;
+            self.make_relative_operation();
+        }
+        Ok(())
+    }
+
+    fn rel_op(&mut self) -> Result<(), SyntaxError> {
+        let node_t = match self.lookahead() {
+            Some(TokenType::GreaterEqualThan) => InternalNodeType::GreaterEqualThan,
+            Some(TokenType::LessEqualThan) => InternalNodeType::LessEqualThan,
+            Some(TokenType::GreaterThan) => InternalNodeType::GreaterThan,
+            Some(TokenType::LessThan) => InternalNodeType::LessThan,
+            Some(TokenType::NotEq) => InternalNodeType::NotEqual,
+            Some(TokenType::EqEq) => InternalNodeType::Equal,
+            _ => return Err(self.unexpected("relational expression", &[REL_OPS])),
+        };
+        self.make_family_root(node_t);
+        self.idx += 1;
+        Ok(())
+    }
+
+    fn arith_expr(&mut self) -> Result<(), SyntaxError> {
+        const FOLLOW: &[TokenType] = &[
+            TokenType::CloseParen,
+            TokenType::SemiColon,
+            TokenType::Comma,
+            TokenType::GreaterEqualThan,
+            TokenType::LessEqualThan,
+            TokenType::GreaterThan,
+            TokenType::LessThan,
+            TokenType::NotEq,
+            TokenType::EqEq,
+            TokenType::CloseSquare,
+            TokenType::Colon,
+            TokenType::And,
+            TokenType::Or,
+        ];
+        if !self.at(EXPR_FIRST) {
+            return Err(self.unexpected("arithmetic expression", &[EXPR_FIRST]));
+        }
+        self.make_family_root(InternalNodeType::ArithExpr);
+        self.term()?;
+        while self.at(ADD_OPS) {
+            self.add_op()?;
+            self.term()?;
+            self.make_relative_operation();
+        }
+        if !self.at(FOLLOW) {
+            return Err(self.unexpected("arithmetic expression", &[ADD_OPS, MULT_OPS, FOLLOW]));
+        }
+        self.add_child();
+        Ok(())
+    }
+
+    fn add_op(&mut self) -> Result<(), SyntaxError> {
+        let node_t = match self.lookahead() {
+            Some(TokenType::Minus) => InternalNodeType::Sub,
+            Some(TokenType::Plus) => InternalNodeType::Add,
+            _ => return Err(self.unexpected("arithmetic expression", &[ADD_OPS])),
+        };
+        self.make_family_root(node_t);
+        self.idx += 1;
+        Ok(())
+    }
+
+    fn term(&mut self) -> Result<(), SyntaxError> {
+        const FOLLOW: &[TokenType] = &[
+            TokenType::CloseParen,
+            TokenType::SemiColon,
+            TokenType::Minus,
+            TokenType::Plus,
+            TokenType::Comma,
+            TokenType::GreaterEqualThan,
+            TokenType::LessEqualThan,
+            TokenType::GreaterThan,
+            TokenType::LessThan,
+            TokenType::NotEq,
+            TokenType::EqEq,
+            TokenType::CloseSquare,
+            TokenType::Colon,
+            TokenType::And,
+            TokenType::Or,
+        ];
+        if !self.at(EXPR_FIRST) {
+            return Err(self.unexpected("term", &[EXPR_FIRST]));
+        }
+        self.make_family_root(InternalNodeType::Term);
+        self.factor()?;
+        while self.at(MULT_OPS) {
+            self.mult_op()?;
+            self.factor()?;
+            self.make_relative_operation();
+        }
+        if !self.at(FOLLOW) {
+            return Err(self.unexpected("term", &[MULT_OPS, FOLLOW]));
+        }
+        self.add_child();
+        Ok(())
+    }
+
+    fn mult_op(&mut self) -> Result<(), SyntaxError> {
+        let node_t = match self.lookahead() {
+            Some(TokenType::Div) => InternalNodeType::Div,
+            Some(TokenType::Mult) => InternalNodeType::Mult,
+            Some(TokenType::Mod) => InternalNodeType::Mod,
+            _ => return Err(self.unexpected("term", &[MULT_OPS])),
+        };
+        self.make_family_root(node_t);
+        self.idx += 1;
+        Ok(())
+    }
+
+    fn factor(&mut self) -> Result<(), SyntaxError> {
+        self.enter_nesting()?;
+        let res = self.factor_inner();
+        self.exit_nesting();
+        res
+    }
+
+    fn factor_inner(&mut self) -> Result<(), SyntaxError> {
+        match self.lookahead() {
+            Some(TokenType::Id) => {
+                self.make_family_root(InternalNodeType::Factor);
+                self.terminal(TokenType::Id, "factor")?;
+                self.factor_amb1()?;
+                self.add_child();
+                Ok(())
+            }
+            Some(TokenType::OpenParen) => {
+                self.expect(TokenType::OpenParen, "parenthesized expression")?;
+                self.expr()?;
+                self.expect(TokenType::CloseParen, "parenthesized expression")
+            }
+            Some(TokenType::Minus) | Some(TokenType::Plus) => {
+                let sign = self.lookahead().unwrap();
+                self.make_family_root(InternalNodeType::SignedFactor);
+                self.terminal(sign, "signed factor")?;
+                self.add_child();
+                self.factor()?;
+                self.add_child();
+                Ok(())
+            }
+            Some(TokenType::Question) => {
+                const CONTEXT: &str = "ternary operation";
+                self.make_family_root(InternalNodeType::TernaryOperation);
+                self.expect(TokenType::Question, CONTEXT)?;
+                self.expect(TokenType::OpenSquare, CONTEXT)?;
+                self.expr()?;
+                self.add_child();
+                self.expect(TokenType::Colon, CONTEXT)?;
+                self.expr()?;
+                self.add_child();
+                self.expect(TokenType::Colon, CONTEXT)?;
+                self.expr()?;
+                self.add_child();
+                self.expect(TokenType::CloseSquare, CONTEXT)
+            }
+            Some(TokenType::Bang) => {
+                self.make_family_root(InternalNodeType::Negation);
+                self.expect(TokenType::Bang, "negation")?;
+                self.factor()?;
+                self.add_child();
+                Ok(())
+            }
+            Some(ty @ TokenType::IntegerType) | Some(ty @ TokenType::FloatType) => {
+                const CONTEXT: &str = "cast";
+                self.make_family_root(InternalNodeType::Cast);
+                self.terminal(ty, CONTEXT)?;
+                self.add_child();
+                self.expect(TokenType::OpenParen, CONTEXT)?;
+                self.expr()?;
+                self.add_child();
+                self.expect(TokenType::CloseParen, CONTEXT)
+            }
+            Some(literal @ TokenType::StringLit)
+            | Some(literal @ TokenType::BoolLit)
+            | Some(literal @ TokenType::FloatLit)
+            | Some(literal @ TokenType::IntegerLit) => self.terminal(literal, "factor"),
+            _ => Err(self.unexpected("factor", &[EXPR_FIRST])),
+        }
+    }
+
+    /// Rest of a factor starting with an identifier: indices, a function call and member accesses
+    fn factor_amb1(&mut self) -> Result<(), SyntaxError> {
+        if self.at(&[TokenType::OpenParen]) {
+            self.expect(TokenType::OpenParen, "function call")?;
+            self.params()?;
+            self.expect(TokenType::CloseParen, "function call")?;
+            self.add_child();
+        } else {
+            self.rept_variable()?;
+        }
+
+        if self.at(&[TokenType::Period]) {
+            self.make_family_root(InternalNodeType::DotOp);
+            self.expect(TokenType::Period, "member access")?;
+            self.terminal(TokenType::Id, "member access")?;
+            self.make_relative_operation();
+            self.factor_amb1()
+        } else if self.at(ADD_OPS)
+            || self.at(MULT_OPS)
+            || self.at(REL_OPS)
+            || self.at(LOGICAL_OPS)
+            || self.at(EXPR_FOLLOW)
+        {
+            Ok(())
+        } else {
+            Err(self.unexpected(
+                "factor",
+                &[
+                    &[TokenType::Period],
+                    ADD_OPS,
+                    MULT_OPS,
+                    REL_OPS,
+                    LOGICAL_OPS,
+                    EXPR_FOLLOW,
+                ],
+            ))
+        }
+    }
+
+    fn params(&mut self) -> Result<(), SyntaxError> {
+        const CONTEXT: &str = "function call arguments";
+        self.make_family_root(InternalNodeType::FuncCallParams);
+        if self.at(&[TokenType::CloseParen]) {
+            self.make_empty_node();
+            self.add_child();
+            return Ok(());
+        }
+        if !self.at(EXPR_FIRST) {
+            return Err(self.unexpected(CONTEXT, &[EXPR_FIRST, &[TokenType::CloseParen]]));
+        }
+        self.expr()?;
+        self.add_child();
+        while self.at(&[TokenType::Comma]) {
+            self.expect(TokenType::Comma, CONTEXT)?;
+            self.expr()?;
+            self.add_child();
+        }
+        if !self.at(&[TokenType::CloseParen]) {
+            return Err(self.unexpected(CONTEXT, &[&[TokenType::Comma, TokenType::CloseParen]]));
+        }
+        Ok(())
+    }
+}
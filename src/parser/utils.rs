@@ -1,13 +1,17 @@
 //! Utilities for the parsing
 
-use crate::parser::ast::{Node, SemanticStack};
-use crate::parser::grammar::{DerivationTable, GrammarSymbol};
+use crate::parser::ast::Node;
+use crate::lexer::token::Token;
+use crate::lexer::utils::LINE_ENDINGS;
+use crate::parser::parse::SyntaxError;
+use crate::parser::grammar::{DerivationTable, GrammarRule};
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::fs::OpenOptions;
 use std::hash::{Hash, Hasher};
 use std::io;
 use std::io::{BufWriter, Write};
+use std::str::FromStr;
 
 /// https://stackoverflow.com/questions/45786717/how-to-implement-hashmap-with-two-keys
 pub trait KeyPair<A, B> {
@@ -57,46 +61,287 @@ impl<A, B> KeyPair<A, B> for (&A, &B) {
     }
 }
 
-/// Serializes a [DerivationTable] to a file
+/// Output formats for a [DerivationTable]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DerivationFormat {
+    Markdown,
+    Json,
+    Html,
+}
+
+impl DerivationFormat {
+    /// File extension used for this format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DerivationFormat::Markdown => "md",
+            DerivationFormat::Json => "json",
+            DerivationFormat::Html => "html",
+        }
+    }
+}
+
+impl FromStr for DerivationFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "md" | "markdown" => Ok(DerivationFormat::Markdown),
+            "json" => Ok(DerivationFormat::Json),
+            "html" => Ok(DerivationFormat::Html),
+            _ => Err(format!(
+                "Unknown derivation format '{}', expected one of md, json or html",
+                s
+            )),
+        }
+    }
+}
+
+/// Writes syntax errors to `{file_name}.outsyntaxerrors`, one per line
+pub fn write_syntax_errors_to_file(errors: &[SyntaxError], file_name: &str) -> io::Result<()> {
+    log::info!("Writing syntax errors to file");
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(format!("{}.outsyntaxerrors", file_name))?;
+    let mut buf_writer = BufWriter::new(file);
+
+    for error in errors {
+        buf_writer.write_all(
+            format!(
+                "Syntax error: {}: line {}.{}",
+                error.message, error.line_num, LINE_ENDINGS
+            )
+            .as_bytes(),
+        )?;
+    }
+
+    buf_writer.flush()?;
+    Ok(())
+}
+
+/// Serializes a [DerivationTable] to a file, in the given [DerivationFormat]
 pub fn serialize_derivation_table_to_file(
     table: DerivationTable,
     file_name: &str,
+    format: DerivationFormat,
 ) -> io::Result<()> {
     let table_file = OpenOptions::new()
         .write(true)
         .create(true)
         .truncate(true)
-        .open(format!("{}.derivation.md", file_name))?;
+        .open(format!("{}.derivation.{}", file_name, format.extension()))?;
     let mut buf_writer = BufWriter::new(table_file);
 
-    buf_writer.write("| Stack | Lookahead | Rule |\n| --- | --- | --- |\n".as_bytes())?;
+    match format {
+        DerivationFormat::Markdown => write_derivation_markdown(&table, &mut buf_writer)?,
+        DerivationFormat::Json => {
+            buf_writer.write_all(derivation_table_to_json(&table).as_bytes())?;
+        }
+        DerivationFormat::Html => write_derivation_html(&table, &mut buf_writer)?,
+    }
 
-    for record in table.0 {
-        buf_writer.write("|".as_bytes())?;
-        buf_writer.write(
+    buf_writer.flush()?;
+    Ok(())
+}
+
+fn write_derivation_markdown<W: Write>(table: &DerivationTable, writer: &mut W) -> io::Result<()> {
+    writer.write_all(
+        "| Step | Sentential Form | Stack | Lookahead | Rule |\n| --- | --- | --- | --- | --- |\n"
+            .as_bytes(),
+    )?;
+
+    for (step, record) in table.records().iter().enumerate() {
+        let stack = record
+            .stack_state
+            .iter()
+            .map(|symbol| format!("{:?}", symbol))
+            .collect::<Vec<String>>()
+            .join(" ");
+        writer.write_all(
             format!(
-                "{:?}",
-                record
-                    .stack_state
-                    .iter()
-                    .rev()
-                    .take(5)
-                    .rev()
-                    .collect::<Vec<&GrammarSymbol>>()
+                "| {} | {} | {} | {} | {} |\n",
+                step,
+                escape_markdown(&table.sentential_form(record).join(" ")),
+                escape_markdown(&stack),
+                escape_markdown(&lookahead_to_string(&record.lookahead_token)),
+                escape_markdown(&rule_to_string(&record.derived_rule))
             )
             .as_bytes(),
         )?;
-        buf_writer.write("|".as_bytes())?;
-        buf_writer.write(format!("{:?}", record.lookahead_token).as_bytes())?;
-        buf_writer.write("|".as_bytes())?;
-        buf_writer.write(format!("{:?}", record.derived_rule).as_bytes())?;
-        buf_writer.write("|\n".as_bytes())?;
     }
 
-    buf_writer.flush()?;
     Ok(())
 }
 
+/// Converts a [DerivationTable] to a JSON document
+pub fn derivation_table_to_json(table: &DerivationTable) -> String {
+    let mut json = String::from("{\n  \"steps\": [");
+
+    for (step, record) in table.records().iter().enumerate() {
+        if step > 0 {
+            json.push(',');
+        }
+        let sentential_form = table
+            .sentential_form(record)
+            .iter()
+            .map(|s| escape_json(s))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let stack = record
+            .stack_state
+            .iter()
+            .map(|symbol| escape_json(&format!("{:?}", symbol)))
+            .collect::<Vec<String>>()
+            .join(", ");
+        let lookahead = match &record.lookahead_token {
+            None => String::from("null"),
+            Some(token) => format!(
+                "{{ \"type\": {}, \"lexeme\": {}, \"line\": {} }}",
+                escape_json(&format!("{:?}", token.token_type())),
+                escape_json(token.lexeme()),
+                token.line_num()
+            ),
+        };
+        let rule = match &record.derived_rule {
+            None => String::from("null"),
+            Some(rule) => escape_json(&rule.to_string()),
+        };
+
+        json.push_str(&format!(
+            "\n    {{ \"step\": {}, \"matched\": {}, \"sentential_form\": [{}], \"stack\": [{}], \"lookahead\": {}, \"rule\": {} }}",
+            step, record.matched_count, sentential_form, stack, lookahead, rule
+        ));
+    }
+
+    json.push_str("\n  ]\n}\n");
+    json
+}
+
+fn write_derivation_html<W: Write>(table: &DerivationTable, writer: &mut W) -> io::Result<()> {
+    // The derivation is embedded as JSON and rendered one step at a time
+    let json = derivation_table_to_json(table).replace("</", "<\\/");
+    writer.write_all(
+        format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Derivation</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+#form {{ font-family: monospace; white-space: pre-wrap; border: 1px solid #ccc; padding: 1em; }}
+.matched {{ color: #2a7a2a; }}
+.rule {{ font-family: monospace; }}
+</style>
+</head>
+<body>
+<h1>Leftmost Derivation</h1>
+<div>
+<button id="first">&lt;&lt;</button>
+<button id="prev">&lt;</button>
+<input id="slider" type="range" min="0" value="0">
+<button id="next">&gt;</button>
+<button id="last">&gt;&gt;</button>
+<span id="position"></span>
+</div>
+<h2>Sentential Form</h2>
+<div id="form"></div>
+<h2>Rule</h2>
+<div id="rule" class="rule"></div>
+<h2>Lookahead</h2>
+<div id="lookahead" class="rule"></div>
+<script>
+const derivation = {json};
+const steps = derivation.steps;
+let current = 0;
+const slider = document.getElementById("slider");
+slider.max = Math.max(steps.length - 1, 0);
+function escapeHtml(s) {{
+  return s.replace(/&/g, "&amp;").replace(/</g, "&lt;").replace(/>/g, "&gt;");
+}}
+function render() {{
+  if (steps.length === 0) {{ return; }}
+  const step = steps[current];
+  const pending = step.stack.length;
+  const form = step.sentential_form.map(function (symbol, i) {{
+    return i < step.matched ? '<span class="matched">' + escapeHtml(symbol) + '</span>' : escapeHtml(symbol);
+  }});
+  document.getElementById("form").innerHTML = form.join(" ");
+  document.getElementById("rule").textContent = step.rule === null ? "-" : step.rule;
+  document.getElementById("lookahead").textContent = step.lookahead === null
+    ? "-"
+    : step.lookahead.type + " '" + step.lookahead.lexeme + "' (line " + step.lookahead.line + ")";
+  document.getElementById("position").textContent = "Step " + current + " / " + (steps.length - 1) + " (" + pending + " stack symbols)";
+  slider.value = current;
+}}
+function go(step) {{
+  current = Math.min(Math.max(step, 0), steps.length - 1);
+  render();
+}}
+document.getElementById("first").onclick = function () {{ go(0); }};
+document.getElementById("prev").onclick = function () {{ go(current - 1); }};
+document.getElementById("next").onclick = function () {{ go(current + 1); }};
+document.getElementById("last").onclick = function () {{ go(steps.length - 1); }};
+slider.oninput = function () {{ go(parseInt(slider.value)); }};
+document.onkeydown = function (e) {{
+  if (e.key === "ArrowLeft") {{ go(current - 1); }}
+  if (e.key === "ArrowRight") {{ go(current + 1); }}
+}};
+render();
+</script>
+</body>
+</html>
+"#,
+            json = json
+        )
+        .as_bytes(),
+    )?;
+
+    Ok(())
+}
+
+fn lookahead_to_string(lookahead: &Option<Token>) -> String {
+    match lookahead {
+        None => String::from("-"),
+        Some(token) => format!(
+            "{:?} '{}' (line {})",
+            token.token_type(),
+            token.lexeme(),
+            token.line_num()
+        ),
+    }
+}
+
+fn rule_to_string(rule: &Option<GrammarRule>) -> String {
+    match rule {
+        None => String::from("-"),
+        Some(rule) => rule.to_string(),
+    }
+}
+
+fn escape_markdown(s: &str) -> String {
+    s.replace('|', "\\|")
+}
+
+fn escape_json(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
 struct LabeledNode {
     node: Node,
     label: String,
@@ -109,7 +354,7 @@ impl LabeledNode {
 }
 
 /// Serializes a [SemanticStack] into GraphViz dot file as a graph
-pub fn serialize_tree_to_file(mut tree: SemanticStack, file_name: &str) -> io::Result<()> {
+pub fn serialize_tree_to_file(root: Node, file_name: &str) -> io::Result<()> {
     let tree_file = OpenOptions::new()
         .write(true)
         .create(true)
@@ -121,7 +366,6 @@ pub fn serialize_tree_to_file(mut tree: SemanticStack, file_name: &str) -> io::R
     let mut node_label_count: HashMap<String, usize> = HashMap::new();
 
     let mut root_stack: Vec<LabeledNode> = Vec::new();
-    let root = tree.0.remove(0);
     let root_suffix = node_label_count.entry(root.to_string()).or_insert(0);
     let root_label = format!("{}_{}", root.to_string(), *root_suffix);
     *root_suffix += 1;
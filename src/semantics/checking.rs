@@ -1,9 +1,12 @@
 use crate::parser::ast::{Node, NodeVal};
 use crate::semantics::checking::WarningType::{OverloadWarning, ShadowedMemberWarning};
-use crate::semantics::symbol_table::Scope::{Class, Function, FunctionParameter, Variable};
+use crate::semantics::flow::check_control_flow;
+use crate::semantics::symbol_table::Scope::{Class, Enum, Function, FunctionParameter, Variable};
 use crate::semantics::symbol_table::{ClassEntry, FunctionEntry, Scope, SymbolTable, Type};
-use crate::semantics::utils::get_ancestors_for_class;
-use crate::semantics::validation::validate_statement;
+use crate::semantics::utils::{
+    find_function_for_definition, get_ancestors_for_class, get_class_hierarchy_functions,
+};
+use crate::semantics::validation::{validate_statement, Enclosing};
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -20,9 +23,17 @@ pub enum SemanticError {
     FunctionNotFound(String),
     InvalidParameters(String),
     TypeMistmatch(String),
+    NonBooleanCondition(String),
     NotCallable(String),
-    RecursionNotSupported(String),
     NotClassType(String),
+    InvalidConstant(String),
+    ConstantAssignment(String),
+    AmbiguousCall(String),
+    InvalidOverride(String),
+    UnsupportedInheritance(String),
+    NotInLoop(String),
+    DuplicateCase(String),
+    MissingReturn(String),
     //MultiplyDeclVariable(String),
     //MultiplyDeclMember(String),
     //MultiplyDeclClass(String),
@@ -32,6 +43,7 @@ pub enum SemanticError {
 pub enum WarningType {
     OverloadWarning(String),
     ShadowedMemberWarning(String),
+    UnreachableCodeWarning(String),
 }
 
 #[allow(dead_code)]
@@ -44,20 +56,53 @@ pub(crate) fn report_symbol_errors(global: &SymbolTable) -> Vec<SemanticError> {
     errors.append(&mut check_multiply_decl_id(global));
 
     for scope in global.scopes() {
-        match scope {
-            Class(e) => {
-                errors.append(&mut check_class_symbol_errors(e, global));
-            }
-            Function(e) => {
-                errors.append(&mut check_function_symbol_errors(e, global));
+        errors.append(&mut check_scope_symbol_errors(scope, global));
+    }
+
+    errors.append(&mut check_main_declared(global));
+
+    errors
+}
+
+/// Checks a scope of the global table, identifiers are looked up in `global`
+pub(crate) fn check_scope_symbol_errors(scope: &Scope, global: &SymbolTable) -> Vec<SemanticError> {
+    let mut errors: Vec<SemanticError> = Vec::new();
+
+    match scope {
+        Class(e) => {
+            errors.append(&mut check_class_symbol_errors(e, global));
+        }
+        Function(e) => {
+            errors.append(&mut check_function_symbol_errors(e, global));
+        }
+        Variable(e) => match e.var_type() {
+            Type::Custom(ident) | Type::CustomArray(ident, _)
+                if !matches!(global.find_scope_by_ident(ident), Some(Class(_))) =>
+            {
+                errors.push(SemanticError::UndeclaredClass(format!(
+                    "Global variable {} has undeclared type {}",
+                    e.ident(),
+                    ident
+                )));
             }
             _ => {}
-        }
+        },
+        _ => {}
     }
 
     errors
 }
 
+/// A program has a main function, in one of its files
+pub(crate) fn check_main_declared(global: &SymbolTable) -> Vec<SemanticError> {
+    match global.find_scope_by_ident("main") {
+        Some(Function(_)) => Vec::new(),
+        _ => vec![SemanticError::FunctionNotFound(String::from(
+            "The program has no main function",
+        ))],
+    }
+}
+
 #[allow(dead_code)]
 pub fn report_semantic_errors(root: &Node, global: &SymbolTable) -> Vec<SemanticError> {
     let mut errors: Vec<SemanticError> = Vec::new();
@@ -68,10 +113,9 @@ pub fn report_semantic_errors(root: &Node, global: &SymbolTable) -> Vec<Semantic
     // Check referenced ID for existence
 
     log::info!("Checking semantic errors in function bodies");
-    log::warn!("WARNING: IF ANY FUNCTIONS ARE OVERLOADED IT WILL CAUSE ISSUES");
 
     // function definitions
-    for function_definition in root.children()[1].children() {
+    for function_definition in root.children()[2].children() {
         match (
             function_definition.children()[0].val(),
             function_definition.children()[1].val(),
@@ -79,16 +123,22 @@ pub fn report_semantic_errors(root: &Node, global: &SymbolTable) -> Vec<Semantic
             (Some(NodeVal::Leaf(token1)), Some(NodeVal::Leaf(token2))) => {
                 // member function
                 if let Some(Scope::Class(ce)) = global.find_scope_by_ident(token1.lexeme()) {
-                    if let Some(Scope::Function(fe)) =
-                        ce.table().find_scope_by_ident(token2.lexeme())
+                    if let Some(fe) =
+                        find_function_for_definition(function_definition, ce.table(), global)
                     {
                         for statement in function_definition.children()[4].children()[1].children()
                         {
-                            let statement_res = validate_statement(statement, fe, global);
+                            let statement_res =
+                                validate_statement(statement, fe, global, Enclosing::default());
                             if statement_res.is_err() {
                                 errors.push(statement_res.unwrap_err());
                             }
                         }
+                        errors.append(&mut check_control_flow(
+                            &function_definition.children()[4].children()[1],
+                            fe,
+                            global,
+                        ));
                     } else {
                         errors.push(SemanticError::FunctionNotFound(format!(
                             "No member function {} found in {}: line {}",
@@ -107,13 +157,20 @@ pub fn report_semantic_errors(root: &Node, global: &SymbolTable) -> Vec<Semantic
             }
             (Some(NodeVal::Leaf(token1)), None) => {
                 // free
-                if let Some(Scope::Function(fe)) = global.find_scope_by_ident(token1.lexeme()) {
+                if let Some(fe) = find_function_for_definition(function_definition, global, global)
+                {
                     for statement in function_definition.children()[4].children()[1].children() {
-                        let statement_res = validate_statement(statement, fe, global);
+                        let statement_res =
+                            validate_statement(statement, fe, global, Enclosing::default());
                         if statement_res.is_err() {
                             errors.push(statement_res.unwrap_err());
                         }
                     }
+                    errors.append(&mut check_control_flow(
+                        &function_definition.children()[4].children()[1],
+                        fe,
+                        global,
+                    ));
                 } else {
                     errors.push(SemanticError::FunctionNotFound(format!(
                         "No function {} found: line {}",
@@ -126,15 +183,24 @@ pub fn report_semantic_errors(root: &Node, global: &SymbolTable) -> Vec<Semantic
         }
     }
 
-    if let Some(Scope::Function(main)) = global.find_scope_by_ident("main") {
-        for statement in root.children()[2].children()[0].children()[1].children() {
-            let statement_res = validate_statement(statement, main, global);
-            if statement_res.is_err() {
-                errors.push(statement_res.unwrap_err());
+    // an imported file has no main function
+    if let Some(main_body) = root.children()[3].children().first() {
+        if let Some(Scope::Function(main)) = global.find_scope_by_ident("main") {
+            for statement in main_body.children()[1].children() {
+                let statement_res =
+                    validate_statement(statement, main, global, Enclosing::default());
+                if statement_res.is_err() {
+                    errors.push(statement_res.unwrap_err());
+                }
             }
+            errors.append(&mut check_control_flow(
+                &main_body.children()[1],
+                main,
+                global,
+            ));
+        } else {
+            panic!();
         }
-    } else {
-        panic!();
     }
 
     errors
@@ -154,9 +220,11 @@ pub fn check_class_symbol_errors(class: &ClassEntry, global: &SymbolTable) -> Ve
     errors.append(&mut check_undeclared_types_usage_class(class, global));
     // Check circular inheritance
     errors.append(&mut check_circular_inheritance(class, global));
+    // Check that only the first base class has member functions
+    errors.append(&mut check_multiple_inheritance(class, global));
     // Check circular data member dependencies
     errors.append(&mut check_circular_data_member_dependencies(class, global));
-    // Check shadowed members
+    // Check shadowed members & overridden member functions
     errors.append(&mut check_shadowed_members(class, global));
 
     // check errors in member functions
@@ -212,18 +280,21 @@ pub(crate) fn check_multiply_decl_id(table: &SymbolTable) -> Vec<SemanticError>
                             e2.line_num()
                         )));
                         continue;
-                    } else if e1.ident() == e2.ident() && e1.member_of() == e2.member_of()
-                    //overload
+                    } else if e1.ident() == e2.ident()
+                        && e1.member_of() == e2.member_of()
+                        && e1.type_sig().0 == e2.type_sig().0
+                    //same params, calls can't tell them apart -> Error
                     {
-                        errors.push(SemanticError::Warning(WarningType::OverloadWarning(
-                            format!(
-                                "Found func overload for {}: lines {} and {}",
-                                e1.ident(),
-                                e1.line_num(),
-                                e2.line_num()
-                            ),
+                        errors.push(SemanticError::MultipleDeclIdent(format!(
+                            "Func {} is declared with the same parameters but different return types: lines {} and {}",
+                            e1.ident(),
+                            e1.line_num(),
+                            e2.line_num()
                         )));
                         continue;
+                    } else if e1.ident() == e2.ident() {
+                        //overload, resolved by the argument types of each call
+                        continue;
                     }
                 }
                 (e1, e2) if e1.ident() == e2.ident() => {
@@ -242,6 +313,38 @@ pub(crate) fn check_multiply_decl_id(table: &SymbolTable) -> Vec<SemanticError>
     errors
 }
 
+/// Reports the identifiers of a file's table that another file already declares.
+/// Free functions only clash when they have the same parameters, otherwise they are overloads.
+pub(crate) fn check_multiply_decl_id_across_files(
+    table: &SymbolTable,
+    other: &[Scope],
+    other_file: &str,
+) -> Vec<SemanticError> {
+    let mut errors: Vec<SemanticError> = Vec::new();
+
+    for scope in table.scopes() {
+        for other_scope in other {
+            let clash = match (scope, other_scope) {
+                (Function(e1), Function(e2)) => {
+                    e1.ident() == e2.ident() && e1.type_sig().0 == e2.type_sig().0
+                }
+                (e1, e2) => e1.ident() == e2.ident(),
+            };
+            if clash {
+                errors.push(SemanticError::MultipleDeclIdent(format!(
+                    "Ident {} is already declared in {} (line {}): line {}",
+                    scope.ident(),
+                    other_file,
+                    other_scope.line_num(),
+                    scope.line_num()
+                )));
+            }
+        }
+    }
+
+    errors
+}
+
 #[allow(dead_code)]
 pub(crate) fn check_undeclared_types_usage_fn(
     entry: &FunctionEntry,
@@ -262,6 +365,13 @@ pub(crate) fn check_undeclared_types_usage_fn(
                         if let Some(found) = global.find_scope_by_ident(ident) {
                             match found {
                                 Class(_) => { /* good */ }
+                                // an enum of a file imported after this one
+                                Enum(_) => errors.push(SemanticError::UndeclaredClass(format!(
+                                    "Variable {} in fn {} has type {}, whose file isn't imported",
+                                    e.ident(),
+                                    entry.ident(),
+                                    ident
+                                ))),
                                 _ => {
                                     panic!()
                                 }
@@ -284,6 +394,13 @@ pub(crate) fn check_undeclared_types_usage_fn(
                         if let Some(found) = global.find_scope_by_ident(ident) {
                             match found {
                                 Class(_) => { /* good */ }
+                                // an enum of a file imported after this one
+                                Enum(_) => errors.push(SemanticError::UndeclaredClass(format!(
+                                    "Func param {} in fn {} has type {}, whose file isn't imported",
+                                    e.ident(),
+                                    entry.ident(),
+                                    ident
+                                ))),
                                 _ => {
                                     panic!()
                                 }
@@ -324,6 +441,13 @@ pub(crate) fn check_undeclared_types_usage_class(
                         if let Some(found) = global.find_scope_by_ident(ident) {
                             match found {
                                 Class(_) => { /* good */ }
+                                // an enum of a file imported after this one
+                                Enum(_) => errors.push(SemanticError::UndeclaredClass(format!(
+                                    "Variable {} in class {} has type {}, whose file isn't imported",
+                                    e.ident(),
+                                    entry.ident(),
+                                    ident
+                                ))),
                                 _ => {
                                     panic!()
                                 }
@@ -367,6 +491,33 @@ pub fn check_circular_inheritance(class: &ClassEntry, global: &SymbolTable) -> V
     errors
 }
 
+/// Member functions find the data members of their class at the start of the object, which is where
+/// the first base class is laid out. The other base classes come after it, so they can't have member functions.
+pub(crate) fn check_multiple_inheritance(
+    class: &ClassEntry,
+    global: &SymbolTable,
+) -> Vec<SemanticError> {
+    let mut errors: Vec<SemanticError> = Vec::new();
+
+    for base in class.inherits().iter().skip(1) {
+        if let Type::Custom(ident) = base {
+            if let Some(Class(base_entry)) = global.find_scope_by_ident(ident) {
+                let (functions, _) = get_class_hierarchy_functions(base_entry, global);
+                if !functions.is_empty() {
+                    errors.push(SemanticError::UnsupportedInheritance(format!(
+                        "{} can only inherit member functions from its first base class, {} has some: line {}",
+                        class.ident(),
+                        base_entry.ident(),
+                        class.line_num()
+                    )));
+                }
+            }
+        }
+    }
+
+    errors
+}
+
 #[allow(dead_code)]
 pub(crate) fn check_circular_data_member_dependencies(
     _class: &ClassEntry,
@@ -455,6 +606,28 @@ fn check_shadowed_members_priv(class: &ClassEntry, ancestor: &ClassEntry) -> Vec
             Function(e) => {
                 for parent_member in ancestor.table().scopes() {
                     match parent_member {
+                        // same parameters: the member func overrides the ancestor's one, so calls dispatch to it
+                        Function(pe)
+                            if e.ident() == pe.ident()
+                                && e.type_sig().0 == pe.type_sig().0
+                                && (e.type_sig().1 != pe.type_sig().1
+                                    || e.visibility() != pe.visibility()) =>
+                        {
+                            warnings.push(SemanticError::InvalidOverride(format!(
+                                "{}'s member func {} : {:?} ({:?}) can't override {}'s member func {} : {:?} ({:?}): line {}",
+                                class.ident(),
+                                e.ident(),
+                                e.type_sig().1,
+                                e.visibility(),
+                                ancestor.ident(),
+                                pe.ident(),
+                                pe.type_sig().1,
+                                pe.visibility(),
+                                e.line_num()
+                            )));
+                        }
+                        Function(pe)
+                            if e.ident() == pe.ident() && e.type_sig().0 == pe.type_sig().0 => {}
                         Function(pe) => {
                             if e.ident() == pe.ident() {
                                 warnings.push(SemanticError::Warning(ShadowedMemberWarning(
@@ -0,0 +1,198 @@
+//! Compile-time evaluation of constant expressions
+//!
+//! Constant expressions are made of integer and bool literals, other constants and the operators that apply to them.
+//! Bools evaluate to 0 or 1, which is how they are stored at runtime.
+
+use crate::lexer::token::TokenType;
+use crate::parser::ast::{InternalNodeType, Node, NodeVal};
+use crate::semantics::checking::SemanticError;
+use crate::semantics::symbol_table::Type::{Bool, Integer};
+use crate::semantics::symbol_table::{FunctionEntry, Scope, SymbolTable, Type};
+use crate::semantics::utils::function_constants;
+
+/// Evaluates an Expr node to its type and value
+/// # Arguments
+/// * `expr` - the Expr node
+/// * `constants` - the identifiers visible from the expression, innermost first. Identifiers that aren't constants make the expression non-constant.
+pub fn evaluate_constant_expr(
+    expr: &Node,
+    constants: &SymbolTable,
+) -> Result<(Type, i32), SemanticError> {
+    assert_eq!(expr.val(), Some(&NodeVal::Internal(InternalNodeType::Expr)));
+    evaluate(&expr.children()[0], constants)
+}
+
+/// Evaluates the label of a Case node of a switch statement to its type and value.
+/// The label must be a constant expression of the type of the switch expression, which is up to the caller to check.
+pub fn evaluate_case_label(
+    case: &Node,
+    function_entry: &FunctionEntry,
+    global: &SymbolTable,
+) -> Result<(Type, i32), SemanticError> {
+    assert_eq!(case.val(), Some(&NodeVal::Internal(InternalNodeType::Case)));
+    let constants = function_constants(function_entry, global);
+    evaluate(&case.children()[0], &constants)
+}
+
+fn evaluate(node: &Node, constants: &SymbolTable) -> Result<(Type, i32), SemanticError> {
+    match node.val() {
+        Some(NodeVal::Leaf(token)) => match token.token_type() {
+            TokenType::IntegerLit => match token.lexeme().parse::<i32>() {
+                Ok(value) => Ok((Integer, value)),
+                Err(_) => Err(SemanticError::InvalidConstant(format!(
+                    "Integer literal {} is too large: line {}",
+                    token.lexeme(),
+                    token.line_num()
+                ))),
+            },
+            TokenType::BoolLit => Ok((Bool, (token.lexeme() == "true") as i32)),
+            TokenType::Id => match constants.find_scope_by_ident(token.lexeme()) {
+                Some(Scope::Constant(ce)) if node.children().is_empty() => {
+                    Ok((ce.const_type().clone(), ce.value()))
+                }
+                _ => Err(SemanticError::InvalidConstant(format!(
+                    "{} is not a constant: line {}",
+                    token.lexeme(),
+                    token.line_num()
+                ))),
+            },
+            _ => Err(SemanticError::InvalidConstant(format!(
+                "Only integer and bool constants are supported, found {}: line {}",
+                token.lexeme(),
+                token.line_num()
+            ))),
+        },
+        Some(NodeVal::Internal(node_t)) => match node_t {
+            InternalNodeType::Expr => evaluate(&node.children()[0], constants),
+            InternalNodeType::ArithExpr | InternalNodeType::Term => {
+                evaluate(&node.children()[0], constants)
+            }
+            InternalNodeType::Factor => match node.children()[0].val() {
+                Some(NodeVal::Leaf(_)) => evaluate(&node.children()[0], constants),
+                _ => Err(not_constant(node)),
+            },
+            InternalNodeType::SignedFactor => {
+                let value = expect_type(&node.children()[1], Integer, constants)?;
+                match node.children()[0].val() {
+                    Some(NodeVal::Leaf(sign)) if sign.token_type() == TokenType::Minus => value
+                        .checked_neg()
+                        .map(|v| (Integer, v))
+                        .ok_or_else(|| overflow(node)),
+                    _ => Ok((Integer, value)),
+                }
+            }
+            InternalNodeType::Negation => {
+                let value = expect_type(&node.children()[0], Bool, constants)?;
+                Ok((Bool, (value == 0) as i32))
+            }
+            InternalNodeType::TernaryOperation => {
+                let condition = expect_type(&node.children()[0], Bool, constants)?;
+                let then_res = evaluate(&node.children()[1], constants)?;
+                let else_res = evaluate(&node.children()[2], constants)?;
+                if then_res.0 != else_res.0 {
+                    return Err(SemanticError::TypeMistmatch(format!(
+                        "Mistmatched ternary op types -> {:?} : {:?}: line {}",
+                        then_res.0,
+                        else_res.0,
+                        line_of(node)
+                    )));
+                }
+                Ok(if condition != 0 { then_res } else { else_res })
+            }
+            InternalNodeType::And | InternalNodeType::Or => {
+                let lhs = expect_type(&node.children()[0], Bool, constants)?;
+                let rhs = expect_type(&node.children()[1], Bool, constants)?;
+                let value = match node_t {
+                    InternalNodeType::And => lhs != 0 && rhs != 0,
+                    _ => lhs != 0 || rhs != 0,
+                };
+                Ok((Bool, value as i32))
+            }
+            InternalNodeType::Equal
+            | InternalNodeType::NotEqual
+            | InternalNodeType::LessThan
+            | InternalNodeType::GreaterThan
+            | InternalNodeType::LessEqualThan
+            | InternalNodeType::GreaterEqualThan => {
+                let (lhs_ty, lhs) = evaluate(&node.children()[0], constants)?;
+                let rhs = expect_type(&node.children()[1], lhs_ty, constants)?;
+                let value = match node_t {
+                    InternalNodeType::Equal => lhs == rhs,
+                    InternalNodeType::NotEqual => lhs != rhs,
+                    InternalNodeType::LessThan => lhs < rhs,
+                    InternalNodeType::GreaterThan => lhs > rhs,
+                    InternalNodeType::LessEqualThan => lhs <= rhs,
+                    _ => lhs >= rhs,
+                };
+                Ok((Bool, value as i32))
+            }
+            InternalNodeType::Add
+            | InternalNodeType::Sub
+            | InternalNodeType::Mult
+            | InternalNodeType::Div
+            | InternalNodeType::Mod => {
+                let lhs = expect_type(&node.children()[0], Integer, constants)?;
+                let rhs = expect_type(&node.children()[1], Integer, constants)?;
+                if rhs == 0
+                    && (*node_t == InternalNodeType::Div || *node_t == InternalNodeType::Mod)
+                {
+                    return Err(SemanticError::InvalidConstant(format!(
+                        "Division by zero in constant expression: line {}",
+                        line_of(node)
+                    )));
+                }
+                let value = match node_t {
+                    InternalNodeType::Add => lhs.checked_add(rhs),
+                    InternalNodeType::Sub => lhs.checked_sub(rhs),
+                    InternalNodeType::Mult => lhs.checked_mul(rhs),
+                    InternalNodeType::Div => lhs.checked_div(rhs),
+                    _ => lhs.checked_rem(rhs),
+                };
+                value.map(|v| (Integer, v)).ok_or_else(|| overflow(node))
+            }
+            _ => Err(not_constant(node)),
+        },
+        None => Err(not_constant(node)),
+    }
+}
+
+/// Evaluates a node that has to be of a given type
+fn expect_type(node: &Node, expected: Type, constants: &SymbolTable) -> Result<i32, SemanticError> {
+    let (ty, value) = evaluate(node, constants)?;
+    if ty != expected {
+        return Err(SemanticError::TypeMistmatch(format!(
+            "Constant expression expected {:?}, but got {:?} instead: line {}",
+            expected,
+            ty,
+            line_of(node)
+        )));
+    }
+    Ok(value)
+}
+
+fn not_constant(node: &Node) -> SemanticError {
+    SemanticError::InvalidConstant(format!(
+        "Expression is not a compile-time constant: line {}",
+        line_of(node)
+    ))
+}
+
+fn overflow(node: &Node) -> SemanticError {
+    SemanticError::InvalidConstant(format!(
+        "Constant expression overflows an integer: line {}",
+        line_of(node)
+    ))
+}
+
+/// Line of the first token under a node
+pub(crate) fn line_of(node: &Node) -> usize {
+    match node.val() {
+        Some(NodeVal::Leaf(token)) => token.line_num(),
+        _ => node
+            .children()
+            .iter()
+            .map(line_of)
+            .find(|l| *l != 0)
+            .unwrap_or(0),
+    }
+}
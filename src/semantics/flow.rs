@@ -0,0 +1,170 @@
+//! Control-flow analysis of function bodies
+//!
+//! Follows how control leaves each statement to find the statements that can never run,
+//! and the functions whose end can be reached without returning a value.
+//! Conditions aren't evaluated, except for loops whose condition is a constant `true`: those only end with a `break`.
+
+use crate::parser::ast::{InternalNodeType, Node, NodeVal};
+use crate::semantics::checking::{SemanticError, WarningType};
+use crate::semantics::evaluation::{evaluate_constant_expr, line_of};
+use crate::semantics::symbol_table::{FunctionEntry, SymbolTable, Type};
+use crate::semantics::utils::function_constants;
+
+/// How control can leave a statement
+#[derive(Debug, Clone, Copy)]
+struct Flow {
+    /// Control can go on to the next statement
+    completes: bool,
+    /// A `break` leaves the innermost loop or switch around the statement
+    breaks: bool,
+}
+
+/// Reports the statements of a function body that can't be reached, and the non-void functions
+/// that can reach the end of their body without a `return`.
+/// # Arguments
+/// * `statements` - the StatementList node of the function body
+pub fn check_control_flow(
+    statements: &Node,
+    function_entry: &FunctionEntry,
+    global: &SymbolTable,
+) -> Vec<SemanticError> {
+    let constants = function_constants(function_entry, global);
+    let mut errors: Vec<SemanticError> = Vec::new();
+
+    let flow = check_block(statements, function_entry, &constants, &mut errors);
+    if flow.completes && function_entry.type_sig().1 != Type::Void {
+        errors.push(SemanticError::MissingReturn(format!(
+            "Not all paths of function {} return a value: line {}",
+            function_entry.ident(),
+            function_entry.line_num()
+        )));
+    }
+
+    errors
+}
+
+/// Follows the statements of a block in order. Only the first unreachable statement is reported.
+fn check_block(
+    block: &Node,
+    function_entry: &FunctionEntry,
+    constants: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> Flow {
+    let mut flow = Flow {
+        completes: true,
+        breaks: false,
+    };
+    let mut last_line = 0;
+    for statement in block.children().iter().filter(|n| n.val().is_some()) {
+        // break and continue have no line of their own
+        let line = match line_of(statement) {
+            0 => last_line,
+            line => line,
+        };
+        if !flow.completes {
+            errors.push(SemanticError::Warning(WarningType::UnreachableCodeWarning(
+                format!(
+                    "Unreachable statement in function {}: line {}",
+                    function_entry.ident(),
+                    line
+                ),
+            )));
+            break;
+        }
+        let statement_flow = check_statement(statement, function_entry, constants, errors);
+        flow = Flow {
+            completes: statement_flow.completes,
+            breaks: flow.breaks || statement_flow.breaks,
+        };
+        last_line = line;
+    }
+
+    flow
+}
+
+fn check_statement(
+    statement: &Node,
+    function_entry: &FunctionEntry,
+    constants: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> Flow {
+    match statement.val() {
+        Some(NodeVal::Internal(InternalNodeType::ReturnStatement))
+        | Some(NodeVal::Internal(InternalNodeType::ContinueStatement)) => Flow {
+            completes: false,
+            breaks: false,
+        },
+        Some(NodeVal::Internal(InternalNodeType::BreakStatement)) => Flow {
+            completes: false,
+            breaks: true,
+        },
+        Some(NodeVal::Internal(InternalNodeType::IfStatement)) => {
+            let then_flow =
+                check_block(&statement.children()[1], function_entry, constants, errors);
+            let else_flow =
+                check_block(&statement.children()[2], function_entry, constants, errors);
+            Flow {
+                completes: then_flow.completes || else_flow.completes,
+                breaks: then_flow.breaks || else_flow.breaks,
+            }
+        }
+        Some(NodeVal::Internal(InternalNodeType::WhileStatement)) => check_loop(
+            &statement.children()[0],
+            &statement.children()[1],
+            function_entry,
+            constants,
+            errors,
+        ),
+        Some(NodeVal::Internal(InternalNodeType::ForStatement)) => check_loop(
+            &statement.children()[1],
+            &statement.children()[3],
+            function_entry,
+            constants,
+            errors,
+        ),
+        Some(NodeVal::Internal(InternalNodeType::SwitchStatement)) => {
+            // cases don't fall through, so the switch goes on when one of its cases does,
+            // breaks out of it, or when no case matches
+            let mut has_default = false;
+            let mut case_completes = false;
+            for case in &statement.children()[1..] {
+                has_default |=
+                    case.val() == Some(&NodeVal::Internal(InternalNodeType::DefaultCase));
+                let case_flow = check_block(
+                    case.children().last().unwrap(),
+                    function_entry,
+                    constants,
+                    errors,
+                );
+                case_completes |= case_flow.completes || case_flow.breaks;
+            }
+            Flow {
+                completes: !has_default || case_completes,
+                breaks: false,
+            }
+        }
+        _ => Flow {
+            completes: true,
+            breaks: false,
+        },
+    }
+}
+
+/// A loop can run any number of times, so it goes on after its body unless its condition is always true
+fn check_loop(
+    condition: &Node,
+    body: &Node,
+    function_entry: &FunctionEntry,
+    constants: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> Flow {
+    let body_flow = check_block(body, function_entry, constants, errors);
+    let endless = matches!(
+        evaluate_constant_expr(condition, constants),
+        Ok((Type::Bool, value)) if value != 0
+    );
+    Flow {
+        completes: !endless || body_flow.breaks,
+        breaks: false,
+    }
+}
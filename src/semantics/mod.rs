@@ -1,4 +1,6 @@
 pub mod checking;
+pub mod evaluation;
+pub mod flow;
 pub mod symbol_table;
 pub mod utils;
 pub mod validation;
@@ -1,12 +1,19 @@
 use crate::parser::ast::{InternalNodeType, Node, NodeVal};
-use crate::semantics::checking::{report_semantic_errors, report_symbol_errors, SemanticError};
-use crate::semantics::symbol_table::Type::{CustomArray, FloatArray, IntegerArray, StringArray};
+use crate::parser::modules::{FileId, Module};
+use crate::semantics::checking::{
+    check_main_declared, check_multiply_decl_id, check_multiply_decl_id_across_files,
+    check_scope_symbol_errors, report_semantic_errors, report_symbol_errors, SemanticError,
+};
+use crate::semantics::symbol_table::Type::{
+    BoolArray, CustomArray, EnumArray, FloatArray, IntegerArray, StringArray,
+};
 use crate::semantics::utils::{
-    generate_class_entries, generate_function_entries, map_main_to_func_entry,
-    merge_member_function_tables,
+    generate_class_entries, generate_constant_entries, generate_enum_entries,
+    generate_function_entries, generate_global_variable_entries, map_main_to_func_entry, merge_member_function_tables,
 };
 use std::fmt;
 use std::fmt::{Debug, Formatter};
+use std::ops::Range;
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -15,6 +22,8 @@ pub enum Scope {
     Function(FunctionEntry),
     Variable(VariableEntry),
     FunctionParameter(ParameterEntry),
+    Constant(ConstantEntry),
+    Enum(EnumEntry),
 }
 
 impl Scope {
@@ -24,6 +33,8 @@ impl Scope {
             Scope::Function(e) => &e.identifier,
             Scope::Variable(e) => &e.identifier,
             Scope::FunctionParameter(e) => &e.identifier,
+            Scope::Constant(e) => &e.identifier,
+            Scope::Enum(e) => &e.identifier,
         }
     }
 
@@ -33,6 +44,8 @@ impl Scope {
             Scope::Function(e) => e.line_num(),
             Scope::Variable(e) => e.line_num(),
             Scope::FunctionParameter(e) => e.line_num(),
+            Scope::Constant(e) => e.line_num(),
+            Scope::Enum(e) => e.line_num(),
         }
     }
 }
@@ -125,8 +138,12 @@ pub enum Type {
     FloatArray(Vec<u32>),
     String,
     StringArray(Vec<u32>),
+    Bool,
+    BoolArray(Vec<u32>),
     Custom(String),
     CustomArray(String, Vec<u32>),
+    Enum(String),
+    EnumArray(String, Vec<u32>),
     Void,
 }
 
@@ -136,21 +153,31 @@ impl Type {
             Type::Integer => IntegerArray(array_dim),
             Type::Float => FloatArray(array_dim),
             Type::String => StringArray(array_dim),
+            Type::Bool => BoolArray(array_dim),
             Type::Custom(id) => CustomArray(id.clone(), array_dim),
+            Type::Enum(id) => EnumArray(id.clone(), array_dim),
             Type::IntegerArray(_) => IntegerArray(array_dim),
             Type::FloatArray(_) => FloatArray(array_dim),
             Type::StringArray(_) => StringArray(array_dim),
+            Type::BoolArray(_) => BoolArray(array_dim),
             Type::CustomArray(s, _) => CustomArray(s.clone(), array_dim),
+            Type::EnumArray(s, _) => EnumArray(s.clone(), array_dim),
             _ => self.clone(),
         }
     }
 
+    pub fn is_enum(&self) -> bool {
+        matches!(self, Type::Enum(_))
+    }
+
     pub fn to_simple_type(&self) -> Type {
         match self {
             Type::IntegerArray(_) => Type::Integer,
             Type::FloatArray(_) => Type::Float,
             Type::StringArray(_) => Type::String,
+            Type::BoolArray(_) => Type::Bool,
             Type::CustomArray(s, _) => Type::Custom(s.clone()),
+            Type::EnumArray(s, _) => Type::Enum(s.clone()),
             _ => self.clone(),
         }
     }
@@ -168,6 +195,9 @@ impl Debug for Type {
             Type::String => {
                 write!(f, "string")
             }
+            Type::Bool => {
+                write!(f, "bool")
+            }
             Type::Void => {
                 write!(f, "void")
             }
@@ -192,6 +222,13 @@ impl Debug for Type {
                 }
                 Ok(())
             }
+            Type::BoolArray(dim) => {
+                write!(f, "bool")?;
+                for u in dim {
+                    write!(f, "[{}]", u)?;
+                }
+                Ok(())
+            }
             Type::Custom(id) => {
                 write!(f, "{}", id)
             }
@@ -202,6 +239,16 @@ impl Debug for Type {
                 }
                 Ok(())
             }
+            Type::Enum(id) => {
+                write!(f, "{}", id)
+            }
+            EnumArray(id, dim) => {
+                write!(f, "{}", id)?;
+                for u in dim {
+                    write!(f, "[{}]", u)?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -295,7 +342,6 @@ impl FunctionEntry {
         &mut self.table
     }
 
-    #[allow(dead_code)]
     pub fn visibility(&self) -> Visibility {
         self.visibility
     }
@@ -451,33 +497,235 @@ impl ParameterEntry {
     }
 }
 
+/// A named constant. Its value is computed at compile time; bools are 0 or 1, like they are stored at runtime.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ConstantEntry {
+    identifier: String,
+    constant_type: Type,
+    value: i32,
+    visibility: Visibility,
+    line_num: usize,
+}
+
+impl ConstantEntry {
+    pub fn new(ident: &str, ty: Type, value: i32, line_num: usize) -> Self {
+        Self {
+            identifier: ident.to_string(),
+            constant_type: ty,
+            value,
+            visibility: Visibility::default(),
+            line_num,
+        }
+    }
+
+    pub fn ident(&self) -> &str {
+        &self.identifier
+    }
+
+    pub fn const_type(&self) -> &Type {
+        &self.constant_type
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+
+    #[allow(dead_code)]
+    pub fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+
+    pub fn set_visibility(&mut self, vis: Visibility) {
+        self.visibility = vis;
+    }
+
+    pub fn line_num(&self) -> usize {
+        self.line_num
+    }
+}
+
+/// An enumeration type. Its enumerators are global constants of the enum's type,
+/// whose values are their position in the declaration.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EnumEntry {
+    identifier: String,
+    enumerators: Vec<String>,
+    line_num: usize,
+}
+
+impl EnumEntry {
+    pub fn new(ident: &str, enumerators: Vec<String>, line_num: usize) -> Self {
+        Self {
+            identifier: ident.to_string(),
+            enumerators,
+            line_num,
+        }
+    }
+
+    pub fn ident(&self) -> &str {
+        &self.identifier
+    }
+
+    pub fn enumerators(&self) -> &Vec<String> {
+        &self.enumerators
+    }
+
+    pub fn line_num(&self) -> usize {
+        self.line_num
+    }
+}
+
 #[allow(dead_code)]
 pub fn generate_symbol_table(root: &Node) -> (SymbolTable, Vec<SemanticError>) {
+    let (global_table, _, errors) = generate_module_symbol_table(root, &SymbolTable::new());
+    (global_table, errors)
+}
+
+/// Generates the table of a single file. Its constants and types can use the constants and enums of `imported`.
+/// Also returns the definitions of member functions whose class isn't declared in the file.
+fn generate_module_symbol_table(
+    root: &Node,
+    imported: &SymbolTable,
+) -> (SymbolTable, Vec<FunctionEntry>, Vec<SemanticError>) {
     assert_eq!(root.val(), Some(&NodeVal::Internal(InternalNodeType::Root)));
-    assert_eq!(root.children().len(), 3); // class declarations, func definitions, main
+    assert_eq!(root.children().len(), 4); // global declarations, class declarations, func definitions, main
 
     log::info!("Generating Global symbol table");
 
-    let mut global_table = SymbolTable::new();
+    // the imported constants are only there to be used, they aren't part of the file's table
+    let mut global_table = imported.clone();
+    let imported_count = global_table.scopes().len();
+    let mut errors: Vec<SemanticError> = Vec::new();
 
-    let class_entries: Vec<ClassEntry> = generate_class_entries(&root.children()[0]);
+    // Enums come first, their enumerators are constants the other declarations can use
+    let (enum_entries, enumerators) = generate_enum_entries(&root.children()[0]);
+    global_table.add_scopes(enum_entries.into_iter().map(Scope::Enum).collect());
+    global_table.add_scopes(enumerators.into_iter().map(Scope::Constant).collect());
 
-    let (free_function_entries, mut member_function_entries) =
-        generate_function_entries(&root.children()[1]);
+    // Then constants, so classes, functions and global variables can use them
+    let constant_entries: Vec<ConstantEntry> =
+        generate_constant_entries(&root.children()[0], &global_table, &mut errors);
+    global_table.add_scopes(constant_entries.into_iter().map(Scope::Constant).collect());
 
-    let main_entry: FunctionEntry = map_main_to_func_entry(&root.children()[2]);
+    let variable_entries: Vec<VariableEntry> =
+        generate_global_variable_entries(&root.children()[0], &global_table, &mut errors);
+    global_table.add_scopes(variable_entries.into_iter().map(Scope::Variable).collect());
 
+    let class_entries: Vec<ClassEntry> =
+        generate_class_entries(&root.children()[1], &global_table, &mut errors);
     global_table.add_scopes(class_entries.into_iter().map(Scope::Class).collect());
+
+    let (free_function_entries, mut member_function_entries) =
+        generate_function_entries(&root.children()[2], &global_table, &mut errors);
+
+    // an imported file has no main function
+    let main_entry: Option<FunctionEntry> = if root.children()[3].children().is_empty() {
+        None
+    } else {
+        Some(map_main_to_func_entry(
+            &root.children()[3],
+            &global_table,
+            &mut errors,
+        ))
+    };
+
     global_table.add_scopes(
         free_function_entries
             .into_iter()
             .map(Scope::Function)
             .collect(),
     );
-    global_table.add_scope(Scope::Function(main_entry));
+    if let Some(main_entry) = main_entry {
+        global_table.add_scope(Scope::Function(main_entry));
+    }
+
+    errors.append(&mut merge_member_function_tables(
+        &mut global_table,
+        &mut member_function_entries,
+    ));
 
-    let errors: Vec<SemanticError> =
-        merge_member_function_tables(&mut global_table, &mut member_function_entries);
+    global_table.scopes_mut().drain(..imported_count);
+
+    (global_table, member_function_entries, errors)
+}
+
+/// Generates the global table of a program made of several files, see [crate::parser::modules].
+/// Each file gets its own table, in the order of `modules`, which are merged into the global table.
+/// A file can use the constants and enums of the files before it, and any other identifier of the program.
+/// The symbols are checked too, since their errors depend on the file they are declared in.
+/// Errors are paired with the file they were found in.
+pub fn generate_program_symbol_table(
+    modules: &[Module],
+) -> (SymbolTable, Vec<(FileId, SemanticError)>) {
+    let mut global_table = SymbolTable::new();
+    let mut errors: Vec<(FileId, SemanticError)> = Vec::new();
+    // the scopes of each module in the global table
+    let mut module_scopes: Vec<Range<usize>> = Vec::new();
+    let mut unmerged_member_functions: Vec<(FileId, FunctionEntry)> = Vec::new();
+
+    for module in modules {
+        let file_id = module.file_id();
+        let constants = SymbolTable::new_from_scopes(
+            global_table
+                .scopes()
+                .iter()
+                .filter(|scope| matches!(scope, Scope::Constant(_) | Scope::Enum(_)))
+                .cloned()
+                .collect(),
+        );
+        let (table, member_functions, module_errors) =
+            generate_module_symbol_table(module.root(), &constants);
+        errors.extend(module_errors.into_iter().map(|e| (file_id, e)));
+
+        errors.extend(
+            check_multiply_decl_id(&table)
+                .into_iter()
+                .map(|e| (file_id, e)),
+        );
+        for (other, scopes) in modules.iter().zip(module_scopes.iter()) {
+            errors.extend(
+                check_multiply_decl_id_across_files(
+                    &table,
+                    &global_table.scopes()[scopes.clone()],
+                    &other.file_name(),
+                )
+                .into_iter()
+                .map(|e| (file_id, e)),
+            );
+        }
+
+        let start = global_table.scopes().len();
+        global_table.merge(table);
+        module_scopes.push(start..global_table.scopes().len());
+        unmerged_member_functions.extend(member_functions.into_iter().map(|fe| (file_id, fe)));
+    }
+
+    // a member function can be defined in another file than its class.
+    // The ones left have no class, which is reported with their definition.
+    for (file_id, member_function) in unmerged_member_functions {
+        errors.extend(
+            merge_member_function_tables(&mut global_table, &mut vec![member_function])
+                .into_iter()
+                .map(|e| (file_id, e)),
+        );
+    }
+
+    for (module, scopes) in modules.iter().zip(module_scopes) {
+        for scope in &global_table.scopes()[scopes] {
+            errors.extend(
+                check_scope_symbol_errors(scope, &global_table)
+                    .into_iter()
+                    .map(|e| (module.file_id(), e)),
+            );
+        }
+    }
+    if let Some(program) = modules.last() {
+        errors.extend(
+            check_main_declared(&global_table)
+                .into_iter()
+                .map(|e| (program.file_id(), e)),
+        );
+    }
 
     (global_table, errors)
 }
@@ -494,3 +742,21 @@ pub fn check_semantics(root: &Node, global: &SymbolTable) -> Vec<SemanticError>
 
     errors
 }
+
+/// Checks the function bodies of every file of a program, see [generate_program_symbol_table].
+/// Errors are paired with the file they were found in.
+pub fn check_program_semantics(
+    modules: &[Module],
+    global: &SymbolTable,
+) -> Vec<(FileId, SemanticError)> {
+    log::info!("Checking program semantics");
+
+    modules
+        .iter()
+        .flat_map(|module| {
+            report_semantic_errors(module.root(), global)
+                .into_iter()
+                .map(move |e| (module.file_id(), e))
+        })
+        .collect()
+}
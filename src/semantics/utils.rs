@@ -1,11 +1,19 @@
 use crate::lexer::token::{Token, TokenType};
-use crate::parser::ast::InternalNodeType::{ClassDeclarations, FunctionDefinitions};
+use crate::lexer::utils::LINE_ENDINGS;
+use crate::parser::ast::InternalNodeType::{
+    ClassDeclarations, FunctionDefinitions, GlobalDeclarations,
+};
 use crate::parser::ast::{InternalNodeType, Node, NodeVal};
+use crate::parser::modules::{FileId, Module, ModuleError};
 use crate::semantics::checking::{SemanticError};
+use crate::semantics::evaluation::evaluate_constant_expr;
 use crate::semantics::symbol_table;
-use crate::semantics::symbol_table::Scope::{Class, Function, FunctionParameter, Variable};
+use crate::semantics::symbol_table::Scope::{
+    Class, Constant, Enum, Function, FunctionParameter, Variable,
+};
 use crate::semantics::symbol_table::{
-    ClassEntry, FunctionEntry, ParameterEntry, Scope, SymbolTable, Type, VariableEntry, Visibility,
+    ClassEntry, ConstantEntry, EnumEntry, FunctionEntry, ParameterEntry, Scope, SymbolTable,
+    Type, VariableEntry, Visibility,
 };
 use std::fs::OpenOptions;
 use std::io;
@@ -13,36 +21,222 @@ use std::io::{BufWriter, Write};
 use crate::semantics::checking::WarningType::{OverloadWarning, ShadowedMemberWarning};
 
 #[allow(dead_code)]
-pub fn generate_class_entries(node: &Node) -> Vec<ClassEntry> {
+/// Maps the enum declarations, along with their enumerators: global constants of the enum's type,
+/// numbered from 0 in the order they are declared
+pub fn generate_enum_entries(node: &Node) -> (Vec<EnumEntry>, Vec<ConstantEntry>) {
+    assert_eq!(node.val(), Some(&NodeVal::Internal(GlobalDeclarations)));
+    log::info!("Generating enums");
+
+    let mut enum_entries: Vec<EnumEntry> = Vec::new();
+    let mut enumerators: Vec<ConstantEntry> = Vec::new();
+    for enum_decl in node.children().iter().filter(|n| {
+        n.val() == Some(&NodeVal::Internal(InternalNodeType::EnumDeclaration))
+    }) {
+        let tokens: Vec<&Token> = enum_decl
+            .children()
+            .iter()
+            .map(|n| match n.val() {
+                Some(NodeVal::Leaf(t)) => t,
+                _ => panic!(),
+            })
+            .collect();
+        let ty = Type::Enum(tokens[0].lexeme().to_string());
+        for (value, token) in tokens[1..].iter().enumerate() {
+            enumerators.push(ConstantEntry::new(
+                token.lexeme(),
+                ty.clone(),
+                value as i32,
+                token.line_num(),
+            ));
+        }
+        enum_entries.push(EnumEntry::new(
+            tokens[0].lexeme(),
+            tokens[1..].iter().map(|t| t.lexeme().to_string()).collect(),
+            tokens[0].line_num(),
+        ));
+    }
+
+    (enum_entries, enumerators)
+}
+
+#[allow(dead_code)]
+/// Evaluates the global constants, in order. Each constant can use the ones declared before it,
+/// and the ones of `imported`.
+pub fn generate_constant_entries(
+    node: &Node,
+    imported: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> Vec<ConstantEntry> {
+    assert_eq!(node.val(), Some(&NodeVal::Internal(GlobalDeclarations)));
+    log::info!("Evaluating global constants");
+
+    let mut constants = imported.clone();
+    let imported_count = constants.scopes().len();
+    for const_decl in node.children().iter().filter(|n| {
+        n.val() == Some(&NodeVal::Internal(InternalNodeType::ConstDeclaration))
+    }) {
+        let entry = map_const_decl_to_entry(const_decl, &constants, errors);
+        constants.add_scope(Scope::Constant(entry));
+    }
+
+    constants
+        .scopes()
+        .iter()
+        .skip(imported_count)
+        .filter_map(|scope| match scope {
+            Constant(e) => Some(e.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[allow(dead_code)]
+/// Maps the global variable declarations. `global` must already hold the global constants.
+pub fn generate_global_variable_entries(
+    node: &Node,
+    global: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> Vec<VariableEntry> {
+    assert_eq!(node.val(), Some(&NodeVal::Internal(GlobalDeclarations)));
+    log::info!("Generating global variables");
+
+    node.children()
+        .iter()
+        .filter(|n| n.val() == Some(&NodeVal::Internal(InternalNodeType::VarDeclaration)))
+        .map(|n| map_var_decl_to_entry(n, global, errors))
+        .collect()
+}
+
+#[allow(dead_code)]
+pub fn generate_class_entries(
+    node: &Node,
+    global: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> Vec<ClassEntry> {
     assert_eq!(node.val(), Some(&NodeVal::Internal(ClassDeclarations)));
     log::info!("Generating class symbol tables");
 
     let entries: Vec<ClassEntry> = node
         .children()
         .iter()
-        .map(map_class_decl_to_entry)
+        .map(|n| map_class_decl_to_entry(n, global, errors))
         .collect();
 
     entries
 }
 
 #[allow(dead_code)]
-pub fn generate_function_entries(node: &Node) -> (Vec<FunctionEntry>, Vec<FunctionEntry>) {
+/// `global` must already hold the global constants and the classes
+pub fn generate_function_entries(
+    node: &Node,
+    global: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> (Vec<FunctionEntry>, Vec<FunctionEntry>) {
     assert_eq!(node.val(), Some(&NodeVal::Internal(FunctionDefinitions)));
     log::info!("Generating symbol tables for function declarations");
 
     let entries: (Vec<FunctionEntry>, Vec<FunctionEntry>) = node
         .children()
         .iter()
-        .map(map_func_def_to_entry)
+        .map(|n| map_func_def_to_entry(n, global, errors))
         .partition(|entry| entry.member_of().is_none());
 
     entries
 }
 
+/// Builds the table used to evaluate constants and name types in a scope: every identifier of the scope itself,
+/// so locals shadow outer constants, followed by the constants and enums visible from the enclosing scope
+pub(crate) fn visible_constants(inner: &SymbolTable, outer: &SymbolTable) -> SymbolTable {
+    let mut visible = inner.clone();
+    visible.add_scopes(
+        outer
+            .scopes()
+            .iter()
+            .filter(|scope| {
+                matches!(scope, Constant(_) | Enum(_))
+                    && std::ptr::eq(outer.find_scope_by_ident(scope.ident()).unwrap(), *scope)
+            })
+            .cloned()
+            .collect(),
+    );
+    visible
+}
+
+/// Builds the table used to evaluate constants in the body of a function:
+/// its own identifiers, then the constants of its class, then the global ones
+pub(crate) fn function_constants(function_entry: &FunctionEntry, global: &SymbolTable) -> SymbolTable {
+    let outer = match function_entry
+        .member_of()
+        .and_then(|class_ident| global.find_scope_by_ident(class_ident))
+    {
+        Some(Class(ce)) => visible_constants(ce.table(), global),
+        _ => global.clone(),
+    };
+    visible_constants(function_entry.table(), &outer)
+}
+
+/// Finds the constant an identifier refers to from within a function.
+/// Identifiers of the function shadow the members of its class, which shadow global constants.
+pub(crate) fn find_constant<'a>(
+    ident: &str,
+    function_entry: &'a FunctionEntry,
+    global: &'a SymbolTable,
+) -> Option<&'a ConstantEntry> {
+    match function_entry.table().find_scope_by_ident(ident) {
+        Some(Constant(e)) => return Some(e),
+        Some(_) => return None,
+        None => {}
+    }
+    if let Some(Class(ce)) = function_entry
+        .member_of()
+        .and_then(|class_ident| global.find_scope_by_ident(class_ident))
+    {
+        match ce.table().find_scope_by_ident(ident) {
+            Some(Constant(e)) => return Some(e),
+            Some(_) => return None,
+            None => {}
+        }
+    }
+    match global.find_scope_by_ident(ident) {
+        Some(Constant(e)) => Some(e),
+        _ => None,
+    }
+}
+
+/// Looks up a variable from a function: one of its local variables, or a global variable.
+/// Parameters, local variables and data members of the function's class shadow global variables.
+pub(crate) fn find_variable<'a>(
+    ident: &str,
+    function_entry: &'a FunctionEntry,
+    global: &'a SymbolTable,
+) -> Option<&'a VariableEntry> {
+    match function_entry.table().find_scope_by_ident(ident) {
+        Some(Variable(e)) => return Some(e),
+        Some(_) => return None,
+        None => {}
+    }
+    if let Some(Class(ce)) = function_entry
+        .member_of()
+        .and_then(|class_ident| global.find_scope_by_ident(class_ident))
+    {
+        let (data_members, _errors) = get_class_hierarchy_data_members(ce, global);
+        if data_members.iter().any(|ve| ve.ident() == ident) {
+            return None;
+        }
+    }
+    match global.find_scope_by_ident(ident) {
+        Some(Variable(e)) => Some(e),
+        _ => None,
+    }
+}
+
 #[allow(dead_code)]
 /// Maps a ClassDeclaration node to a ClassEntry
-pub fn map_class_decl_to_entry(node: &Node) -> ClassEntry {
+pub fn map_class_decl_to_entry(
+    node: &Node,
+    global: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> ClassEntry {
     assert_eq!(
         node.val(),
         Some(&NodeVal::Internal(InternalNodeType::ClassDeclaration))
@@ -73,13 +267,17 @@ pub fn map_class_decl_to_entry(node: &Node) -> ClassEntry {
                             let inherits: Vec<Type> = node.children()[1]
                                 .children()
                                 .iter()
-                                .map(map_to_type)
-                                .collect();
-                            let mut members: Vec<Scope> = node.children()[2]
-                                .children()
-                                .iter()
-                                .map(map_member_to_scope)
+                                .map(|n| map_to_type(n, global))
                                 .collect();
+                            // Members can use the class constants declared before them
+                            let mut class_table = SymbolTable::new();
+                            for member in node.children()[2].children() {
+                                let constants = visible_constants(&class_table, global);
+                                class_table.add_scope(map_member_to_scope(
+                                    member, &constants, errors,
+                                ));
+                            }
+                            let mut members: Vec<Scope> = class_table.scopes().clone();
                             for scope in members.iter_mut() {
                                 match scope {
                                     Scope::Function(entry) => {
@@ -107,7 +305,11 @@ pub fn map_class_decl_to_entry(node: &Node) -> ClassEntry {
 
 #[allow(dead_code)]
 /// Maps a FuncDeclaration node to a FunctionEntry
-pub(crate) fn map_func_decl_to_entry(node: &Node) -> FunctionEntry {
+pub(crate) fn map_func_decl_to_entry(
+    node: &Node,
+    constants: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> FunctionEntry {
     assert_eq!(
         node.val(),
         Some(&NodeVal::Internal(InternalNodeType::FuncDeclaration))
@@ -136,9 +338,10 @@ pub(crate) fn map_func_decl_to_entry(node: &Node) -> FunctionEntry {
                 let params: Vec<ParameterEntry> = node.children()[1]
                     .children()
                     .iter()
-                    .map(map_func_param_to_entry)
+                    .filter(|n| n.val().is_some())
+                    .map(|n| map_func_param_to_entry(n, constants, errors))
                     .collect();
-                let return_ty = map_to_type(&node.children()[2]);
+                let return_ty = map_to_type(&node.children()[2], constants);
                 let ty_signature: (Vec<Type>, Type) = (
                     params.iter().map(|p| p.param_type().clone()).collect(),
                     return_ty,
@@ -160,7 +363,11 @@ pub(crate) fn map_func_decl_to_entry(node: &Node) -> FunctionEntry {
 
 #[allow(dead_code)]
 /// Maps a FuncDef node to a FunctionEntry
-pub(crate) fn map_func_def_to_entry(node: &Node) -> FunctionEntry {
+pub(crate) fn map_func_def_to_entry(
+    node: &Node,
+    global: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> FunctionEntry {
     assert_eq!(
         node.val(),
         Some(&NodeVal::Internal(InternalNodeType::FuncDef))
@@ -196,15 +403,21 @@ pub(crate) fn map_func_def_to_entry(node: &Node) -> FunctionEntry {
         log::info!("Generating symbol table for function {}", ident1);
     }
 
+    // member functions also see the constants of their class
+    let outer: SymbolTable = match ident2.and_then(|_| global.find_scope_by_ident(ident1)) {
+        Some(Class(ce)) => visible_constants(ce.table(), global),
+        _ => global.clone(),
+    };
+
     let params: Vec<ParameterEntry> = match node.children()[2].children()[0].val() {
         None => Vec::new(),
         Some(_) => node.children()[2]
             .children()
             .iter()
-            .map(map_func_param_to_entry)
+            .map(|n| map_func_param_to_entry(n, &outer, errors))
             .collect(),
     };
-    let return_ty = map_to_type(&node.children()[3]);
+    let return_ty = map_to_type(&node.children()[3], &outer);
     let ty_signature: (Vec<Type>, Type) = (
         params.iter().map(|p| p.param_type().clone()).collect(),
         return_ty,
@@ -215,14 +428,12 @@ pub(crate) fn map_func_def_to_entry(node: &Node) -> FunctionEntry {
             .map(|p| Scope::FunctionParameter(p))
             .collect(),
     );
-    let body_vars: Vec<VariableEntry> = node.children()[4] //funcbody
-        .children()[0]
-        .children() //vars in var block
-        .iter()
-        .filter(|n| n.val().is_some())
-        .map(map_var_decl_to_entry)
-        .collect();
-    symbol_table.add_scopes(body_vars.into_iter().map(Scope::Variable).collect());
+    map_var_block_to_scopes(
+        &node.children()[4].children()[0], //var block of funcbody
+        &mut symbol_table,
+        &outer,
+        errors,
+    );
     match ident2 {
         None => FunctionEntry::new(ident1, ty_signature, symbol_table, line_num, true),
         Some(ident) => {
@@ -231,45 +442,121 @@ pub(crate) fn map_func_def_to_entry(node: &Node) -> FunctionEntry {
     }
 }
 
+/// Finds the entry of a function definition in `table`. Overloads share an identifier, so they are told apart by their signature.
+pub(crate) fn find_function_for_definition<'a>(
+    function_definition: &Node,
+    table: &'a SymbolTable,
+    global: &SymbolTable,
+) -> Option<&'a FunctionEntry> {
+    // errors in the definition were reported when the symbol table was generated
+    let definition_entry = map_func_def_to_entry(function_definition, global, &mut Vec::new());
+    match table.find_scope_by_scope(&Scope::Function(definition_entry)) {
+        Some(Scope::Function(fe)) => Some(fe),
+        _ => None,
+    }
+}
+
 #[allow(dead_code)]
 /// Maps Main node to a FunctionEntry
-pub(crate) fn map_main_to_func_entry(node: &Node) -> FunctionEntry {
+pub(crate) fn map_main_to_func_entry(
+    node: &Node,
+    global: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> FunctionEntry {
     assert_eq!(node.val(), Some(&NodeVal::Internal(InternalNodeType::Main)));
     assert_eq!(node.children().len(), 1);
     assert_eq!(node.children()[0].children().len(), 2);
-    if node.children()[0].children()[0].children()[0].val() == None {
-        FunctionEntry::new(
-            "main",
-            (Vec::new(), Type::Void),
-            SymbolTable::new(),
-            999,
-            true,
-        )
-    } else {
-        let var_scopes: Vec<Scope> = node.children()[0].children()[0]
-            .children()
-            .iter()
-            .map(|n| Scope::Variable(map_var_decl_to_entry(n)))
-            .collect();
-        FunctionEntry::new(
-            "main",
-            (Vec::new(), Type::Void),
-            SymbolTable::new_from_scopes(var_scopes),
-            999,
-            true,
-        )
+    let mut symbol_table = SymbolTable::new();
+    map_var_block_to_scopes(
+        &node.children()[0].children()[0],
+        &mut symbol_table,
+        global,
+        errors,
+    );
+    FunctionEntry::new("main", (Vec::new(), Type::Void), symbol_table, 999, true)
+}
+
+/// Adds the variables and constants of a VarBlock to a function's table, in order.
+/// Declarations can use the constants declared before them, and those of `outer`.
+pub(crate) fn map_var_block_to_scopes(
+    var_block: &Node,
+    table: &mut SymbolTable,
+    outer: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) {
+    assert_eq!(
+        var_block.val(),
+        Some(&NodeVal::Internal(InternalNodeType::VarBlock))
+    );
+    for decl in var_block.children().iter().filter(|n| n.val().is_some()) {
+        let constants = visible_constants(table, outer);
+        let scope = match decl.val() {
+            Some(NodeVal::Internal(InternalNodeType::ConstDeclaration)) => {
+                Scope::Constant(map_const_decl_to_entry(decl, &constants, errors))
+            }
+            _ => Scope::Variable(map_var_decl_to_entry(decl, &constants, errors)),
+        };
+        table.add_scope(scope);
     }
 }
 
+#[allow(dead_code)]
+/// Maps a ConstDeclaration node to a ConstantEntry, evaluating its value
+pub(crate) fn map_const_decl_to_entry(
+    node: &Node,
+    constants: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> ConstantEntry {
+    assert_eq!(
+        node.val(),
+        Some(&NodeVal::Internal(InternalNodeType::ConstDeclaration))
+    );
+    assert_eq!(node.children().len(), 3);
+    let ty = map_to_type(&node.children()[0], constants);
+    let (ident, line_num) = match node.children()[1].val() {
+        Some(NodeVal::Leaf(t)) => (t.lexeme(), t.line_num()),
+        _ => {
+            panic!()
+        }
+    };
+
+    let value = match evaluate_constant_expr(&node.children()[2], constants) {
+        Ok(_) if ty != Type::Integer && ty != Type::Bool && !ty.is_enum() => {
+            errors.push(SemanticError::InvalidConstant(format!(
+                "Constant {} has type {:?}, only integer, bool and enum constants are supported: line {}",
+                ident, ty, line_num
+            )));
+            0
+        }
+        Ok((value_ty, value)) if value_ty == ty => value,
+        Ok((value_ty, _)) => {
+            errors.push(SemanticError::TypeMistmatch(format!(
+                "Constant {} expected {:?}, but got {:?} instead: line {}",
+                ident, ty, value_ty, line_num
+            )));
+            0
+        }
+        Err(err) => {
+            errors.push(err);
+            0
+        }
+    };
+    ConstantEntry::new(ident, ty, value, line_num)
+}
+
 #[allow(dead_code)]
 /// Maps a VarDeclaration node to a Variable Entry
-pub(crate) fn map_var_decl_to_entry(node: &Node) -> VariableEntry {
+pub(crate) fn map_var_decl_to_entry(
+    node: &Node,
+    constants: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> VariableEntry {
     assert_eq!(
         node.val(),
         Some(&NodeVal::Internal(InternalNodeType::VarDeclaration))
     );
     assert_eq!(node.children().len(), 3);
-    let ty = map_to_type(&node);
+    let ty = map_decl_to_type(node, constants, errors);
     let (ident, line_num) = match node.children()[1].val() {
         None => {
             panic!()
@@ -286,13 +573,17 @@ pub(crate) fn map_var_decl_to_entry(node: &Node) -> VariableEntry {
 
 #[allow(dead_code)]
 /// Maps a FuncParam node to a ParameterEntry
-pub(crate) fn map_func_param_to_entry(node: &Node) -> ParameterEntry {
+pub(crate) fn map_func_param_to_entry(
+    node: &Node,
+    constants: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> ParameterEntry {
     assert_eq!(
         node.val(),
         Some(&NodeVal::Internal(InternalNodeType::FuncParam))
     );
     assert_eq!(node.children().len(), 3);
-    let ty = map_to_type(&node);
+    let ty = map_decl_to_type(node, constants, errors);
     let (ident, line_num) = match node.children()[1].val() {
         None => {
             panic!()
@@ -308,8 +599,8 @@ pub(crate) fn map_func_param_to_entry(node: &Node) -> ParameterEntry {
 }
 
 #[allow(dead_code)]
-/// Extracts type information from a token, or from VarDeclaration/FuncParam
-pub(crate) fn map_to_type(node: &Node) -> symbol_table::Type {
+/// Extracts type information from a token. `visible` holds the enums the type can name.
+pub(crate) fn map_to_type(node: &Node, visible: &SymbolTable) -> symbol_table::Type {
     match node.val() {
         None => {
             panic!()
@@ -319,12 +610,40 @@ pub(crate) fn map_to_type(node: &Node) -> symbol_table::Type {
                 TokenType::IntegerType => symbol_table::Type::Integer,
                 TokenType::FloatType => symbol_table::Type::Float,
                 TokenType::StringType => symbol_table::Type::String,
-                TokenType::Id => symbol_table::Type::Custom(t.lexeme().to_string()),
+                TokenType::BoolType => symbol_table::Type::Bool,
+                TokenType::Id => map_named_type(t.lexeme(), visible),
                 TokenType::Void => return symbol_table::Type::Void,
                 _ => {
                     panic!()
                 }
             },
+            NodeVal::Internal(_) => {
+                panic!()
+            }
+        },
+    }
+}
+
+/// A type named by an identifier: an enum if `visible` holds one with that identifier, a class otherwise
+fn map_named_type(ident: &str, visible: &SymbolTable) -> Type {
+    match visible.find_scope_by_ident(ident) {
+        Some(Enum(_)) => Type::Enum(ident.to_string()),
+        _ => Type::Custom(ident.to_string()),
+    }
+}
+
+#[allow(dead_code)]
+/// Extracts type information from VarDeclaration/FuncParam. Array dimensions can be constants.
+pub(crate) fn map_decl_to_type(
+    node: &Node,
+    constants: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> symbol_table::Type {
+    match node.val() {
+        None => {
+            panic!()
+        }
+        Some(node_val) => match node_val {
             NodeVal::Internal(InternalNodeType::FuncParam)
             | NodeVal::Internal(InternalNodeType::VarDeclaration) => {
                 assert_eq!(node.children().len(), 3);
@@ -341,7 +660,8 @@ pub(crate) fn map_to_type(node: &Node) -> symbol_table::Type {
                             TokenType::IntegerType => symbol_table::Type::Integer,
                             TokenType::FloatType => symbol_table::Type::Float,
                             TokenType::StringType => symbol_table::Type::String,
-                            TokenType::Id => symbol_table::Type::Custom(t.lexeme().to_string()),
+                            TokenType::BoolType => symbol_table::Type::Bool,
+                            TokenType::Id => map_named_type(t.lexeme(), constants),
                             _ => {
                                 panic!()
                             }
@@ -357,8 +677,7 @@ pub(crate) fn map_to_type(node: &Node) -> symbol_table::Type {
                     let array_dim: Vec<u32> = node.children()[2]
                         .children()
                         .iter()
-                        .map(map_to_unsigned)
-                        .filter_map(|o| o)
+                        .map(|n| map_to_unsigned(n, constants, errors))
                         .collect();
                     ty.to_array_type(array_dim)
                 };
@@ -377,6 +696,7 @@ pub(crate) fn map_token_to_type(token: &Token) -> Type {
         TokenType::IntegerLit => Type::Integer,
         TokenType::FloatLit => Type::Float,
         TokenType::StringLit => Type::String,
+        TokenType::BoolLit => Type::Bool,
         _ => {
             panic!()
         }
@@ -384,13 +704,43 @@ pub(crate) fn map_token_to_type(token: &Token) -> Type {
 }
 
 #[allow(dead_code)]
-/// Extracts usize from tokens
-pub(crate) fn map_to_unsigned(node: &Node) -> Option<u32> // e.g. ArrayDim children() are integer tokens
+/// Extracts an array dimension from an integer token or a constant (0 if unsized).
+/// An invalid dimension is reported and treated as unsized.
+pub(crate) fn map_to_unsigned(
+    node: &Node,
+    constants: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> u32 // e.g. ArrayDim children() are integer tokens or constant ids
 {
     match node.val() {
-        None => Some(0),
+        None => 0,
         Some(v) => match v {
-            NodeVal::Leaf(t) => Some(t.lexeme().parse::<u32>().unwrap()),
+            NodeVal::Leaf(t) if t.token_type() == TokenType::Id => {
+                match constants.find_scope_by_ident(t.lexeme()) {
+                    Some(Constant(e)) if *e.const_type() == Type::Integer && e.value() > 0 => {
+                        e.value() as u32
+                    }
+                    Some(Constant(e)) => {
+                        errors.push(SemanticError::InvalidConstant(format!(
+                            "Array dimension {} must be a positive integer, but is {:?} {}: line {}",
+                            t.lexeme(),
+                            e.const_type(),
+                            e.value(),
+                            t.line_num()
+                        )));
+                        0
+                    }
+                    _ => {
+                        errors.push(SemanticError::InvalidConstant(format!(
+                            "Array dimension {} is not a constant: line {}",
+                            t.lexeme(),
+                            t.line_num()
+                        )));
+                        0
+                    }
+                }
+            }
+            NodeVal::Leaf(t) => t.lexeme().parse::<u32>().unwrap(),
             NodeVal::Internal(_) => {
                 panic!()
             }
@@ -419,8 +769,12 @@ pub(crate) fn map_to_visibility(node: &Node) -> Visibility {
 }
 
 #[allow(dead_code)]
-/// Maps a class member to a Variable or Function Scope
-pub(crate) fn map_member_to_scope(node: &Node) -> Scope {
+/// Maps a class member to a Variable, Function or Constant Scope
+pub(crate) fn map_member_to_scope(
+    node: &Node,
+    constants: &SymbolTable,
+    errors: &mut Vec<SemanticError>,
+) -> Scope {
     assert_eq!(
         node.val(),
         Some(&NodeVal::Internal(InternalNodeType::MemberDeclaration))
@@ -444,17 +798,31 @@ pub(crate) fn map_member_to_scope(node: &Node) -> Scope {
                             panic!()
                         }
                         NodeVal::Internal(InternalNodeType::MemberVarDeclaration) => {
-                            let mut var = map_var_decl_to_entry(&node.children()[1].children()[0]);
+                            let mut var = map_var_decl_to_entry(
+                                &node.children()[1].children()[0],
+                                constants,
+                                errors,
+                            );
                             var.set_visibility(visibility);
                             Scope::Variable(var)
                         }
+                        NodeVal::Internal(InternalNodeType::MemberConstDeclaration) => {
+                            let mut constant = map_const_decl_to_entry(
+                                &node.children()[1].children()[0],
+                                constants,
+                                errors,
+                            );
+                            constant.set_visibility(visibility);
+                            Scope::Constant(constant)
+                        }
                         NodeVal::Internal(InternalNodeType::MemberFuncDeclaration) => {
-                            let mut func =
-                                map_func_decl_to_entry(&node.children()[1].children()[0]);
-                            func.set_visibility(visibility);
-                            Scope::Function(map_func_decl_to_entry(
+                            let mut func = map_func_decl_to_entry(
                                 &node.children()[1].children()[0],
-                            ))
+                                constants,
+                                errors,
+                            );
+                            func.set_visibility(visibility);
+                            Scope::Function(func)
                         }
                         _ => {
                             panic!()
@@ -471,16 +839,19 @@ pub(crate) fn map_member_to_scope(node: &Node) -> Scope {
 }
 
 #[allow(dead_code)]
+/// Merges the member function definitions into the tables of their classes.
+/// The ones of classes that aren't in `global_table` are left in `member_funcs`.
 pub(crate) fn merge_member_function_tables(
     global_table: &mut SymbolTable,
     member_funcs: &mut Vec<FunctionEntry>,
 ) -> Vec<SemanticError> {
     let mut errors: Vec<SemanticError> = Vec::new();
+    let mut unmerged: Vec<FunctionEntry> = Vec::new();
 
     for member_func in member_funcs.drain(..) {
         match global_table.find_scope_by_ident_mut(member_func.member_of().unwrap()) {
             None => {
-                panic!()
+                unmerged.push(member_func);
             }
             Some(scope) => match scope {
                 Scope::Class(entry) => {
@@ -498,6 +869,14 @@ pub(crate) fn merge_member_function_tables(
                             global_table.add_scope(member_func_scope);
                         }
                         Some(fscope) => match fscope {
+                            Scope::Function(fentry) if fentry.is_defined() => {
+                                errors.push(SemanticError::MultipleDeclIdent(format!(
+                                    "Member func {}::{} is defined more than once: line {}",
+                                    fentry.member_of().unwrap(),
+                                    fentry.ident(),
+                                    member_func_scope.line_num()
+                                )));
+                            }
                             Scope::Function(fentry) => {
                                 let member_func = match member_func_scope {
                                     Scope::Function(e) => e,
@@ -519,6 +898,7 @@ pub(crate) fn merge_member_function_tables(
             },
         }
     }
+    member_funcs.append(&mut unmerged);
 
     errors
 }
@@ -603,6 +983,34 @@ pub fn get_class_hierarchy_functions<'a>(
     }
 }
 
+/// Returns the method table of a class: the member functions its objects dispatch to.
+/// Inherited functions come first, in declaration order, and a function takes the slot of the one it overrides,
+/// so a function has the same slot in the tables of all the classes inheriting it.
+pub fn get_class_method_table<'a>(
+    class_entry: &'a ClassEntry,
+    global: &'a SymbolTable,
+) -> Vec<&'a FunctionEntry> {
+    let (mut classes, _) = get_ancestors_for_class(class_entry, global);
+    classes.reverse();
+    classes.push(class_entry);
+
+    let mut table: Vec<&FunctionEntry> = Vec::new();
+    for class in classes {
+        for scope in class.table().scopes() {
+            if let Scope::Function(fe) = scope {
+                match table
+                    .iter()
+                    .position(|slot| slot.ident() == fe.ident() && slot.type_sig().0 == fe.type_sig().0)
+                {
+                    Some(slot) => table[slot] = fe,
+                    None => table.push(fe),
+                }
+            }
+        }
+    }
+    table
+}
+
 #[allow(dead_code)]
 pub fn get_class_hierarchy_data_members<'a>(
     class_entry: &'a ClassEntry,
@@ -665,6 +1073,9 @@ impl IntoMarkDownTable for ClassEntry {
                         e.var_type()
                     ));
                 }
+                Constant(e) => {
+                    rows.push(e.md_row());
+                }
                 _ => {}
             }
         }
@@ -673,6 +1084,41 @@ impl IntoMarkDownTable for ClassEntry {
     }
 }
 
+impl IntoMarkDownTable for EnumEntry {
+    fn md_table(&self) -> Vec<String> {
+        let mut rows: Vec<String> = Vec::new();
+
+        rows.push(format!(
+            "Table: {}<a name=\"{}\"></a>",
+            self.ident(),
+            self.ident()
+        ));
+        rows.push(String::from("|\tname\t|\tkind\t|\ttype\t|\tlink\t|"));
+        rows.push(String::from("| --- | --- | --- | --- |"));
+        for (value, enumerator) in self.enumerators().iter().enumerate() {
+            rows.push(format!(
+                "|\t{}\t|\tenumerator\t|\t{} = {}\t|\tX\t|",
+                enumerator,
+                self.ident(),
+                value
+            ));
+        }
+
+        rows
+    }
+}
+
+impl ConstantEntry {
+    fn md_row(&self) -> String {
+        format!(
+            "|\t{}\t|\tconstant\t|\t{:?} = {}\t|\tX\t|",
+            self.ident(),
+            self.const_type(),
+            self.value()
+        )
+    }
+}
+
 impl IntoMarkDownTable for FunctionEntry {
     fn md_table(&self) -> Vec<String> {
         let mut rows: Vec<String> = Vec::new();
@@ -713,6 +1159,9 @@ impl IntoMarkDownTable for FunctionEntry {
                         e.param_type()
                     ));
                 }
+                Constant(e) => {
+                    rows.push(e.md_row());
+                }
                 _ => {}
             }
         }
@@ -721,6 +1170,17 @@ impl IntoMarkDownTable for FunctionEntry {
     }
 }
 
+/// Whether a constant is one of the enumerators of its enum
+fn is_enumerator(constant: &ConstantEntry, global: &SymbolTable) -> bool {
+    match constant.const_type() {
+        Type::Enum(ident) => match global.find_scope_by_ident(ident) {
+            Some(Enum(e)) => e.enumerators().iter().any(|en| en == constant.ident()),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 #[allow(dead_code)]
 pub fn serialize_symbol_table_to_file(global: &SymbolTable, file_name: &str) -> io::Result<()> {
     let file = OpenOptions::new()
@@ -730,8 +1190,45 @@ pub fn serialize_symbol_table_to_file(global: &SymbolTable, file_name: &str) ->
         .open(format!("{}.outsymboltables.md", file_name))?;
     let mut buf_writer = BufWriter::new(file);
 
+    let global_rows: Vec<String> = global
+        .scopes()
+        .iter()
+        .filter_map(|scope| match scope {
+            // enumerators are listed with their enum
+            Constant(e) if is_enumerator(e, global) => None,
+            Constant(e) => Some(e.md_row()),
+            Enum(e) => Some(format!(
+                "|\t{}\t|\tenum\t|\t{}\t|\t[table](#{})\t|",
+                e.ident(),
+                e.ident(),
+                e.ident()
+            )),
+            Variable(e) => Some(format!(
+                "|\t{}\t|\tvariable\t|\t{:?}\t|\tX\t|",
+                e.ident(),
+                e.var_type()
+            )),
+            _ => None,
+        })
+        .collect();
+    if !global_rows.is_empty() {
+        buf_writer.write_all("Table: global<a name=\"global\"></a>\n".as_bytes())?;
+        buf_writer.write_all("|\tname\t|\tkind\t|\ttype\t|\tlink\t|\n".as_bytes())?;
+        buf_writer.write_all("| --- | --- | --- | --- |\n".as_bytes())?;
+        for row in global_rows {
+            buf_writer.write_all(format!("{}\n", row).as_bytes())?;
+        }
+        buf_writer.write_all("\n____\n".as_bytes())?;
+    }
+
     for top_scope in global.scopes() {
         match top_scope {
+            Constant(_) | Variable(_) => continue,
+            Enum(e) => {
+                for row in e.md_table() {
+                    buf_writer.write_all(format!("{}\n", row).as_bytes())?;
+                }
+            }
             Class(e) => {
                 for row in e.md_table() {
                     buf_writer.write(format!("{}\n", row).as_bytes())?;
@@ -787,4 +1284,57 @@ pub fn write_semantic_error_to_file(errors: Vec<SemanticError>, file_name: &str)
 
     buf_writer.flush()?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Writes the semantic errors of a program made of several files, each one with the name of its file
+pub fn write_program_semantic_errors_to_file(
+    errors: &[(FileId, SemanticError)],
+    modules: &[Module],
+    file_name: &str,
+) -> io::Result<()> {
+    log::info!("Writing semantic errors to file");
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(format!("{}.outsemanticerrors", file_name))?;
+    let mut buf_writer = BufWriter::new(file);
+
+    for (file_id, error) in errors {
+        let source = modules
+            .iter()
+            .find(|module| module.file_id() == *file_id)
+            .map_or_else(String::new, |module| module.file_name());
+        let severity = match error {
+            SemanticError::Warning(_) => "WARNING",
+            _ => "ERROR",
+        };
+        buf_writer.write_all(
+            format!("{}: {}: {:?}{}", severity, source, error, LINE_ENDINGS).as_bytes(),
+        )?;
+    }
+
+    buf_writer.flush()?;
+    Ok(())
+}
+
+/// Writes the errors of the imported files that couldn't be loaded. The program isn't checked in that case,
+/// so they take the place of its semantic errors.
+pub fn write_module_errors_to_file(errors: &[ModuleError], file_name: &str) -> io::Result<()> {
+    log::info!("Writing module errors to file");
+
+    let file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(format!("{}.outsemanticerrors", file_name))?;
+    let mut buf_writer = BufWriter::new(file);
+
+    for error in errors {
+        buf_writer.write_all(format!("ERROR: {}{}", error, LINE_ENDINGS).as_bytes())?;
+    }
+
+    buf_writer.flush()?;
+    Ok(())
+}
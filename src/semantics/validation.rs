@@ -1,15 +1,30 @@
 use crate::lexer::token::{Token, TokenType};
 use crate::parser::ast::{InternalNodeType, Node, NodeVal};
 use crate::semantics::checking::SemanticError;
-use crate::semantics::symbol_table::Type::{Float, Integer};
-use crate::semantics::symbol_table::{FunctionEntry, Scope, SymbolTable, Type};
-use crate::semantics::utils::{get_class_hierarchy_functions, map_token_to_type};
+use crate::semantics::evaluation::{evaluate_case_label, line_of};
+use crate::semantics::symbol_table::Type::{Bool, Integer};
+use crate::semantics::symbol_table::{ClassEntry, FunctionEntry, Scope, SymbolTable, Type};
+use crate::semantics::utils::{
+    find_constant, find_variable, get_class_hierarchy_data_members, get_class_hierarchy_functions,
+    map_token_to_type,
+};
+
+/// The statements enclosing the one being validated, which tell where break and continue can be used
+#[derive(Debug, Default, Copy, Clone)]
+pub(crate) struct Enclosing {
+    /// In the body of a loop: break and continue can be used
+    pub in_loop: bool,
+    /// In a case of a switch statement: break can be used
+    pub in_switch: bool,
+}
 
+/// Validates a statement
 #[allow(dead_code)]
 pub(crate) fn validate_statement(
     statement: &Node,
     function_entry: &FunctionEntry,
     global: &SymbolTable,
+    enclosing: Enclosing,
 ) -> Result<(), SemanticError> {
     match statement.val() {
         Some(NodeVal::Internal(InternalNodeType::GenericStatement)) => {
@@ -35,10 +50,16 @@ pub(crate) fn validate_statement(
             }
         }
         Some(NodeVal::Internal(InternalNodeType::IfStatement)) => {
-            return validate_if_statement(statement, function_entry, global);
+            return validate_if_statement(statement, function_entry, global, enclosing);
         }
         Some(NodeVal::Internal(InternalNodeType::WhileStatement)) => {
-            return validate_while_statement(statement, function_entry, global);
+            return validate_while_statement(statement, function_entry, global, enclosing);
+        }
+        Some(NodeVal::Internal(InternalNodeType::ForStatement)) => {
+            return validate_for_statement(statement, function_entry, global, enclosing);
+        }
+        Some(NodeVal::Internal(InternalNodeType::SwitchStatement)) => {
+            return validate_switch_statement(statement, function_entry, global, enclosing);
         }
         Some(NodeVal::Internal(InternalNodeType::ReadStatement)) => {
             return validate_read_statement(statement, function_entry, global);
@@ -49,9 +70,22 @@ pub(crate) fn validate_statement(
         Some(NodeVal::Internal(InternalNodeType::ReturnStatement)) => {
             return validate_return_statement(statement, function_entry, global);
         }
+        Some(NodeVal::Internal(InternalNodeType::BreakStatement))
+            if !enclosing.in_loop && !enclosing.in_switch =>
+        {
+            return Err(SemanticError::NotInLoop(format!(
+                "break statement outside of a loop or switch in function {}",
+                function_entry.ident()
+            )));
+        }
+        Some(NodeVal::Internal(InternalNodeType::ContinueStatement)) if !enclosing.in_loop => {
+            return Err(SemanticError::NotInLoop(format!(
+                "continue statement outside of a loop in function {}",
+                function_entry.ident()
+            )));
+        }
         Some(NodeVal::Internal(InternalNodeType::BreakStatement))
         | Some(NodeVal::Internal(InternalNodeType::ContinueStatement)) => {
-            log::error!("Break | Continue semantic checking: NOT IMPLEMENTED");
             return Ok(());
         }
         None => {
@@ -77,7 +111,17 @@ fn validate_assignment(
         Some(NodeVal::Internal(InternalNodeType::DotOp)) => {
             validate_dot_operator(lhs, function_entry, global)
         }
-        Some(NodeVal::Leaf(token)) => validate_ident(token, lhs, function_entry, global),
+        Some(NodeVal::Leaf(token)) => {
+            if let Some(ce) = find_constant(token.lexeme(), function_entry, global) {
+                return Err(SemanticError::ConstantAssignment(format!(
+                    "Cannot assign to constant {} declared on line {}: line {}",
+                    ce.ident(),
+                    ce.line_num(),
+                    token.line_num()
+                )));
+            }
+            validate_ident(token, lhs, function_entry, global)
+        }
         _ => {
             panic!()
         }
@@ -94,7 +138,8 @@ fn validate_assignment(
     }
     let rhs_ty = rhs_ty.unwrap();
 
-    if lhs_ty == rhs_ty {
+    if promoted_type(&lhs_ty, &rhs_ty) == Some(lhs_ty.clone()) {
+        // an integer is promoted when assigned to a float
         return Ok(());
     } else {
         return Err(SemanticError::TypeMistmatch(format!(
@@ -103,85 +148,83 @@ fn validate_assignment(
         )));
     }
 }
-/// Validates a function call statement (always free function ?)
+/// Validates a function call statement
 fn validate_func_call_statement(
     func_id: &Token,
     func_call_statement: &Node,
     function_entry: &FunctionEntry,
     global: &SymbolTable,
 ) -> Result<(), SemanticError> {
-    let mut func_call_errs: Vec<SemanticError> = Vec::new();
-    // member function call if statement is within member function
-    if function_entry.member_of().is_some() {
-        let class_ident = function_entry.member_of().unwrap();
-        if let Some(Scope::Class(ce)) = global.find_scope_by_ident(class_ident) {
-            let (functions, mut errors) = get_class_hierarchy_functions(ce, global);
-            if errors.len() > 0 {
-                return Err(errors.pop().unwrap());
-            } else {
-                for fe in functions {
-                    let func_call_res = validate_function_call(
-                        func_id,
-                        &func_call_statement.children()[0],
-                        fe,
-                        function_entry,
-                        global,
-                    );
-                    if func_call_res.is_ok() {
-                        return Ok(());
-                    } else {
-                        func_call_errs.push(func_call_res.unwrap_err());
-                    }
-                }
-            }
+    let candidates = function_candidates(func_id.lexeme(), function_entry, global)?;
+    if candidates.is_empty() {
+        return Err(SemanticError::FunctionNotFound(format!(
+            "No function {} found: line {}",
+            func_id.lexeme(),
+            func_id.line_num()
+        )));
+    }
+    resolve_function_call(
+        func_id,
+        &func_call_statement.children()[0],
+        &candidates,
+        function_entry,
+        global,
+    )
+    .and(Ok(()))
+}
+
+/// Returns the functions a call to `ident` can refer to from within a function.
+/// Inside a member function, the functions of its class hierarchy come first, so they hide free functions with the same identifier.
+pub(crate) fn function_candidates<'a>(
+    ident: &str,
+    function_entry: &FunctionEntry,
+    global: &'a SymbolTable,
+) -> Result<Vec<&'a FunctionEntry>, SemanticError> {
+    if let Some(Scope::Class(ce)) = function_entry
+        .member_of()
+        .and_then(|class_ident| global.find_scope_by_ident(class_ident))
+    {
+        let (functions, mut errors) = get_class_hierarchy_functions(ce, global);
+        if !errors.is_empty() {
+            return Err(errors.pop().unwrap());
         }
-    } else {
-        // free function call
-        for scope in global.find_all_scopes_by_ident(func_id.lexeme()) {
-            if let Scope::Function(fe) = scope {
-                if fe.ident() == func_id.lexeme() {
-                    let func_call_res = validate_function_call(
-                        func_id,
-                        &func_call_statement.children()[0],
-                        fe,
-                        function_entry,
-                        global,
-                    );
-                    if func_call_res.is_ok() {
-                        return Ok(());
-                    } else {
-                        func_call_errs.push(func_call_res.unwrap_err());
-                    }
-                }
-            }
+        let member_functions: Vec<&FunctionEntry> = functions
+            .into_iter()
+            .filter(|fe| fe.ident() == ident)
+            .collect();
+        if !member_functions.is_empty() {
+            return Ok(member_functions);
         }
     }
 
-    return Err(func_call_errs.pop().unwrap());
+    Ok(global
+        .find_all_scopes_by_ident(ident)
+        .into_iter()
+        .filter_map(|scope| match scope {
+            Scope::Function(fe) => Some(fe),
+            _ => None,
+        })
+        .collect())
 }
 
-/// Validates if a function is called correctly (is defined, correct num of params, correct type of params) and returns the return type of the call.
-fn validate_function_call(
+/// Resolves a call to one of the overloads in `candidates` and validates its arguments.
+/// The candidate whose parameters need the fewest conversions wins; a tie between the best candidates is ambiguous.
+/// Candidates are expected most derived class first, a function hides the inherited ones with the same parameters.
+pub(crate) fn resolve_function_call<'a>(
     ident_token: &Token,
     func_call_params: &Node,
-    called_function: &FunctionEntry,
+    candidates: &[&'a FunctionEntry],
     function_entry: &FunctionEntry,
     global: &SymbolTable,
-) -> Result<Type, SemanticError> {
+) -> Result<&'a FunctionEntry, SemanticError> {
     log::warn!("validating function call");
     log::warn!(
         "{}({} params)",
         ident_token.lexeme(),
         func_call_params.children().len()
     );
-    if called_function == function_entry {
-        return Err(SemanticError::RecursionNotSupported(format!(
-            "Recursion not supported: line {}",
-            ident_token.line_num()
-        )));
-    }
 
-    let mut param_types: Vec<Type> = Vec::new();
+    let mut arg_types: Vec<Type> = Vec::new();
     for param in func_call_params.children().iter() {
         if param.val().is_none()
         // no params
@@ -192,29 +235,110 @@ fn validate_function_call(
                 param.val(),
                 Some(&NodeVal::Internal(InternalNodeType::Expr))
             );
-            let res: Result<Type, SemanticError> = validate_expr(param, function_entry, global);
-            if res.is_err() {
-                return res;
-            } else {
-                param_types.push(res.unwrap());
-            }
+            arg_types.push(validate_expr(param, function_entry, global)?);
         }
     }
 
-    log::warn!("called with : {:?}", param_types);
-    log::warn!("expected : {:?}", called_function.type_sig().0);
+    log::warn!("called with : {:?}", arg_types);
 
-    for (idx, (provided, expected)) in param_types
+    let mut visible: Vec<&FunctionEntry> = Vec::new();
+    for candidate in candidates {
+        if !visible
+            .iter()
+            .any(|fe| fe.type_sig().0 == candidate.type_sig().0)
+        {
+            visible.push(candidate);
+        }
+    }
+
+    let mut matches: Vec<(&FunctionEntry, u32)> = visible
+        .into_iter()
+        .filter_map(|fe| call_cost(&arg_types, &fe.type_sig().0).map(|cost| (fe, cost)))
+        .collect();
+    let best_cost = match matches.iter().map(|(_, cost)| *cost).min() {
+        Some(cost) => cost,
+        None => {
+            return Err(SemanticError::InvalidParameters(format!(
+                "No overload of {} matches {}, candidates are {}: line {}",
+                ident_token.lexeme(),
+                signature(ident_token.lexeme(), &arg_types),
+                candidates
+                    .iter()
+                    .map(|fe| signature(fe.ident(), &fe.type_sig().0))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                ident_token.line_num()
+            )));
+        }
+    };
+    matches.retain(|(_, cost)| *cost == best_cost);
+    if matches.len() > 1 {
+        return Err(SemanticError::AmbiguousCall(format!(
+            "Call {} is ambiguous between {}: line {}",
+            signature(ident_token.lexeme(), &arg_types),
+            matches
+                .iter()
+                .map(|(fe, _)| signature(fe.ident(), &fe.type_sig().0))
+                .collect::<Vec<String>>()
+                .join(" and "),
+            ident_token.line_num()
+        )));
+    }
+
+    Ok(matches[0].0)
+}
+
+/// Number of conversions needed to pass the arguments to the parameters, None if they can't be passed
+fn call_cost(arg_types: &[Type], param_types: &[Type]) -> Option<u32> {
+    if arg_types.len() != param_types.len() {
+        return None;
+    }
+    arg_types
         .iter()
-        .zip(called_function.type_sig().0.iter())
-        .enumerate()
-    {
-        if provided != expected {
-            return Err(SemanticError::InvalidParameters(format!("Invalid parameters at index {} used for function {}: line {}. Expected {:?}, but got {:?}", idx, ident_token.lexeme(), ident_token.line_num(), expected, provided)));
+        .zip(param_types.iter())
+        .map(|(provided, expected)| conversion_cost(provided, expected))
+        .sum()
+}
+
+/// An exact match costs nothing. An integer promoted to a float parameter, or an array passed to an unsized (`[]`) dimension
+/// of the same element type, costs one conversion
+fn conversion_cost(provided: &Type, expected: &Type) -> Option<u32> {
+    let unsized_match = |provided: &Vec<u32>, expected: &Vec<u32>| {
+        if provided.len() == expected.len()
+            && provided
+                .iter()
+                .zip(expected.iter())
+                .all(|(p, e)| *e == 0 || p == e)
+        {
+            Some(1)
+        } else {
+            None
         }
+    };
+    match (provided, expected) {
+        _ if provided == expected => Some(0),
+        (Integer, Type::Float) => Some(1),
+        (Type::IntegerArray(p), Type::IntegerArray(e))
+        | (Type::FloatArray(p), Type::FloatArray(e))
+        | (Type::StringArray(p), Type::StringArray(e))
+        | (Type::BoolArray(p), Type::BoolArray(e)) => unsized_match(p, e),
+        (Type::CustomArray(pc, p), Type::CustomArray(ec, e)) if pc == ec => unsized_match(p, e),
+        (Type::EnumArray(pc, p), Type::EnumArray(ec, e)) if pc == ec => unsized_match(p, e),
+        _ => None,
     }
+}
 
-    return Ok(called_function.type_sig().1.clone());
+/// Formats a call signature for diagnostics, e.g. f(integer, float[3])
+fn signature(ident: &str, types: &[Type]) -> String {
+    format!(
+        "{}({})",
+        ident,
+        types
+            .iter()
+            .map(|ty| format!("{:?}", ty))
+            .collect::<Vec<String>>()
+            .join(", ")
+    )
 }
 
 /// Validates Indice -> idx should be arith expr returning an integer
@@ -243,31 +367,20 @@ fn validate_indices(
     Ok(())
 }
 
-/// Validate an If statement -> {rel expr, stat block, stat block}
+/// Validate an If statement -> {expr, stat block, stat block}
 fn validate_if_statement(
     if_statement: &Node,
     function_entry: &FunctionEntry,
     global: &SymbolTable,
+    enclosing: Enclosing,
 ) -> Result<(), SemanticError> {
     log::warn!("validating if statement");
-    // validate rel expr
-    let rel_expr_res: Result<Type, SemanticError> =
-        validate_rel_expr(&if_statement.children()[0], function_entry, global);
-    if rel_expr_res.is_err() {
-        return Err(rel_expr_res.unwrap_err());
-    }
-    let rel_expr_res = rel_expr_res.unwrap();
-    if rel_expr_res != Integer {
-        return Err(SemanticError::TypeMistmatch(format!(
-            "If condition expected integer. Got {:?} instead: in {}",
-            rel_expr_res,
-            function_entry.ident()
-        )));
-    }
+    // validate condition
+    validate_condition(&if_statement.children()[0], "If", function_entry, global)?;
 
     // validate statblock 1 (then)
     for statement in if_statement.children()[1].children() {
-        let res = validate_statement(statement, function_entry, global);
+        let res = validate_statement(statement, function_entry, global, enclosing);
         if res.is_err() {
             return res;
         } else {
@@ -277,7 +390,7 @@ fn validate_if_statement(
 
     // validate statblock 2 (else)
     for statement in if_statement.children()[2].children() {
-        let res = validate_statement(statement, function_entry, global);
+        let res = validate_statement(statement, function_entry, global, enclosing);
         if res.is_err() {
             return res;
         } else {
@@ -288,31 +401,33 @@ fn validate_if_statement(
     Ok(())
 }
 
-/// Validates a While statement -> {rel expr, stat block)
+/// Validates a While statement -> {expr, stat block)
 fn validate_while_statement(
     while_statement: &Node,
     function_entry: &FunctionEntry,
     global: &SymbolTable,
+    enclosing: Enclosing,
 ) -> Result<(), SemanticError> {
     log::warn!("validating while statement");
-    // validate rel expr
-    let rel_expr_res: Result<Type, SemanticError> =
-        validate_rel_expr(&while_statement.children()[0], function_entry, global);
-    if rel_expr_res.is_err() {
-        return Err(rel_expr_res.unwrap_err());
-    }
-    let rel_expr_res = rel_expr_res.unwrap();
-    if rel_expr_res != Integer {
-        return Err(SemanticError::TypeMistmatch(format!(
-            "While condition expected integer. Got {:?} instead: in {}",
-            rel_expr_res,
-            function_entry.ident()
-        )));
-    }
+    // validate condition
+    validate_condition(
+        &while_statement.children()[0],
+        "While",
+        function_entry,
+        global,
+    )?;
 
     // validate statblock
     for statement in while_statement.children()[1].children() {
-        let res = validate_statement(statement, function_entry, global);
+        let res = validate_statement(
+            statement,
+            function_entry,
+            global,
+            Enclosing {
+                in_loop: true,
+                ..enclosing
+            },
+        );
         if res.is_err() {
             return res;
         } else {
@@ -323,6 +438,109 @@ fn validate_while_statement(
     Ok(())
 }
 
+/// Validates a For statement -> {assignment, expr, assignment, stat block}
+fn validate_for_statement(
+    for_statement: &Node,
+    function_entry: &FunctionEntry,
+    global: &SymbolTable,
+    enclosing: Enclosing,
+) -> Result<(), SemanticError> {
+    // validate initializer
+    validate_assignment(&for_statement.children()[0], function_entry, global)?;
+
+    // validate condition
+    validate_condition(&for_statement.children()[1], "For", function_entry, global)?;
+
+    // validate step
+    validate_assignment(&for_statement.children()[2], function_entry, global)?;
+
+    // validate statblock
+    for statement in for_statement.children()[3].children() {
+        validate_statement(
+            statement,
+            function_entry,
+            global,
+            Enclosing {
+                in_loop: true,
+                ..enclosing
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Validates a Switch statement -> {expr, case | default case, ...}.
+/// The expression is an integer, and the case labels are distinct integer constants.
+fn validate_switch_statement(
+    switch_statement: &Node,
+    function_entry: &FunctionEntry,
+    global: &SymbolTable,
+    enclosing: Enclosing,
+) -> Result<(), SemanticError> {
+    let expr = &switch_statement.children()[0];
+    let expr_type = match validate_expr(expr, function_entry, global)? {
+        ty @ Integer | ty @ Type::Enum(_) => ty,
+        other => {
+            return Err(SemanticError::TypeMistmatch(format!(
+                "Switch expression expected integer or enum. Got {:?} instead: line {}",
+                other,
+                line_of(expr)
+            )))
+        }
+    };
+
+    let mut case_values: Vec<i32> = Vec::new();
+    let mut has_default = false;
+    for case in &switch_statement.children()[1..] {
+        match case.val() {
+            Some(NodeVal::Internal(InternalNodeType::Case)) => {
+                let (label_type, value) = evaluate_case_label(case, function_entry, global)?;
+                if label_type != expr_type {
+                    return Err(SemanticError::TypeMistmatch(format!(
+                        "Case label expected {:?}, but got {:?} instead: line {}",
+                        expr_type,
+                        label_type,
+                        line_of(case)
+                    )));
+                }
+                if case_values.contains(&value) {
+                    return Err(SemanticError::DuplicateCase(format!(
+                        "Duplicate case {} in switch: line {}",
+                        value,
+                        line_of(case)
+                    )));
+                }
+                case_values.push(value);
+            }
+            _ => {
+                if has_default {
+                    return Err(SemanticError::DuplicateCase(format!(
+                        "Duplicate default case in switch in function {}",
+                        function_entry.ident()
+                    )));
+                }
+                has_default = true;
+            }
+        }
+
+        // validate the statements of the case
+        for statement in case.children().last().unwrap().children() {
+            validate_statement(
+                statement,
+                function_entry,
+                global,
+                Enclosing {
+                    in_switch: true,
+                    ..enclosing
+                },
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
 fn validate_read_statement(
     _read_statement: &Node,
     _function_entry: &FunctionEntry,
@@ -369,7 +587,9 @@ fn validate_return_statement(
     }
     let res = res.unwrap();
 
-    return if res != function_entry.type_sig().1 {
+    // an integer is promoted when returned from a float function
+    let expected = &function_entry.type_sig().1;
+    return if promoted_type(&res, expected).as_ref() != Some(expected) {
         Err(SemanticError::TypeMistmatch(format!(
             "Return Statement: function {} expected {:?}, but got {:?} instead",
             function_entry.ident(),
@@ -387,25 +607,9 @@ fn validate_dot_operator(
     global: &SymbolTable,
 ) -> Result<Type, SemanticError> {
     log::warn!("validating dot operator");
-    let lhs = match dot_op.children()[0].val() {
-        Some(NodeVal::Leaf(token)) => {
-            // We have an identifier that should be present in the current function scope
-            validate_ident(token, &dot_op.children()[0], function_entry, global)
-        }
-        Some(NodeVal::Internal(InternalNodeType::DotOp)) => {
-            validate_dot_operator(&dot_op.children()[0], function_entry, global)
-        }
-        _ => {
-            panic!()
-        }
-    };
-
-    if lhs.is_err() {
-        return lhs;
-    }
-    let lhs = lhs.unwrap();
+    let lhs = validate_dot_operand(&dot_op.children()[0], function_entry, global)?;
 
-    let rhs_token = match dot_op.children()[0].val() {
+    let rhs_token = match dot_op.children()[1].val() {
         Some(NodeVal::Leaf(token)) => token,
         _ => {
             panic!()
@@ -415,104 +619,103 @@ fn validate_dot_operator(
     match lhs {
         Type::Custom(class_ident) => {
             if let Some(Scope::Class(ce)) = global.find_scope_by_ident(&class_ident) {
-                match ce.table().find_scope_by_ident(rhs_token.lexeme()) {
-                    Some(Scope::Function(_)) => {
-                        if dot_op.children().len() < 3 {
+                let candidates = member_function_candidates(ce, rhs_token.lexeme(), global);
+                if !candidates.is_empty() {
+                    if dot_op.children().len() < 3 {
+                        return Err(SemanticError::InvalidParameters(format!(
+                            "No function matched parameters for {}: line {}",
+                            rhs_token.lexeme(),
+                            rhs_token.line_num()
+                        )));
+                    } else if dot_op.children().len() == 3 {
+                        if dot_op.children()[2].val()
+                            != Some(&NodeVal::Internal(InternalNodeType::FuncCallParams))
+                        {
                             return Err(SemanticError::InvalidParameters(format!(
                                 "No function matched parameters for {}: line {}",
                                 rhs_token.lexeme(),
                                 rhs_token.line_num()
                             )));
-                        } else if dot_op.children().len() == 3 {
-                            if dot_op.children()[2].val()
-                                != Some(&NodeVal::Internal(InternalNodeType::FuncCallParams))
-                            {
-                                return Err(SemanticError::InvalidParameters(format!(
-                                    "No function matched parameters for {}: line {}",
-                                    rhs_token.lexeme(),
-                                    rhs_token.line_num()
-                                )));
-                            }
-                        } else {
-                            return Err(SemanticError::NotIndexable(format!(
-                                "Function {} is not indexable: line {}",
-                                rhs_token.lexeme(),
-                                rhs_token.line_num()
-                            )));
                         }
-
-                        let (function_candidates, _errors) =
-                            get_class_hierarchy_functions(ce, global);
-                        for function in function_candidates {
-                            let res = validate_function_call(
-                                rhs_token,
-                                &dot_op.children()[2],
-                                function,
-                                function_entry,
-                                global,
-                            );
-                            if res.is_ok() {
-                                return Ok(function.type_sig().1.clone());
-                            }
-                        }
-                        return Err(SemanticError::InvalidParameters(format!(
-                            "No function matched parameters for {}: line {}",
+                    } else {
+                        return Err(SemanticError::NotIndexable(format!(
+                            "Function {} is not indexable: line {}",
                             rhs_token.lexeme(),
                             rhs_token.line_num()
                         )));
                     }
-                    Some(Scope::Variable(cve)) => {
-                        return if dot_op.children().len() < 3 {
-                            Ok(cve.var_type().clone())
-                        } else if dot_op.children()[2].val()
-                            == Some(&NodeVal::Internal(InternalNodeType::FuncCallParams))
-                        {
-                            Err(SemanticError::NotCallable(format!(
-                                "Data member {} is not callabled: line {}",
-                                rhs_token.lexeme(),
-                                rhs_token.line_num()
-                            )))
-                        } else {
-                            let indices: &Vec<Node> = &dot_op.children()[2..].into();
-                            let indices_res =
-                                validate_indices(rhs_token, &indices, function_entry, global);
-                            if indices_res.is_err() {
-                                return Err(indices_res.unwrap_err());
-                            }
 
-                            if indices.len() > 0 {
-                                match cve.var_type() {
-                                    Type::IntegerArray(dim)
-                                    | Type::FloatArray(dim)
-                                    | Type::StringArray(dim)
-                                    | Type::CustomArray(_, dim) => {
-                                        if dim.len() < indices.len() {
-                                            Err(SemanticError::TooManyIndices(format!(
+                    resolve_function_call(
+                        rhs_token,
+                        &dot_op.children()[2],
+                        &candidates,
+                        function_entry,
+                        global,
+                    )
+                    .map(|fe| fe.type_sig().1.clone())
+                } else {
+                    // data members of the object, inherited ones included
+                    let (data_members, _errors) = get_class_hierarchy_data_members(ce, global);
+                    match data_members
+                        .into_iter()
+                        .find(|cve| cve.ident() == rhs_token.lexeme())
+                    {
+                        Some(cve) => {
+                            if dot_op.children().len() < 3 {
+                                Ok(cve.var_type().clone())
+                            } else if dot_op.children()[2].val()
+                                == Some(&NodeVal::Internal(InternalNodeType::FuncCallParams))
+                            {
+                                Err(SemanticError::NotCallable(format!(
+                                    "Data member {} is not callabled: line {}",
+                                    rhs_token.lexeme(),
+                                    rhs_token.line_num()
+                                )))
+                            } else {
+                                let indices: &Vec<Node> = &dot_op.children()[2..].into();
+                                validate_indices(rhs_token, indices, function_entry, global)?;
+
+                                if !indices.is_empty() {
+                                    match cve.var_type() {
+                                        Type::IntegerArray(dim)
+                                        | Type::FloatArray(dim)
+                                        | Type::StringArray(dim)
+                                        | Type::BoolArray(dim)
+                                        | Type::CustomArray(_, dim)
+                                        | Type::EnumArray(_, dim) => {
+                                            if dim.len() < indices.len() {
+                                                Err(SemanticError::TooManyIndices(format!(
                                                 "Found {} indices, but dimension is {}: line {}",
                                                 indices.len(),
                                                 dim.len(),
                                                 rhs_token.line_num()
                                             )))
-                                        } else if dim.len() == indices.len() {
-                                            Ok(cve.var_type().to_simple_type())
-                                        } else {
-                                            Ok(cve
-                                                .var_type()
-                                                .to_array_type(dim[indices.len()..].to_vec()))
+                                            } else if dim.len() == indices.len() {
+                                                Ok(cve.var_type().to_simple_type())
+                                            } else {
+                                                Ok(cve
+                                                    .var_type()
+                                                    .to_array_type(dim[indices.len()..].to_vec()))
+                                            }
                                         }
+                                        ty => Err(SemanticError::NotIndexable(format!(
+                                            "Cannot index variable of type {:?}: line {}",
+                                            ty,
+                                            rhs_token.line_num()
+                                        ))),
                                     }
-                                    ty => Err(SemanticError::NotIndexable(format!(
-                                        "Cannot index variable of type {:?}: line {}",
-                                        ty,
-                                        rhs_token.line_num()
-                                    ))),
+                                } else {
+                                    Ok(cve.var_type().clone())
                                 }
-                            } else {
-                                Ok(cve.var_type().clone())
                             }
                         }
+                        _ => Err(SemanticError::UndeclaredVariable(format!(
+                            "No member {} in class {}: line {}",
+                            rhs_token.lexeme(),
+                            class_ident,
+                            rhs_token.line_num()
+                        ))),
                     }
-                    _ => panic!(),
                 }
             } else {
                 return Err(SemanticError::UndeclaredClass(format!(
@@ -532,17 +735,60 @@ fn validate_dot_operator(
     }
 }
 
+/// Validates the left hand side of a dot operator, returning its type
+pub(crate) fn validate_dot_operand(
+    operand: &Node,
+    function_entry: &FunctionEntry,
+    global: &SymbolTable,
+) -> Result<Type, SemanticError> {
+    match operand.val() {
+        Some(NodeVal::Leaf(token)) => {
+            // We have an identifier that should be present in the current function scope
+            validate_ident(token, operand, function_entry, global)
+        }
+        Some(NodeVal::Internal(InternalNodeType::DotOp)) => {
+            validate_dot_operator(operand, function_entry, global)
+        }
+        _ => {
+            panic!()
+        }
+    }
+}
+
+/// Returns the member functions named `ident` of a class, including the inherited ones, most derived first
+pub(crate) fn member_function_candidates<'a>(
+    class_entry: &'a ClassEntry,
+    ident: &str,
+    global: &'a SymbolTable,
+) -> Vec<&'a FunctionEntry> {
+    let (functions, _errors) = get_class_hierarchy_functions(class_entry, global);
+    functions
+        .into_iter()
+        .filter(|fe| fe.ident() == ident)
+        .collect()
+}
+
 /// Validates an Expr
-fn validate_expr(
+pub(crate) fn validate_expr(
     expr: &Node,
     function_entry: &FunctionEntry,
     global: &SymbolTable,
 ) -> Result<Type, SemanticError> {
     /*
-    <expr> ::= #MakeFamilyRootNode("Expr") <arithExpr> <exprAmb1>
+    <expr> ::= #MakeFamilyRootNode("Expr") <conjunction> <rightrec-expr> #AddChild
+
+    <rightrec-expr> ::= #MakeFamilyRootNode("Or") 'or' <conjunction> #MakeRelativeOperation <rightrec-expr>
+    <rightrec-expr> ::= EPSILON
+
+    <conjunction> ::= <comparison> <rightrec-conjunction>
+
+    <rightrec-conjunction> ::= #MakeFamilyRootNode("And") 'and' <comparison> #MakeRelativeOperation <rightrec-conjunction>
+    <rightrec-conjunction> ::= EPSILON
 
-    <exprAmb1> ::= <relOp> <arithExpr> #MakeRelativeOperation #AddChild
-    <exprAmb1> ::= EPSILON #AddChild
+    <comparison> ::= <arithExpr> <exprAmb1>
+
+    <exprAmb1> ::= <relOp> <arithExpr> #MakeRelativeOperation
+    <exprAmb1> ::= EPSILON
 
     <arithExpr> ::= #MakeFamilyRootNode("ArithExpr") <term> <rightrec-arithExpr> #AddChild
 
@@ -554,89 +800,98 @@ fn validate_expr(
     <relOp> ::= #MakeFamilyRootNode("GreaterEqualThan") 'geq'
      */
     log::warn!("validating expr");
-    let res: Result<Type, SemanticError> = match expr.children()[0].val() {
+    validate_expr_operand(&expr.children()[0], function_entry, global)
+}
+
+/// Validates the child of an Expr, or an operand of a logical operator
+fn validate_expr_operand(
+    operand: &Node,
+    function_entry: &FunctionEntry,
+    global: &SymbolTable,
+) -> Result<Type, SemanticError> {
+    let res: Result<Type, SemanticError> = match operand.val() {
+        Some(NodeVal::Internal(InternalNodeType::And))
+        | Some(NodeVal::Internal(InternalNodeType::Or)) => {
+            let lhs_res = validate_expr_operand(&operand.children()[0], function_entry, global)?;
+            let rhs_res = validate_expr_operand(&operand.children()[1], function_entry, global)?;
+            validate_operand_type(operand, lhs_res)?;
+            validate_operand_type(operand, rhs_res)
+        }
         Some(NodeVal::Internal(InternalNodeType::Equal))
         | Some(NodeVal::Internal(InternalNodeType::NotEqual))
         | Some(NodeVal::Internal(InternalNodeType::LessThan))
         | Some(NodeVal::Internal(InternalNodeType::GreaterThan))
         | Some(NodeVal::Internal(InternalNodeType::LessEqualThan))
         | Some(NodeVal::Internal(InternalNodeType::GreaterEqualThan)) => {
-            let rel_op = &expr.children()[0];
-            let lhs_res = validate_arith_expr(&rel_op.children()[0], function_entry, global);
-            let rhs_res = validate_arith_expr(&rel_op.children()[1], function_entry, global);
-            if lhs_res.is_err() {
-                lhs_res
-            } else if rhs_res.is_err() {
-                rhs_res
-            } else {
-                let lhs_res = lhs_res.unwrap();
-                let rhs_res = rhs_res.unwrap();
-                if lhs_res != rhs_res {
-                    Err(SemanticError::TypeMistmatch(format!(
-                        "Type Mistmatch in relative operation: {:?} {:?} {:?}: line {}",
-                        lhs_res,
-                        rel_op.val(),
-                        rhs_res,
-                        999
-                    )))
-                } else {
-                    Ok(Integer) // In relative operations, the resulting type is an integer (where anything but 0 is true)
-                }
-            }
+            validate_rel_expr(operand, function_entry, global)
         }
         Some(NodeVal::Internal(InternalNodeType::ArithExpr)) => {
-            let arith_res = validate_arith_expr(&expr.children()[0], function_entry, global);
+            let arith_res = validate_arith_expr(operand, function_entry, global);
             arith_res
         }
         _ => {
-            panic!("failed match expr for {:?}", expr.children()[0].val())
+            panic!("failed match expr for {:?}", operand.val())
         }
     };
 
     return res;
 }
 
-///Validates a Rel Expr
+/// Validates a relative operation. Both operands have the same type once promoted, and strings can only be compared for equality
 fn validate_rel_expr(
-    rel_expr: &Node,
+    rel_op: &Node,
     function_entry: &FunctionEntry,
     global: &SymbolTable,
 ) -> Result<Type, SemanticError> {
-    log::warn!("validating rel expr");
-    let lhs_res = validate_arith_expr(
-        &rel_expr.children()[0].children()[0],
-        function_entry,
-        global,
-    );
-    if lhs_res.is_err() {
-        return lhs_res;
-    }
-    let lhs_res = lhs_res.unwrap();
-
-    let rhs_res = validate_arith_expr(
-        &rel_expr.children()[0].children()[1],
-        function_entry,
-        global,
-    );
-    if rhs_res.is_err() {
-        return rhs_res;
-    }
-    let rhs_res = rhs_res.unwrap();
-
-    if lhs_res != rhs_res {
+    let lhs_res = validate_arith_expr(&rel_op.children()[0], function_entry, global)?;
+    let rhs_res = validate_arith_expr(&rel_op.children()[1], function_entry, global)?;
+    if promoted_type(&lhs_res, &rhs_res).is_none() {
         return Err(SemanticError::TypeMistmatch(format!(
-            "Type mistmatch in rel expr -> {:?} {:?} {:?}",
+            "Type Mistmatch in relative operation: {:?} {:?} {:?}: line {}",
             lhs_res,
-            rel_expr.val(),
-            rhs_res
+            rel_op.val(),
+            rhs_res,
+            999
         )));
     }
+    match rel_op.val() {
+        Some(NodeVal::Internal(InternalNodeType::Equal))
+        | Some(NodeVal::Internal(InternalNodeType::NotEqual)) => Ok(Bool),
+        _ if lhs_res == Type::String => Err(SemanticError::TypeMistmatch(format!(
+            "Operator {:?} cannot be applied to {:?}, strings can only be compared with == and <>",
+            rel_op.val(),
+            lhs_res
+        ))),
+        _ => Ok(Bool),
+    }
+}
 
-    return Ok(lhs_res);
+/// Validates the condition of an if, while, for or ternary operation, which has to be a bool
+fn validate_condition(
+    condition: &Node,
+    construct: &str,
+    function_entry: &FunctionEntry,
+    global: &SymbolTable,
+) -> Result<(), SemanticError> {
+    log::warn!("validating condition");
+    match validate_expr(condition, function_entry, global)? {
+        Bool => Ok(()),
+        Integer => Err(SemanticError::NonBooleanCondition(format!(
+            "{} condition expected bool. Got integer instead, use a comparison such as `<> 0`: in {}",
+            construct,
+            function_entry.ident()
+        ))),
+        other => Err(SemanticError::NonBooleanCondition(format!(
+            "{} condition expected bool. Got {:?} instead: in {}",
+            construct,
+            other,
+            function_entry.ident()
+        ))),
+    }
 }
 
 /// Validate an Arith Expr
-fn validate_arith_expr(
+pub(crate) fn validate_arith_expr(
     arith_expr: &Node,
     function_entry: &FunctionEntry,
     global: &SymbolTable,
@@ -655,7 +910,7 @@ fn validate_arith_expr(
     <factor> ::= #MakeTerminalNode 'intLit'
     <factor> ::= #MakeTerminalNode 'floatLit'
     <factor> ::= #MakeTerminalNode 'stringLit'
-    <factor> ::= '(' <arithExpr> ')'
+    <factor> ::= '(' <expr> ')'
     <factor> ::= #MakeFamilyRootNode("Negation") 'not' <factor> #AddChild
     <factor> ::= #MakeFamilyRootNode("SignedFactor") <sign> #AddChild <factor> #AddChild
     <factor> ::= #MakeFamilyRootNode("TernaryOperation") 'qm' '[' <expr> #AddChild ':' <expr> #AddChild ':' <expr> #AddChild ']'
@@ -668,17 +923,15 @@ fn validate_arith_expr(
 
     <addOp> ::= #MakeFamilyRootNode("Add") '+'
     <addOp> ::= #MakeFamilyRootNode("Sub") '-'
-    <addOp> ::= #MakeFamilyRootNode("Or") 'or'
 
     <multOp> ::= #MakeFamilyRootNode("Mult") '*'
     <multOp> ::= #MakeFamilyRootNode("Div") '/'
-    <multOp> ::= #MakeFamilyRootNode("And") 'and'
+    <multOp> ::= #MakeFamilyRootNode("Mod") 'mod'
      */
     log::warn!("validating arith expr");
     let arith_expr_res: Result<Type, SemanticError> = match arith_expr.children()[0].val() {
         Some(NodeVal::Internal(InternalNodeType::Add))
-        | Some(NodeVal::Internal(InternalNodeType::Sub))
-        | Some(NodeVal::Internal(InternalNodeType::Or)) => {
+        | Some(NodeVal::Internal(InternalNodeType::Sub)) => {
             validate_add_op(&arith_expr.children()[0], function_entry, global)
         }
         Some(NodeVal::Internal(InternalNodeType::Term)) => {
@@ -707,7 +960,7 @@ fn validate_term(
     <factor> ::= #MakeTerminalNode 'intLit'
     <factor> ::= #MakeTerminalNode 'floatLit'
     <factor> ::= #MakeTerminalNode 'stringLit'
-    <factor> ::= '(' <arithExpr> ')'
+    <factor> ::= '(' <expr> ')'
     <factor> ::= #MakeFamilyRootNode("Negation") 'not' <factor> #AddChild
     <factor> ::= #MakeFamilyRootNode("SignedFactor") <sign> #AddChild <factor> #AddChild
     <factor> ::= #MakeFamilyRootNode("TernaryOperation") 'qm' '[' <expr> #AddChild ':' <expr> #AddChild ':' <expr> #AddChild ']'
@@ -720,60 +973,102 @@ fn validate_term(
 
     <multOp> ::= #MakeFamilyRootNode("Mult") '*'
     <multOp> ::= #MakeFamilyRootNode("Div") '/'
-    <multOp> ::= #MakeFamilyRootNode("And") 'and'
+    <multOp> ::= #MakeFamilyRootNode("Mod") 'mod'
      */
     log::warn!("validating term");
     let term_res: Result<Type, SemanticError> = match term.children()[0].val() {
         Some(NodeVal::Internal(InternalNodeType::Mult))
         | Some(NodeVal::Internal(InternalNodeType::Div))
-        | Some(NodeVal::Internal(InternalNodeType::And)) => {
+        | Some(NodeVal::Internal(InternalNodeType::Mod)) => {
             validate_mult_op(&term.children()[0], function_entry, global)
         }
+        _ => validate_factor(&term.children()[0], function_entry, global),
+    };
+
+    return term_res;
+}
+
+/// Validates a factor: a literal, an ident, a parenthesized/signed/negated factor, a ternary operation or a cast
+fn validate_factor(
+    factor: &Node,
+    function_entry: &FunctionEntry,
+    global: &SymbolTable,
+) -> Result<Type, SemanticError> {
+    log::warn!("validating factor");
+    match factor.val() {
         Some(NodeVal::Leaf(token)) => Ok(map_token_to_type(token)),
         Some(NodeVal::Internal(InternalNodeType::Factor)) => {
-            validate_ident_factor(&term.children()[0], function_entry, global)
+            validate_ident_factor(factor, function_entry, global)
         } // Factor node has id child
-        Some(NodeVal::Internal(InternalNodeType::ArithExpr)) => {
-            validate_arith_expr(&term.children()[0], function_entry, global)
+        Some(NodeVal::Internal(InternalNodeType::Expr)) => {
+            validate_expr(factor, function_entry, global)
         }
         Some(NodeVal::Internal(InternalNodeType::SignedFactor)) => {
-            match term.children()[0].children()[1].val() {
-                Some(NodeVal::Internal(InternalNodeType::Factor)) => {
-                    validate_ident_factor(&term.children()[0].children()[1], function_entry, global)
-                }
-                Some(NodeVal::Internal(InternalNodeType::ArithExpr)) => {
-                    validate_arith_expr(&term.children()[0], function_entry, global)
-                }
-                Some(NodeVal::Leaf(token)) => Ok(map_token_to_type(token)),
-                _ => {
-                    panic!()
-                }
+            let operand = validate_factor(&factor.children()[1], function_entry, global)?;
+            if operand == Bool || operand == Type::String || operand.is_enum() {
+                return Err(SemanticError::TypeMistmatch(format!(
+                    "Cannot apply a sign to a {:?}: in {}",
+                    operand,
+                    function_entry.ident()
+                )));
             }
+            Ok(operand)
         }
         Some(NodeVal::Internal(InternalNodeType::Negation)) => {
-            // Factor, token, arith expr
-            match term.children()[0].children()[0].val() {
-                Some(NodeVal::Internal(InternalNodeType::Factor)) => {
-                    validate_ident_factor(&term.children()[0].children()[1], function_entry, global)
-                }
-                Some(NodeVal::Internal(InternalNodeType::ArithExpr)) => {
-                    validate_arith_expr(&term.children()[0], function_entry, global)
-                }
-                Some(NodeVal::Leaf(token)) => Ok(map_token_to_type(token)),
-                _ => {
-                    panic!()
-                }
+            let operand = validate_factor(&factor.children()[0], function_entry, global)?;
+            if operand != Bool {
+                return Err(SemanticError::TypeMistmatch(format!(
+                    "Negation expected bool. Got {:?} instead: in {}",
+                    operand,
+                    function_entry.ident()
+                )));
             }
+            Ok(Bool)
         }
         Some(NodeVal::Internal(InternalNodeType::TernaryOperation)) => {
-            validate_ternary_operation(&term.children()[0], function_entry, global)
+            validate_ternary_operation(factor, function_entry, global)
+        }
+        Some(NodeVal::Internal(InternalNodeType::Cast)) => {
+            let target = match factor.children()[0].val() {
+                Some(NodeVal::Leaf(token)) if token.token_type() == TokenType::FloatType => {
+                    Type::Float
+                }
+                _ => Integer,
+            };
+            let operand = validate_expr(&factor.children()[1], function_entry, global)?;
+            // an enum can be cast to the integer value of its enumerator
+            let enum_to_integer = operand.is_enum() && target == Integer;
+            if operand != Integer && operand != Type::Float && !enum_to_integer {
+                return Err(SemanticError::TypeMistmatch(format!(
+                    "Cannot cast {:?} to {:?}, only integers, floats and enums (to integer) can be cast: line {}",
+                    operand,
+                    target,
+                    line_of(factor)
+                )));
+            }
+            Ok(target)
         }
         _ => {
-            panic!("Failed match term")
+            panic!("Failed match factor")
         }
-    };
+    }
+}
 
-    return term_res;
+/// Validates an operand of an arithmetic expression, as found in its postfix form
+pub(crate) fn validate_arith_operand(
+    operand: &Node,
+    function_entry: &FunctionEntry,
+    global: &SymbolTable,
+) -> Result<Type, SemanticError> {
+    match operand.val() {
+        Some(NodeVal::Leaf(token)) if token.token_type() == TokenType::Id => {
+            validate_ident(token, operand, function_entry, global)
+        }
+        Some(NodeVal::Internal(InternalNodeType::DotOp)) => {
+            validate_dot_operator(operand, function_entry, global)
+        }
+        _ => validate_factor(operand, function_entry, global),
+    }
 }
 
 fn validate_ident_factor(
@@ -810,6 +1105,23 @@ fn validate_ident(
     global: &SymbolTable,
 ) -> Result<Type, SemanticError> {
     log::warn!("validating ident");
+    if let Some(ce) = find_constant(ident_token.lexeme(), function_entry, global) {
+        return match ident_node.children().first().and_then(|n| n.val()) {
+            Some(NodeVal::Internal(InternalNodeType::FuncCallParams)) => {
+                Err(SemanticError::NotCallable(format!(
+                    "Constant {} is not callable: line {}",
+                    ce.ident(),
+                    ident_token.line_num()
+                )))
+            }
+            Some(_) => Err(SemanticError::NotIndexable(format!(
+                "Constant {} is not indexable: line {}",
+                ce.ident(),
+                ident_token.line_num()
+            ))),
+            None => Ok(ce.const_type().clone()),
+        };
+    }
     if let Some(Scope::FunctionParameter(e)) = function_entry
         .table()
         .find_scope_by_ident(ident_token.lexeme())
@@ -828,7 +1140,9 @@ fn validate_ident(
                         Type::IntegerArray(dim)
                         | Type::FloatArray(dim)
                         | Type::StringArray(dim)
-                        | Type::CustomArray(_, dim) => {
+                        | Type::BoolArray(dim)
+                        | Type::CustomArray(_, dim)
+                        | Type::EnumArray(_, dim) => {
                             if dim.len() < ident_node.children().len() {
                                 return Err(SemanticError::TooManyIndices(format!("Tried indexing param {} with {} indices, but it only has {} dimensions: line {}", e.ident(), ident_node.children().len(), dim.len(), ident_token.line_num())));
                             }
@@ -862,10 +1176,7 @@ fn validate_ident(
         } else {
             return Ok(e.param_type().clone());
         }
-    } else if let Some(Scope::Variable(e)) = function_entry
-        .table()
-        .find_scope_by_ident(ident_token.lexeme())
-    {
+    } else if let Some(e) = find_variable(ident_token.lexeme(), function_entry, global) {
         if ident_node.children().len() > 0 {
             match ident_node.children()[0].val() {
                 Some(NodeVal::Internal(InternalNodeType::FuncCallParams)) => {
@@ -880,7 +1191,9 @@ fn validate_ident(
                         Type::IntegerArray(dim)
                         | Type::FloatArray(dim)
                         | Type::StringArray(dim)
-                        | Type::CustomArray(_, dim) => {
+                        | Type::BoolArray(dim)
+                        | Type::CustomArray(_, dim)
+                        | Type::EnumArray(_, dim) => {
                             if dim.len() < ident_node.children().len() {
                                 return Err(SemanticError::TooManyIndices(format!("Tried indexing param {} with {} indices, but it only has {} dimensions: line {}", e.ident(), ident_node.children().len(), dim.len(), ident_token.line_num())));
                             }
@@ -914,55 +1227,40 @@ fn validate_ident(
         } else {
             return Ok(e.var_type().clone());
         }
-    } else if let Some(Scope::Function(e)) = global.find_scope_by_ident(ident_token.lexeme()) {
-        return if ident_node.children()[0].val()
+    } else if !ident_node.children().is_empty()
+        && ident_node.children()[0].val()
             == Some(&NodeVal::Internal(InternalNodeType::FuncCallParams))
-        {
-            // return validate_function_call(ident_token, &ident_node.children()[0], function_entry, global);
-            validate_function_call(
-                ident_token,
-                &ident_node.children()[0],
-                e,
-                function_entry,
-                global,
-            )
-        } else {
-            Err(SemanticError::InvalidParameters(format!(
-                "Function call parameters for {} not found.",
-                ident_token.lexeme()
-            )))
-        };
+    {
+        let candidates = function_candidates(ident_token.lexeme(), function_entry, global)?;
+        if candidates.is_empty() {
+            return Err(SemanticError::FunctionNotFound(format!(
+                "No function {} found: line {}",
+                ident_token.lexeme(),
+                ident_token.line_num()
+            )));
+        }
+        resolve_function_call(
+            ident_token,
+            &ident_node.children()[0],
+            &candidates,
+            function_entry,
+            global,
+        )
+        .map(|fe| fe.type_sig().1.clone())
+    } else if let Some(Scope::Function(_)) = global.find_scope_by_ident(ident_token.lexeme()) {
+        Err(SemanticError::InvalidParameters(format!(
+            "Function call parameters for {} not found.",
+            ident_token.lexeme()
+        )))
     } else if function_entry.member_of().is_some() {
         return if let Some(Scope::Class(ce)) =
             global.find_scope_by_ident(function_entry.member_of().unwrap())
         {
-            if ident_node.children().len() > 0
-                && ident_node.children()[0].val()
-                    == Some(&NodeVal::Internal(InternalNodeType::FuncCallParams))
-            {
-                let (member_functions, mut errors) = get_class_hierarchy_functions(ce, global);
-                if errors.len() > 0 {
-                    return Err(errors.pop().unwrap());
-                }
-                for member_function in member_functions {
-                    let res = validate_function_call(
-                        ident_token,
-                        &ident_node.children()[0],
-                        member_function,
-                        function_entry,
-                        global,
-                    );
-                    if res.is_ok() {
-                        return res;
-                    }
-                }
-                Err(SemanticError::InvalidParameters(format!(
-                    "No function matched parameters for {}: line {}",
-                    ident_token.lexeme(),
-                    ident_token.line_num()
-                )))
-            } else if let Some(Scope::Variable(cve)) =
-                ce.table().find_scope_by_ident(ident_token.lexeme())
+            // data members of the object the function was called on, inherited ones included
+            let (data_members, _errors) = get_class_hierarchy_data_members(ce, global);
+            if let Some(cve) = data_members
+                .into_iter()
+                .find(|cve| cve.ident() == ident_token.lexeme())
             {
                 if ident_node.children().len() > 0 {
                     let indices = ident_node.children();
@@ -975,7 +1273,9 @@ fn validate_ident(
                         Type::IntegerArray(dim)
                         | Type::FloatArray(dim)
                         | Type::StringArray(dim)
-                        | Type::CustomArray(_, dim) => {
+                        | Type::BoolArray(dim)
+                        | Type::CustomArray(_, dim)
+                        | Type::EnumArray(_, dim) => {
                             if dim.len() < indices.len() {
                                 return Err(SemanticError::TooManyIndices(format!("Tried indexing param {} with {} indices, but it only has {} dimensions: line {}", cve.ident(), indices.len(), dim.len(), ident_token.line_num())));
                             }
@@ -1020,7 +1320,7 @@ fn validate_ident(
     }
 }
 
-/// Validates an add_op (+, -, or)
+/// Validates an add_op (+, -)
 fn validate_add_op(
     op_node: &Node,
     function_entry: &FunctionEntry,
@@ -1028,14 +1328,13 @@ fn validate_add_op(
 ) -> Result<Type, SemanticError> {
     // lhs might be a term, or another add op
     // rhs is a term
-    log::warn!("validating add/sub/or operator");
+    log::warn!("validating add/sub operator");
     let lhs_res = match op_node.children()[0].val() {
         Some(NodeVal::Internal(InternalNodeType::Term)) => {
             validate_term(&op_node.children()[0], function_entry, global)
         }
         Some(NodeVal::Internal(InternalNodeType::Add))
-        | Some(NodeVal::Internal(InternalNodeType::Sub))
-        | Some(NodeVal::Internal(InternalNodeType::Or)) => {
+        | Some(NodeVal::Internal(InternalNodeType::Sub)) => {
             validate_add_op(&op_node.children()[0], function_entry, global)
         }
         _ => {
@@ -1053,17 +1352,16 @@ fn validate_add_op(
     }
     let rhs_res = rhs_res.unwrap();
 
-    if lhs_res != rhs_res {
-        return Err(SemanticError::TypeMistmatch(format!(
+    match promoted_type(&lhs_res, &rhs_res) {
+        Some(ty) => validate_operand_type(op_node, ty),
+        None => Err(SemanticError::TypeMistmatch(format!(
             "Type Mistmatch in add op: lhs {:?}, rhs {:?}: line {}",
             lhs_res, rhs_res, 888
-        )));
-    } else {
-        return Ok(lhs_res);
+        ))),
     }
 }
 
-/// Validates a mult_op (*, /, and)
+/// Validates a mult_op (*, /, %)
 fn validate_mult_op(
     op_node: &Node,
     function_entry: &FunctionEntry,
@@ -1071,61 +1369,56 @@ fn validate_mult_op(
 ) -> Result<Type, SemanticError> {
     // lhs might be a factor, or another mult op
     // rhs is a factor or token
-    log::warn!("validating mult/div/and operator");
+    log::warn!("validating mult/div/mod operator");
     let lhs_res = match op_node.children()[0].val() {
-        Some(NodeVal::Internal(InternalNodeType::Factor)) => {
-            validate_ident_factor(&op_node.children()[0], function_entry, global)
-        }
         Some(NodeVal::Internal(InternalNodeType::Mult))
         | Some(NodeVal::Internal(InternalNodeType::Div))
-        | Some(NodeVal::Internal(InternalNodeType::And)) => {
+        | Some(NodeVal::Internal(InternalNodeType::Mod)) => {
             validate_mult_op(&op_node.children()[0], function_entry, global)
         }
-        Some(NodeVal::Leaf(token)) => match token.token_type() {
-            TokenType::Id => validate_ident(token, &op_node.children()[1], function_entry, global),
-            TokenType::IntegerLit => Ok(Type::Integer),
-            TokenType::FloatLit => Ok(Type::Float),
-            _ => panic!(),
-        },
-        _ => {
-            log::error!("{:?}", op_node.children()[0]);
-            panic!("Failed to match child of mult op")
-        }
-    };
-    if lhs_res.is_err() {
-        return lhs_res;
+        _ => validate_factor(&op_node.children()[0], function_entry, global),
+    }?;
+
+    let rhs_res = validate_factor(&op_node.children()[1], function_entry, global)?;
+
+    match promoted_type(&lhs_res, &rhs_res) {
+        Some(ty) => validate_operand_type(op_node, ty),
+        None => Err(SemanticError::TypeMistmatch(format!(
+            "Type Mistmatch in mult op: lhs {:?}, rhs {:?}: line {}",
+            lhs_res, rhs_res, 888
+        ))),
     }
-    let lhs_res = lhs_res.unwrap();
+}
 
-    let rhs_res = match op_node.children()[1].val() {
-        Some(NodeVal::Internal(InternalNodeType::Factor)) => {
-            todo!()
-        }
-        Some(NodeVal::Leaf(token)) => match token.token_type() {
-            TokenType::IntegerLit => Ok(Integer),
-            TokenType::FloatLit => Ok(Float),
-            TokenType::Id => validate_ident(token, &op_node.children()[1], function_entry, global),
-            _ => {
-                panic!()
-            }
-        },
-        _ => {
-            panic!()
-        }
-    };
-    if rhs_res.is_err() {
-        return rhs_res;
+/// Type of the operands of a binary operator once promoted: an integer is promoted to a float when the other operand is a float.
+/// None if the operand types don't match.
+pub(crate) fn promoted_type(lhs: &Type, rhs: &Type) -> Option<Type> {
+    match (lhs, rhs) {
+        _ if lhs == rhs => Some(lhs.clone()),
+        (Integer, Type::Float) | (Type::Float, Integer) => Some(Type::Float),
+        _ => None,
     }
-    let rhs_res = rhs_res.unwrap();
+}
 
-    if lhs_res != rhs_res {
+/// Checks that an operand of a binary operator is of a type it accepts.
+/// `&` and `|` only take bools, `%` only takes integers, `+` concatenates strings,
+/// other arithmetic operators take anything but bools, strings and enums.
+fn validate_operand_type(op_node: &Node, operand: Type) -> Result<Type, SemanticError> {
+    let accepted = match op_node.val() {
+        Some(NodeVal::Internal(InternalNodeType::And))
+        | Some(NodeVal::Internal(InternalNodeType::Or)) => operand == Bool,
+        Some(NodeVal::Internal(InternalNodeType::Mod)) => operand == Integer,
+        Some(NodeVal::Internal(InternalNodeType::Add)) => operand != Bool && !operand.is_enum(),
+        _ => operand != Bool && operand != Type::String && !operand.is_enum(),
+    };
+    if !accepted {
         return Err(SemanticError::TypeMistmatch(format!(
-            "Type Mistmatch in mult op: lhs {:?}, rhs {:?}: line {}",
-            lhs_res, rhs_res, 888
+            "Operator {:?} cannot be applied to {:?}",
+            op_node.val(),
+            operand
         )));
-    } else {
-        return Ok(lhs_res);
     }
+    Ok(operand)
 }
 
 /// Validates a ternary operation
@@ -1136,11 +1429,7 @@ fn validate_ternary_operation(
 ) -> Result<Type, SemanticError> {
     log::warn!("validating ternary operator");
     //validate 3 expressions
-    let cond_expr = validate_expr(&ter.children()[0], function_entry, global);
-    if cond_expr.is_err() {
-        return cond_expr;
-    }
-    let cond_expr = cond_expr.unwrap();
+    validate_condition(&ter.children()[0], "Ternary", function_entry, global)?;
 
     let then_expr = validate_expr(&ter.children()[1], function_entry, global);
     if then_expr.is_err() {
@@ -1154,13 +1443,6 @@ fn validate_ternary_operation(
     }
     let else_expr = else_expr.unwrap();
 
-    if cond_expr != Integer {
-        return Err(SemanticError::TypeMistmatch(format!(
-            "Expected integer for condition of ternary operation. Got {:?} instead",
-            cond_expr
-        )));
-    }
-
     if then_expr != else_expr {
         return Err(SemanticError::TypeMistmatch(format!(
             "Mistmatched ternary op types -> {:?} : {:?}",
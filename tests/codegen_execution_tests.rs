@@ -0,0 +1,292 @@
+extern crate comp442_compiler;
+use common::{init, run_on_moon, run_on_moon_with_options, run_program_on_moon};
+use comp442_compiler::codegen::generator::CodegenOptions;
+
+mod common;
+
+#[test]
+fn and_should_skip_its_right_operand_when_the_left_one_is_false() {
+    init();
+
+    let output = run_on_moon(
+        "func check(integer x) : bool {
+          write(x);
+          return (x > 0);
+        }
+        main {
+          var {
+            bool b;
+          }
+          b = check(0) & check(1);
+          write(?[b : 1 : 0]);
+          b = check(5) & check(6) & check(0) & check(7);
+          write(?[b : 1 : 0]);
+        }",
+    );
+
+    if let Some(output) = output {
+        assert_eq!(output, "005600");
+    }
+}
+
+#[test]
+fn or_should_skip_its_right_operand_when_the_left_one_is_true() {
+    init();
+
+    let output = run_on_moon(
+        "func check(integer x) : bool {
+          write(x);
+          return (x > 0);
+        }
+        main {
+          var {
+            bool b;
+          }
+          b = check(2) | check(3);
+          write(?[b : 1 : 0]);
+          b = check(0) | check(4);
+          write(?[b : 1 : 0]);
+        }",
+    );
+
+    if let Some(output) = output {
+        assert_eq!(output, "21041");
+    }
+}
+
+#[test]
+fn conditions_should_not_index_past_a_false_bounds_check() {
+    init();
+
+    let output = run_on_moon(
+        "func at(integer arr[], integer i) : integer {
+          write(i);
+          return (arr[i]);
+        }
+        main {
+          var {
+            integer arr[3];
+            integer i;
+            integer n;
+            integer count;
+          }
+          n = 3;
+          arr[0] = 1;
+          arr[1] = 2;
+          arr[2] = 3;
+          i = 0;
+          count = 0;
+          while (i < n & at(arr, i) > 0) {
+            count = count + arr[i];
+            i = i + 1;
+          };
+          write(count);
+        }",
+    );
+
+    if let Some(output) = output {
+        assert_eq!(output, "0126");
+    }
+}
+
+#[test]
+fn switch_should_run_the_matching_case() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/switch.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "217610301005702176");
+    }
+}
+
+#[test]
+fn bounds_check_should_halt_on_an_index_out_of_bounds() {
+    init();
+
+    let src = "main {
+          var {
+            integer arr[3][2];
+            integer i;
+          }
+          for (i = 0; i < 3; i = i + 1) {
+            arr[i][1] = i;
+            write(arr[i][1]);
+          };
+          i = 0 - 1;
+          write(arr[1][i + 2]);
+          write(arr[i][0]);
+          write(9);
+        }";
+    let options = CodegenOptions { bounds_check: true };
+
+    if let Some(output) = run_on_moon_with_options(src, options) {
+        assert_eq!(output.trim_end(), "0121index out of bounds at line 12");
+    }
+}
+
+#[test]
+fn bounds_check_should_not_change_in_bounds_programs() {
+    init();
+
+    let options = CodegenOptions { bounds_check: true };
+    let output = run_on_moon_with_options(include_str!("codegen/object_arrays.src"), options);
+
+    if let Some(output) = output {
+        assert_eq!(output, "33011671283330");
+    }
+}
+
+#[test]
+fn global_variables_should_be_shared_by_all_functions() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/globals.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "3154561205100151221573.0hi");
+    }
+}
+
+#[test]
+fn integers_and_floats_should_convert_into_each_other() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/casts.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "4.53.253.03.53.01.56.04-23.5111");
+    }
+}
+
+#[test]
+fn enums_should_run_as_their_values() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/enums.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "010101027");
+    }
+}
+
+#[test]
+fn programs_should_run_across_their_files() {
+    init();
+
+    let output = run_program_on_moon("tests/codegen/modules/program.src");
+
+    if let Some(output) = output {
+        assert_eq!(output, "362414");
+    }
+}
+
+#[test]
+fn signed_expressions_should_run() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/signed_expressions.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "-6-2.5107");
+    }
+}
+
+#[test]
+fn signed_variables_and_calls_should_run() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/signed_factors.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "-5-2.55-3-4-1.25-4-105-5.07-40000");
+    }
+}
+
+#[test]
+fn recursive_functions_should_run() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/recursion.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "1205511");
+    }
+}
+
+#[test]
+fn member_functions_should_run_on_their_object() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/member_functions.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "513131010137121");
+    }
+}
+
+#[test]
+fn data_members_should_be_read_and_written_through_the_dot_operator() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/data_members.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "7581350915372135");
+    }
+}
+
+#[test]
+fn arrays_of_objects_should_be_indexed_by_the_class_size() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/object_arrays.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "33011671283330");
+    }
+}
+
+#[test]
+fn float_arithmetic_should_run_in_fixed_point() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/floats.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "3.75-0.753.3751.5-3.750.7551.06212.56811123456.78913.167");
+    }
+}
+
+#[test]
+fn strings_should_run() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/strings.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "hello12hello worldhellozxyxyhi bob4");
+    }
+}
+
+#[test]
+fn overridden_functions_should_dispatch_on_the_class_of_the_object() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/virtual_dispatch.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "01326102010141040");
+    }
+}
+
+#[test]
+fn data_members_of_every_base_class_should_be_laid_out() {
+    init();
+
+    let output = run_on_moon(include_str!("codegen/multiple_inheritance.src"));
+
+    if let Some(output) = output {
+        assert_eq!(output, "12310");
+    }
+}
@@ -1,7 +1,11 @@
 extern crate comp442_compiler;
 use common::init;
-use comp442_compiler::codegen::generator::MoonGenerator;
-use comp442_compiler::semantics::symbol_table::generate_symbol_table;
+use comp442_compiler::codegen::generator::{CodegenOptions, MoonGenerator};
+use comp442_compiler::parser::modules::{link_modules, load_modules};
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
+use comp442_compiler::semantics::symbol_table::{
+    generate_program_symbol_table, generate_symbol_table,
+};
 
 mod common;
 
@@ -11,7 +15,7 @@ fn codegen_function_code_generation_should_work1() {
 
     let lexer = common::setup_lexer_from_file("tests/codegen/assignment.src");
 
-    let parse_res = comp442_compiler::parser::parse::parse(lexer);
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
     assert!(parse_res.is_ok());
     let root = parse_res.unwrap().1.into_ast_root();
     assert!(root.is_ok());
@@ -34,7 +38,7 @@ fn codegen_function_code_generation_should_work2() {
 
     let lexer = common::setup_lexer_from_file("tests/codegen/assignment_expressions.src");
 
-    let parse_res = comp442_compiler::parser::parse::parse(lexer);
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
     assert!(parse_res.is_ok());
     let root = parse_res.unwrap().1.into_ast_root();
     assert!(root.is_ok());
@@ -57,7 +61,7 @@ fn codegen_function_code_generation_should_work3() {
 
     let lexer = common::setup_lexer_from_file("tests/codegen/array_indexing.src");
 
-    let parse_res = comp442_compiler::parser::parse::parse(lexer);
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
     assert!(parse_res.is_ok());
     let root = parse_res.unwrap().1.into_ast_root();
     assert!(root.is_ok());
@@ -80,7 +84,7 @@ fn codegen_function_code_generation_should_work4() {
 
     let lexer = common::setup_lexer_from_file("tests/codegen/while_loop.src");
 
-    let parse_res = comp442_compiler::parser::parse::parse(lexer);
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
     assert!(parse_res.is_ok());
     let root = parse_res.unwrap().1.into_ast_root();
     assert!(root.is_ok());
@@ -103,7 +107,7 @@ fn codegen_function_code_generation_should_work5() {
 
     let lexer = common::setup_lexer_from_file("tests/codegen/if_statement.src");
 
-    let parse_res = comp442_compiler::parser::parse::parse(lexer);
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
     assert!(parse_res.is_ok());
     let root = parse_res.unwrap().1.into_ast_root();
     assert!(root.is_ok());
@@ -126,7 +130,7 @@ fn codegen_function_code_generation_should_work6() {
 
     let lexer = common::setup_lexer_from_file("tests/codegen/function_call.src");
 
-    let parse_res = comp442_compiler::parser::parse::parse(lexer);
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
     assert!(parse_res.is_ok());
     let root = parse_res.unwrap().1.into_ast_root();
     assert!(root.is_ok());
@@ -142,3 +146,546 @@ fn codegen_function_code_generation_should_work6() {
 
     log::info!("{}", output);
 }
+
+#[test]
+fn codegen_function_code_generation_should_work7() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/for_loop.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    // continue jumps to the step, break out of the loop
+    assert!(output.contains("j forstep_1"));
+    assert!(output.contains("j endfor_1"));
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work8() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/bool_conditions.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    // bools are 0/1 words, so !x is computed as x == 0
+    assert!(output.contains("ceqi"));
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work9() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/nested_loops.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    let lines: Vec<&str> = output.lines().map(|line| line.trim()).collect();
+    let jump_after = |comment: &str| {
+        let idx = lines
+            .iter()
+            .position(|line| line.contains(comment))
+            .unwrap();
+        lines[idx + 1]
+    };
+    // the inner while loop was allocated last, but the break belongs to the outer for loop
+    assert_eq!(jump_after("~ Continue Statement ~"), "j while_1");
+    assert_eq!(jump_after("~ Break Statement ~"), "j endfor_1");
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work10() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/logical_operators.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    // & and | skip their right operand when the left one decides the result
+    assert!(output.contains(",endlogical_1"));
+    assert!(output.contains("bnz R"));
+    assert!(!output.contains("and R"));
+    assert!(!output.contains("or R"));
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work11() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/modulus.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    assert!(output.contains("mod R"));
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work12() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/constants.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    // constants are folded into immediates, they never get a label
+    assert!(!output.contains("var_main_N"));
+    assert!(!output.contains("var_main_LAST"));
+    assert!(output.contains("Immediate(\"4\")"));
+    assert!(output.contains(",R0,10\n"));
+    // a[N] takes 16 bytes of the frame of main, right before i
+    assert!(output.contains("%Labelled(\"-28\", R14) = Immediate(\"0\")"));
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work13() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/overloading.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    // each overload gets its own labels, and calls jump to the one matching their arguments
+    assert!(output.contains("fn_5twicei\t"));
+    assert!(output.contains("fn_5twiceii\t"));
+    assert!(output.contains("fn_5twiceb\t"));
+    assert!(output.contains("Storing into function parameter y"));
+    assert!(output.contains("jl R15,fn_5twiceb"));
+    assert!(output.contains("jl R15,fn_5totalA0_ii"));
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work14() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/recursion.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = codegen.finish();
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work15() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/member_functions.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = codegen.finish();
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work16() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/data_members.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = codegen.finish();
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work17() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/object_arrays.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = codegen.finish();
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work18() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/floats.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = codegen.finish();
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work19() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/strings.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = codegen.finish();
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work20() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/virtual_dispatch.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = codegen.finish();
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work21() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/switch.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    // sparse cases are compared one by one
+    assert!(output.contains("ceqi R"));
+    assert!(output.contains(",-5"));
+    assert!(output.contains("j case_1_6"));
+    // dense cases jump through a table, missing values go to the default case
+    assert!(output.contains("dw case_2_1,case_2_2,case_2_3,case_2_4,case_2_5,case_2_7,case_2_6"));
+    assert!(output.contains(",jumptable_2(R"));
+    assert!(output.contains("jr R"));
+    // without a default case, the switch is skipped
+    assert!(output.contains("j endswitch_3"));
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work22() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/array_indexing.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut unchecked = MoonGenerator::new();
+    unchecked.generate(&root, &symbol_table);
+    let unchecked = format!("{}", unchecked.finish());
+    assert!(!unchecked.contains("outofbounds_"));
+    assert!(!unchecked.contains("boundserror"));
+
+    let mut checked = MoonGenerator::with_options(CodegenOptions { bounds_check: true });
+    checked.generate(&root, &symbol_table);
+    let checked = format!("{}", checked.finish());
+    // every index is compared against 0 and the size of its dimension
+    assert!(checked.contains(",outofbounds_1"));
+    assert!(checked.contains(",inbounds_1"));
+    assert!(checked.contains("j boundserror"));
+    assert!(checked.contains("jl R15,putint"));
+
+    log::info!("{}", checked);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work23() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/globals.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    // global variables are laid out once, in the data section
+    assert!(output.contains("gv_calls\t\t\tres 4"));
+    assert!(output.contains("gv_history\t\t\tres 12"));
+    assert!(output.contains("gv_counter\t\t\tres 8"));
+    assert!(output.contains(",gv_calls(R0)"));
+    assert!(output.contains("sw gv_history(R"));
+    // the global object points at its vtable before main runs
+    assert!(output.contains(",R0,gv_counter"));
+    assert!(output.contains(",R0,vt_FAST_COUNTER"));
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work24() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/casts.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    // integers are scaled up into floats, and floats are truncated back down
+    assert!(output
+        .lines()
+        .any(|l| l.contains("muli") && l.ends_with(",1000")));
+    assert!(output
+        .lines()
+        .any(|l| l.contains("divi") && l.ends_with(",1000")));
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work25() {
+    init();
+
+    let path = "tests/codegen/modules/program.src";
+    let lexer = common::setup_lexer_from_file(path);
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let modules = load_modules(path.as_ref(), root.unwrap(), ParseOptions::default());
+    assert!(modules.is_ok());
+    let modules = modules.unwrap();
+    let (symbol_table, errors) = generate_program_symbol_table(&modules);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&link_modules(&modules), &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    // geometry.src is imported twice, its functions are generated once
+    assert_eq!(output.matches("% FUNCTION: perimeter").count(), 1);
+
+    log::info!("{}", output);
+}
+
+#[test]
+fn codegen_function_code_generation_should_work26() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/codegen/enums.src");
+
+    let parse_res = parse_with_options(lexer, ParseOptions::default());
+    assert!(parse_res.is_ok());
+    let root = parse_res.unwrap().1.into_ast_root();
+    assert!(root.is_ok());
+    let root = root.unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert_eq!(errors.len(), 0);
+
+    let mut codegen = MoonGenerator::new();
+
+    codegen.generate(&root, &symbol_table);
+
+    let output = format!("{}", codegen.finish());
+    // enums are part of the mangled names of the functions taking them
+    assert!(output.contains("fn_4next5Color"));
+    // enumerators are inlined as their value, Green being 1
+    assert!(output.contains("Immediate(\"1\")"));
+    assert!(output
+        .lines()
+        .any(|l| l.contains("ceqi") && l.ends_with(",1")));
+
+    log::info!("{}", output);
+}
@@ -1,8 +1,21 @@
+use comp442_compiler::codegen::generator::{CodegenOptions, MoonGenerator};
+use comp442_compiler::codegen::utils::write_moon_code_to_file;
 use comp442_compiler::lexer::lexer::MyLexerAnalyzer;
+use comp442_compiler::parser::ast::Node;
+use comp442_compiler::parser::modules::{link_modules, load_modules};
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
+use comp442_compiler::semantics::checking::SemanticError;
+use comp442_compiler::semantics::symbol_table::{
+    check_program_semantics, check_semantics, generate_program_symbol_table, generate_symbol_table,
+    SymbolTable,
+};
 use dotenv::dotenv;
 use env_logger;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
+#[allow(dead_code)]
 pub fn setup_lexer_from_file<P: AsRef<Path>>(file_name: P) -> MyLexerAnalyzer {
     return MyLexerAnalyzer::from_file(file_name);
 }
@@ -12,10 +25,144 @@ pub fn setup_lexer_from_string(src: &str) -> MyLexerAnalyzer {
     return MyLexerAnalyzer::from_str(src);
 }
 
+/// Recursively collects the `.src` files under a directory
+#[allow(dead_code)]
+pub fn collect_sources(dir: &Path, sources: &mut Vec<PathBuf>) {
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_sources(&path, sources);
+        } else if path.extension().map_or(false, |ext| ext == "src") {
+            sources.push(path);
+        }
+    }
+}
+
+/// Builds the MOON VM from moon_vm/moon.c once per test binary. Returns None if there is no C compiler.
+fn moon_vm() -> Option<PathBuf> {
+    let moon = Path::new(env!("CARGO_TARGET_TMPDIR")).join("moon");
+    if !moon.exists() {
+        // test binaries run concurrently, so the VM is built under a unique name first
+        let build =
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("moon{}", std::process::id()));
+        let status = Command::new("cc")
+            .args(&["-w", "-o"])
+            .arg(&build)
+            .arg("moon_vm/moon.c")
+            .status()
+            .ok()?;
+        if !status.success() {
+            return None;
+        }
+        std::fs::rename(&build, &moon).ok()?;
+    }
+    Some(moon)
+}
+
+/// Parses a program and checks its semantics, returning the errors and warnings found
+#[allow(dead_code)]
+pub fn semantic_errors(src: &str) -> Vec<SemanticError> {
+    let root = parse_with_options(setup_lexer_from_string(src), ParseOptions::default())
+        .unwrap()
+        .1
+        .into_ast_root()
+        .unwrap();
+    let (symbol_table, mut errors) = generate_symbol_table(&root);
+    errors.append(&mut check_semantics(&root, &symbol_table));
+    errors
+}
+
+/// Compiles a program and runs it on the MOON VM with the runtime routines, returning what it wrote.
+/// Returns None when the VM can't be built, so the tests calling it are skipped.
+#[allow(dead_code)]
+pub fn run_on_moon(src: &str) -> Option<String> {
+    run_on_moon_with_options(src, CodegenOptions::default())
+}
+
+/// Same as [run_on_moon], generating the code with the given options
+#[allow(dead_code)]
+pub fn run_on_moon_with_options(src: &str, options: CodegenOptions) -> Option<String> {
+    let root = parse_with_options(setup_lexer_from_string(src), ParseOptions::default())
+        .unwrap()
+        .1
+        .into_ast_root()
+        .unwrap();
+    let (symbol_table, mut errors) = generate_symbol_table(&root);
+    errors.append(&mut check_semantics(&root, &symbol_table));
+    errors.retain(|e| !matches!(e, SemanticError::Warning(_)));
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    run_generated_on_moon(&root, &symbol_table, options)
+}
+
+/// Same as [run_on_moon], for a program split across files. `path` is the program's own file.
+#[allow(dead_code)]
+pub fn run_program_on_moon<P: AsRef<Path>>(path: P) -> Option<String> {
+    let path = path.as_ref();
+    let root = parse_with_options(setup_lexer_from_file(path), ParseOptions::default())
+        .unwrap()
+        .1
+        .into_ast_root()
+        .unwrap();
+    let modules = load_modules(path, root, ParseOptions::default()).unwrap();
+    let (symbol_table, mut errors) = generate_program_symbol_table(&modules);
+    errors.append(&mut check_program_semantics(&modules, &symbol_table));
+    errors.retain(|(_, e)| !matches!(e, SemanticError::Warning(_)));
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    run_generated_on_moon(
+        &link_modules(&modules),
+        &symbol_table,
+        CodegenOptions::default(),
+    )
+}
+
+#[allow(dead_code)]
+fn run_generated_on_moon(
+    root: &Node,
+    symbol_table: &SymbolTable,
+    options: CodegenOptions,
+) -> Option<String> {
+    static PROGRAM_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    let mut codegen = MoonGenerator::with_options(options);
+    codegen.generate(root, symbol_table);
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!(
+        "program{}_{}",
+        std::process::id(),
+        PROGRAM_COUNT.fetch_add(1, Ordering::SeqCst)
+    ));
+    write_moon_code_to_file(codegen.finish(), program.to_str().unwrap()).unwrap();
+
+    let moon = match moon_vm() {
+        Some(moon) => moon,
+        None => {
+            log::warn!("No C compiler to build the MOON VM with, skipping execution");
+            return None;
+        }
+    };
+    let output = Command::new(moon)
+        .arg(program.with_extension("m"))
+        .args(&["moon_vm/util.m", "moon_vm/float.m"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // the VM reports the files it loads before running, and the cycle count after
+    let start = stdout
+        .rfind("Loading ")
+        .and_then(|i| stdout[i..].find('\n').map(|j| i + j + 1))
+        .unwrap_or(0);
+    let written = &stdout[start..];
+    let end = written.trim_end().rfind('\n').unwrap_or(0);
+    Some(written[..end].to_string())
+}
+
 pub fn init() {
     dotenv().ok();
-    env_logger::builder()
+    // tests in the same binary share a logger, only the first one gets to install it
+    let _ = env_logger::builder()
         .is_test(true)
         .parse_filters("trace")
-        .init();
+        .try_init();
 }
@@ -0,0 +1,70 @@
+extern crate comp442_compiler;
+use comp442_compiler::parser::ast::{InternalNodeType, Node, NodeVal};
+use comp442_compiler::parser::invariants::check_ast_invariants;
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
+use common::init;
+use std::path::Path;
+
+mod common;
+
+#[test]
+fn parsed_sources_respect_ast_invariants() {
+    init();
+
+    let mut sources = Vec::new();
+    common::collect_sources(Path::new("tests/parser"), &mut sources);
+    common::collect_sources(Path::new("tests/codegen"), &mut sources);
+    common::collect_sources(Path::new("docs/Assignment3/Assignment3_Handout"), &mut sources);
+    common::collect_sources(Path::new("docs/Assignment4/Assignment4_Handout"), &mut sources);
+
+    let mut checked = 0;
+    for src in sources {
+        let lexer = common::setup_lexer_from_file(&src);
+        if let Ok((_, ast)) = parse_with_options(lexer, ParseOptions::default()) {
+            let root = ast.into_ast_root().unwrap();
+            let violations = check_ast_invariants(&root);
+            assert!(violations.is_empty(), "{:?}: {:?}", src, violations);
+            checked += 1;
+        }
+    }
+    assert!(checked > 0);
+}
+
+#[test]
+fn member_access_and_calls_respect_ast_invariants() {
+    init();
+
+    let src = "main {
+        var { A a; }
+        a.b[1].c = a.f(1, 2).g[0] + ?[x == 1 : -y : !z];
+        a.f(3);
+        read(a.b);
+        while (i < 10) { if (i <> 2) then i = i * (3 / 4); else ; };
+    }";
+    let lexer = common::setup_lexer_from_string(src);
+
+    let root = parse_with_options(lexer, ParseOptions::default())
+        .unwrap()
+        .1
+        .into_ast_root()
+        .unwrap();
+    let violations = check_ast_invariants(&root);
+    assert!(violations.is_empty(), "{:?}", violations);
+}
+
+#[test]
+fn malformed_nodes_should_be_internal_compiler_errors() {
+    init();
+
+    // main's body must be a FuncBody
+    let mut main = Node::new_with_val(NodeVal::Internal(InternalNodeType::Main));
+    main.add_child(Node::new_empty());
+
+    let violations = check_ast_invariants(&main);
+    assert_eq!(violations.len(), 1, "{:?}", violations);
+    assert_eq!(violations[0].rule, "OptProg -> 'main' FuncBody");
+    assert!(violations[0].to_string().starts_with("Internal compiler error"));
+    assert!(violations[0]
+        .to_string()
+        .contains("(built by OptProg -> 'main' FuncBody)"));
+}
@@ -1,5 +1,6 @@
 extern crate comp442_compiler;
 use common::init;
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
 
 mod common;
 
@@ -9,7 +10,7 @@ fn parser_classes_should_work() {
 
     let lexer = common::setup_lexer_from_file("tests/parser/classes/classes_should_work.src");
 
-    assert!(comp442_compiler::parser::parse::parse(lexer).is_ok());
+    assert!(parse_with_options(lexer, ParseOptions::default()).is_ok());
 }
 
 #[test]
@@ -17,7 +18,7 @@ fn parser_no_classes_should_work() {
     init();
 
     let lexer =
-        common::setup_lexer_from_file("tests/parser/classes/no_classes_should_work.src.src");
+        common::setup_lexer_from_file("tests/parser/classes/no_classes_should_work.src");
 
-    assert!(comp442_compiler::parser::parse::parse(lexer).is_ok());
+    assert!(parse_with_options(lexer, ParseOptions::default()).is_ok());
 }
@@ -0,0 +1,60 @@
+extern crate comp442_compiler;
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
+use comp442_compiler::parser::utils::derivation_table_to_json;
+use common::init;
+
+mod common;
+
+#[test]
+fn parser_derivation_not_recorded_by_default() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/parser/functions/functions_should_work.src");
+
+    let (table, _) = parse_with_options(lexer, ParseOptions::default()).unwrap();
+    assert!(table.is_none());
+}
+
+#[test]
+fn parser_derivation_ends_with_input_sentence() {
+    init();
+
+    let src = "main { var { integer x; } x = 1 + 2; }";
+    let lexer = common::setup_lexer_from_string(src);
+
+    let (table, _) = parse_with_options(
+        lexer,
+        ParseOptions {
+            record_derivation: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let table = table.unwrap();
+
+    let first = table.sentential_form(table.records().first().unwrap());
+    assert_eq!(first, vec!["<Start>"]);
+
+    let last = table.sentential_form(table.records().last().unwrap());
+    assert_eq!(last.join(" "), "main { var { integer x ; } x = 1 + 2 ; }");
+}
+
+#[test]
+fn parser_derivation_json_has_every_step() {
+    init();
+
+    let lexer = common::setup_lexer_from_file("tests/parser/functions/functions_should_work.src");
+
+    let (table, _) = parse_with_options(
+        lexer,
+        ParseOptions {
+            record_derivation: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let table = table.unwrap();
+
+    let json = derivation_table_to_json(&table);
+    assert_eq!(json.matches("\"step\":").count(), table.records().len());
+}
@@ -0,0 +1,141 @@
+extern crate comp442_compiler;
+use common::init;
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions, ParserBackend};
+use comp442_compiler::parser::recursive_descent;
+use std::path::Path;
+
+mod common;
+
+fn parse_with_backend(src: &Path, backend: ParserBackend) -> Result<String, ()> {
+    let lexer = common::setup_lexer_from_file(src);
+    let (_, ast) = parse_with_options(
+        lexer,
+        ParseOptions {
+            backend,
+            ..Default::default()
+        },
+    )
+    .map_err(|_| ())?;
+    Ok(format!("{:?}", ast.into_ast_root()?))
+}
+
+fn assert_backends_agree(src: &Path) {
+    let table = common::setup_lexer_from_file(src);
+    let rd = common::setup_lexer_from_file(src);
+
+    let table = parse_with_options(table, ParseOptions::default());
+    let rd = parse_with_options(
+        rd,
+        ParseOptions {
+            backend: ParserBackend::RecursiveDescent,
+            ..Default::default()
+        },
+    );
+
+    match (table, rd) {
+        (Ok((_, table_ast)), Ok((_, rd_ast))) => {
+            assert_eq!(
+                table_ast.into_ast_root().unwrap(),
+                rd_ast.into_ast_root().unwrap(),
+                "ASTs differ for {:?}",
+                src
+            );
+        }
+        (Err(_), Err(_)) => {}
+        (table, rd) => panic!(
+            "Parsers disagree on {:?}: table parser ok = {}, recursive-descent parser ok = {}",
+            src,
+            table.is_ok(),
+            rd.is_ok()
+        ),
+    }
+}
+
+#[test]
+fn parser_backends_agree_on_test_sources() {
+    init();
+
+    let mut sources = Vec::new();
+    common::collect_sources(Path::new("tests/parser"), &mut sources);
+    common::collect_sources(Path::new("tests/codegen"), &mut sources);
+    assert!(!sources.is_empty());
+
+    for src in sources {
+        assert_backends_agree(&src);
+    }
+}
+
+#[test]
+fn parser_backends_agree_on_handout_sources() {
+    init();
+
+    let mut sources = Vec::new();
+    common::collect_sources(
+        Path::new("docs/Assignment3/Assignment3_Handout"),
+        &mut sources,
+    );
+    common::collect_sources(
+        Path::new("docs/Assignment4/Assignment4_Handout"),
+        &mut sources,
+    );
+    assert!(!sources.is_empty());
+
+    for src in sources {
+        assert!(parse_with_backend(&src, ParserBackend::RecursiveDescent).is_ok());
+        assert_backends_agree(&src);
+    }
+}
+
+#[test]
+fn recursive_descent_reports_location_and_expectation() {
+    init();
+
+    let lexer = common::setup_lexer_from_string("main {\n var {\n integer x\n }\n}");
+
+    let errors =
+        recursive_descent::parse(lexer, recursive_descent::DEFAULT_MAX_NESTING_DEPTH).unwrap_err();
+    let err = &errors[0];
+    assert_eq!(err.line_num, 3);
+    assert_eq!(
+        err.message,
+        "unexpected '}' in variable declaration, expected one of '[' or ';'"
+    );
+}
+
+#[test]
+fn recursive_descent_enforces_nesting_depth() {
+    init();
+
+    let src = format!("main {{ write({}1{}); }}", "(".repeat(20), ")".repeat(20));
+
+    let lexer = common::setup_lexer_from_string(&src);
+    assert!(recursive_descent::parse(lexer, 32).is_ok());
+
+    let lexer = common::setup_lexer_from_string(&src);
+    let errors = recursive_descent::parse(lexer, 8).unwrap_err();
+    assert!(errors[0].message.contains("maximum depth is 8"));
+}
+
+#[test]
+fn recursive_descent_should_accept_statements_after_one_nested_too_deeply() {
+    init();
+
+    // write(((((1))))); is nested exactly as deeply as allowed
+    let src = format!(
+        "main {{
+          write(((((1)))));
+          write({}1{});
+          write(((((1)))));
+          write(((((1)))));
+          write(((((1)))));
+        }}",
+        "(".repeat(10),
+        ")".repeat(10)
+    );
+
+    let lexer = common::setup_lexer_from_string(&src);
+    let errors = recursive_descent::parse(lexer, 6).unwrap_err();
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0].line_num, 3);
+    assert!(errors[0].message.contains("maximum depth is 6"));
+}
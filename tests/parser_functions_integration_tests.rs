@@ -1,5 +1,6 @@
 extern crate comp442_compiler;
 use common::init;
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
 
 mod common;
 
@@ -9,7 +10,7 @@ fn parser_functions_should_work() {
 
     let lexer = common::setup_lexer_from_file("tests/parser/functions/functions_should_work.src");
 
-    assert!(comp442_compiler::parser::parse::parse(lexer).is_ok());
+    assert!(parse_with_options(lexer, ParseOptions::default()).is_ok());
 }
 
 #[test]
@@ -19,7 +20,7 @@ fn parser_no_functions_should_work() {
     let lexer =
         common::setup_lexer_from_file("tests/parser/functions/no_functions_should_work.src");
 
-    assert!(comp442_compiler::parser::parse::parse(lexer).is_ok());
+    assert!(parse_with_options(lexer, ParseOptions::default()).is_ok());
 }
 
 #[test]
@@ -28,7 +29,7 @@ fn parser_no_main_should_fail() {
 
     let lexer = common::setup_lexer_from_string("");
 
-    assert!(comp442_compiler::parser::parse::parse(lexer).is_err());
+    assert!(parse_with_options(lexer, ParseOptions::default()).is_err());
 }
 
 #[test]
@@ -37,7 +38,7 @@ fn parser_multiple_main_should_fail() {
 
     let lexer = common::setup_lexer_from_string("main {}\nmain{}");
 
-    assert!(comp442_compiler::parser::parse::parse(lexer).is_err());
+    assert!(parse_with_options(lexer, ParseOptions::default()).is_err());
 }
 
 #[test]
@@ -48,5 +49,5 @@ fn parser_function_array_return_should_fail() {
         "tests/parser/functions/function_returns_array_should_fail.src",
     );
 
-    assert!(comp442_compiler::parser::parse::parse(lexer).is_err());
+    assert!(parse_with_options(lexer, ParseOptions::default()).is_err());
 }
@@ -1,5 +1,6 @@
 extern crate comp442_compiler;
 use common::init;
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
 
 mod common;
 
@@ -9,5 +10,5 @@ fn parser_idnest_should_work() {
 
     let lexer = common::setup_lexer_from_file("tests/parser/idnest/idnest_should_work.src");
 
-    assert!(comp442_compiler::parser::parse::parse(lexer).is_ok());
+    assert!(parse_with_options(lexer, ParseOptions::default()).is_ok());
 }
@@ -0,0 +1,92 @@
+extern crate comp442_compiler;
+use common::init;
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions, ParserBackend};
+
+mod common;
+
+const SRC: &str = "main {
+  var {
+    integer y;
+  }
+  y = 1 +;
+  write(y);
+  y = = 2;
+  write(y;
+}";
+
+fn error_lines(backend: ParserBackend) -> Vec<usize> {
+    let lexer = common::setup_lexer_from_string(SRC);
+    let failure = parse_with_options(
+        lexer,
+        ParseOptions {
+            backend,
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+    failure.errors.iter().map(|err| err.line_num).collect()
+}
+
+#[test]
+fn table_parser_reports_every_syntax_error() {
+    init();
+
+    assert_eq!(error_lines(ParserBackend::Table), vec![5, 7, 8]);
+}
+
+#[test]
+fn recursive_descent_parser_reports_every_syntax_error() {
+    init();
+
+    assert_eq!(error_lines(ParserBackend::RecursiveDescent), vec![5, 7, 8]);
+}
+
+#[test]
+fn parser_keeps_derivation_on_failure() {
+    init();
+
+    let lexer = common::setup_lexer_from_string(SRC);
+    let failure = parse_with_options(
+        lexer,
+        ParseOptions {
+            record_derivation: true,
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+
+    let table = failure.derivation_table.unwrap();
+    let last = table.sentential_form(table.records().last().unwrap());
+    assert_eq!(&last[..4], &["main", "{", "var", "{"]);
+}
+
+#[test]
+fn parser_reports_unexpected_end_of_file() {
+    init();
+
+    let lexer = common::setup_lexer_from_string("main { write(1);");
+    let failure = parse_with_options(lexer, ParseOptions::default()).unwrap_err();
+
+    assert_eq!(failure.errors.len(), 1);
+    assert_eq!(failure.errors[0].line_num, 1);
+    assert!(failure.errors[0].message.contains("end of file"));
+}
+
+#[test]
+fn missing_token_is_reported_on_the_previous_line() {
+    init();
+
+    for backend in [ParserBackend::Table, ParserBackend::RecursiveDescent] {
+        let lexer = common::setup_lexer_from_string("main {\n  write(1)\n  write(2);\n}");
+        let failure = parse_with_options(
+            lexer,
+            ParseOptions {
+                backend,
+                ..Default::default()
+            },
+        )
+        .unwrap_err();
+
+        assert_eq!(failure.errors[0].line_num, 2);
+    }
+}
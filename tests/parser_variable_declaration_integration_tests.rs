@@ -1,5 +1,6 @@
 extern crate comp442_compiler;
 use common::init;
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
 
 mod common;
 
@@ -11,7 +12,7 @@ fn variable_declarations_should_work() {
         "tests/parser/variable_declarations/variable_declarations_should_work.src",
     );
 
-    assert!(comp442_compiler::parser::parse::parse(lexer).is_ok());
+    assert!(parse_with_options(lexer, ParseOptions::default()).is_ok());
 }
 
 #[test]
@@ -20,5 +21,5 @@ fn variable_declarations_multiple_should_fail() {
 
     let lexer = common::setup_lexer_from_string("func f1() : void {var{}var{}} main{}");
 
-    assert!(comp442_compiler::parser::parse::parse(lexer).is_err());
+    assert!(parse_with_options(lexer, ParseOptions::default()).is_err());
 }
@@ -0,0 +1,105 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::semantics::checking::SemanticError;
+
+mod common;
+
+#[test]
+fn bool_conditions_should_validate() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            bool flag;
+            integer x;
+          }
+          flag = x < 3;
+          if (flag) then { x = 1; } else { x = 2; };
+          while (!flag & true) { flag = x == 2; };
+          x = ?[flag | false : 1 : 2];
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn integer_condition_should_be_rejected() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            integer x;
+          }
+          if (x) then { x = 1; } else { x = 2; };
+        }",
+    );
+
+    assert_eq!(errors.len(), 1);
+    match &errors[0] {
+        SemanticError::NonBooleanCondition(msg) => assert!(msg.contains("integer")),
+        err => panic!("expected a non boolean condition error, got {:?}", err),
+    }
+}
+
+#[test]
+fn integer_ternary_condition_should_be_rejected() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            integer x;
+          }
+          x = ?[x : 1 : 2];
+        }",
+    );
+
+    assert!(matches!(
+        errors.as_slice(),
+        [SemanticError::NonBooleanCondition(_)]
+    ));
+}
+
+#[test]
+fn logical_operators_should_require_bools() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            integer x;
+            bool flag;
+          }
+          x = x & 1;
+          flag = !x;
+          x = flag + 1;
+        }",
+    );
+
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|err| matches!(err, SemanticError::TypeMistmatch(_))));
+}
+
+#[test]
+fn comparisons_should_combine_with_logical_operators() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            bool b;
+            integer i;
+            integer n;
+          }
+          b = (i < n) & (n > 0);
+          if (i < n & n > 0 | !b) then { i = 1; } else { i = 2; };
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
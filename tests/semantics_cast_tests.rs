@@ -0,0 +1,136 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::semantics::checking::SemanticError;
+
+mod common;
+
+#[test]
+fn integers_should_be_promoted_to_floats() {
+    init();
+
+    let errors = semantic_errors(
+        "func scale(float x, integer factor) : float {
+          return (x * factor);
+        }
+        func one() : float {
+          return (1);
+        }
+        main {
+          var {
+            integer i;
+            float f;
+            bool b;
+          }
+          f = 1;
+          f = i / 2 + f * 3 - 0.5;
+          f = scale(i, 2);
+          b = i < f;
+          b = 2.5 == i;
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn floats_should_not_be_narrowed_implicitly() {
+    init();
+
+    let errors = semantic_errors(
+        "func truncate(float x) : integer {
+          return (x);
+        }
+        func twice(integer x) : integer {
+          return (x * 2);
+        }
+        main {
+          var {
+            integer i;
+            float f;
+          }
+          i = f;
+          i = twice(f);
+          i = i % f;
+        }",
+    );
+
+    assert_eq!(errors.len(), 4, "{:?}", errors);
+    assert!(errors.iter().all(|e| matches!(
+        e,
+        SemanticError::TypeMistmatch(_) | SemanticError::InvalidParameters(_)
+    )));
+}
+
+#[test]
+fn casts_should_convert_between_integers_and_floats() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            integer i;
+            float f;
+          }
+          i = integer(f * 2.5) % 3;
+          f = float(i) / 2;
+          i = integer(i);
+          f = float(integer(f));
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn casts_should_only_take_numbers() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            integer i;
+            float f;
+            string s;
+          }
+          i = integer(s);
+          f = float(i < 2);
+          s = float(i);
+        }",
+    );
+
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::TypeMistmatch(_))));
+}
+
+#[test]
+fn exact_overloads_should_be_preferred_over_promotion() {
+    init();
+
+    let errors = semantic_errors(
+        "func f(integer x) : integer {
+          return (x);
+        }
+        func f(float x) : integer {
+          return (2);
+        }
+        func g(float x, integer y) : integer {
+          return (y);
+        }
+        func g(integer x, float y) : integer {
+          return (x);
+        }
+        main {
+          var {
+            integer i;
+          }
+          i = f(1);
+          i = f(1.0);
+          i = g(1, 2);
+        }",
+    );
+
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(errors[0], SemanticError::AmbiguousCall(_)));
+}
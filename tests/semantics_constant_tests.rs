@@ -0,0 +1,115 @@
+extern crate comp442_compiler;
+use common::init;
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
+use comp442_compiler::semantics::checking::SemanticError;
+use comp442_compiler::semantics::symbol_table::{
+    check_semantics, generate_symbol_table, Scope, SymbolTable, Type,
+};
+
+mod common;
+
+fn semantics(src: &str) -> (SymbolTable, Vec<SemanticError>) {
+    let lexer = common::setup_lexer_from_string(src);
+    let root = parse_with_options(lexer, ParseOptions::default())
+        .unwrap()
+        .1
+        .into_ast_root()
+        .unwrap();
+    let (symbol_table, mut errors) = generate_symbol_table(&root);
+    errors.append(&mut check_semantics(&root, &symbol_table));
+    (symbol_table, errors)
+}
+
+#[test]
+fn constants_should_be_evaluated() {
+    init();
+
+    let (symbol_table, errors) = semantics(
+        "const integer N = 4;
+        const integer M = N * 2 + 2;
+        const bool BIG = M > 5 & true;
+        main {
+          var {
+            integer a[M];
+            integer x;
+          }
+          x = N + M;
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+    match symbol_table.find_scope_by_ident("M") {
+        Some(Scope::Constant(ce)) => assert_eq!(ce.value(), 10),
+        other => panic!("{:?}", other),
+    }
+    match symbol_table.find_scope_by_ident("BIG") {
+        Some(Scope::Constant(ce)) => assert_eq!(ce.value(), 1),
+        other => panic!("{:?}", other),
+    }
+    match symbol_table.find_scope_by_ident("main") {
+        Some(Scope::Function(fe)) => match fe.table().find_scope_by_ident("a") {
+            Some(Scope::Variable(ve)) => assert_eq!(ve.var_type(), &Type::IntegerArray(vec![10])),
+            other => panic!("{:?}", other),
+        },
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn constants_should_not_be_assigned() {
+    init();
+
+    let (_, errors) = semantics(
+        "const integer N = 4;
+        main {
+          N = 5;
+        }",
+    );
+
+    assert!(matches!(
+        errors.as_slice(),
+        [SemanticError::ConstantAssignment(_)]
+    ));
+}
+
+#[test]
+fn constants_should_need_constant_initializers() {
+    init();
+
+    let (_, errors) = semantics(
+        "main {
+          var {
+            integer x;
+            const integer N = x + 1;
+          }
+          x = N;
+        }",
+    );
+
+    assert!(matches!(
+        errors.as_slice(),
+        [SemanticError::InvalidConstant(_)]
+    ));
+}
+
+#[test]
+fn array_dimensions_should_be_integer_constants() {
+    init();
+
+    let (_, errors) = semantics(
+        "const bool B = true;
+        main {
+          var {
+            integer x;
+            integer a[x];
+            integer b[B];
+          }
+          x = 1;
+        }",
+    );
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::InvalidConstant(_))));
+}
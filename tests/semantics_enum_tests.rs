@@ -0,0 +1,180 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
+use comp442_compiler::semantics::checking::SemanticError;
+use comp442_compiler::semantics::symbol_table::{generate_symbol_table, Scope, Type};
+
+mod common;
+
+#[test]
+fn enumerators_should_be_numbered_constants_of_their_enum() {
+    init();
+
+    let lexer = common::setup_lexer_from_string(
+        "enum Color { Red, Green, Blue };
+        const Color FAVOURITE = Green;
+        main {
+          var {
+            Color c;
+            Color palette[3];
+          }
+        }",
+    );
+    let root = parse_with_options(lexer, ParseOptions::default())
+        .unwrap()
+        .1
+        .into_ast_root()
+        .unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+    assert!(errors.is_empty(), "{:?}", errors);
+
+    match symbol_table.find_scope_by_ident("Color") {
+        Some(Scope::Enum(e)) => assert_eq!(e.enumerators(), &["Red", "Green", "Blue"]),
+        other => panic!("{:?}", other),
+    }
+    for (ident, value) in &[("Red", 0), ("Green", 1), ("Blue", 2), ("FAVOURITE", 1)] {
+        match symbol_table.find_scope_by_ident(ident) {
+            Some(Scope::Constant(e)) => {
+                assert_eq!(e.const_type(), &Type::Enum("Color".to_string()));
+                assert_eq!(e.value(), *value);
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+    match symbol_table.find_scope_by_ident("main") {
+        Some(Scope::Function(fe)) => {
+            assert!(matches!(
+                fe.table().find_scope_by_ident("c"),
+                Some(Scope::Variable(e)) if e.var_type() == &Type::Enum("Color".to_string())
+            ));
+            assert!(matches!(
+                fe.table().find_scope_by_ident("palette"),
+                Some(Scope::Variable(e)) if e.var_type() == &Type::EnumArray("Color".to_string(), vec![3])
+            ));
+        }
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn enums_should_be_comparable_and_castable_to_integers() {
+    init();
+
+    let errors = semantic_errors(
+        "enum Color { Red, Green, Blue };
+        class PIXEL {
+          public Color color;
+        };
+        func next(Color c) : Color {
+          if (c == Blue) then {
+            return (Red);
+          } else {
+          };
+          return (Green);
+        }
+        main {
+          var {
+            Color c;
+            PIXEL p;
+            integer i;
+          }
+          c = next(Red);
+          p.color = c;
+          i = integer(p.color) + 1;
+          write(integer(c));
+          while (c <> Blue) {
+            c = next(c);
+          };
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn enums_should_not_be_mixed_with_integers() {
+    init();
+
+    let errors = semantic_errors(
+        "enum Color { Red, Green };
+        enum Size { Small, Large };
+        func f(Color c) : integer {
+          return (1);
+        }
+        main {
+          var {
+            Color c;
+            integer i;
+          }
+          c = 1;
+          i = Red;
+          i = f(0);
+          if (c == 0) then {
+          } else {
+          };
+          if (c == Small) then {
+          } else {
+          };
+        }",
+    );
+
+    assert_eq!(errors.len(), 5, "{:?}", errors);
+}
+
+#[test]
+fn enums_should_not_be_used_in_arithmetic() {
+    init();
+
+    let errors = semantic_errors(
+        "enum Color { Red, Green };
+        main {
+          var {
+            Color c;
+            integer i;
+          }
+          c = Red + Green;
+          i = -c;
+          c = float(c);
+        }",
+    );
+
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::TypeMistmatch(_))));
+}
+
+#[test]
+fn switch_on_an_enum_should_take_its_enumerators() {
+    init();
+
+    let errors = semantic_errors(
+        "enum Color { Red, Green, Blue };
+        const Color FAVOURITE = Blue;
+        main {
+          var {
+            Color c;
+          }
+          switch (c) {
+            case Red:
+              c = Green;
+            case FAVOURITE:
+              c = Red;
+          };
+          switch (c) {
+            case Green:
+            case 2:
+              break;
+          };
+          switch (c) {
+            case Blue:
+            case FAVOURITE:
+              break;
+          };
+        }",
+    );
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(matches!(errors[0], SemanticError::TypeMistmatch(_)));
+    assert!(matches!(errors[1], SemanticError::DuplicateCase(_)));
+}
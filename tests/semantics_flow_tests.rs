@@ -0,0 +1,200 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::semantics::checking::{SemanticError, WarningType};
+
+mod common;
+
+fn unreachable_lines(errors: &[SemanticError]) -> Vec<String> {
+    errors
+        .iter()
+        .filter_map(|e| match e {
+            SemanticError::Warning(WarningType::UnreachableCodeWarning(msg)) => {
+                Some(msg.rsplit("line ").next().unwrap().to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn functions_returning_on_every_path_should_be_accepted() {
+    init();
+
+    let errors = semantic_errors(
+        "func sign(integer x) : integer {
+          if (x < 0) then {
+            return (-1);
+          } else {
+            if (x == 0) then
+              return (0);
+            else
+              return (1);
+            ;
+          };
+        }
+        func pick(integer x) : integer {
+          switch (x) {
+            case 1:
+              return (10);
+            default:
+              return (0);
+          };
+        }
+        func search(integer x) : integer {
+          while (true) {
+            if (x > 100) then {
+              return (x);
+            } else {
+            };
+            x = x * 2;
+          };
+        }
+        func show(integer x) : void {
+          if (x > 0) then {
+            write(x);
+          } else {
+          };
+        }
+        main {
+          write(sign(3) + pick(1) + search(1));
+          show(2);
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn functions_missing_a_return_should_be_reported() {
+    init();
+
+    let errors = semantic_errors(
+        "class COUNTER {
+          public integer count;
+          public func next() : integer;
+        };
+        func COUNTER::next() : integer {
+          count = count + 1;
+        }
+        func positive(integer x) : integer {
+          if (x > 0) then {
+            return (x);
+          } else {
+          };
+        }
+        func pick(integer x) : integer {
+          switch (x) {
+            case 1:
+              return (10);
+            case 2:
+              return (20);
+          };
+        }
+        func search(integer x) : integer {
+          while (true) {
+            if (x > 100) then {
+              break;
+            } else {
+            };
+            x = x * 2;
+          };
+        }
+        func first(integer x) : integer {
+          for (x = 0; x < 10; x = x + 1) {
+            return (x);
+          };
+        }
+        main {
+        }",
+    );
+
+    assert_eq!(errors.len(), 5, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::MissingReturn(_))));
+}
+
+#[test]
+fn statements_after_a_jump_should_be_unreachable() {
+    init();
+
+    let errors = semantic_errors(
+        "func f(integer x) : integer {
+          return (x);
+          x = x + 1;
+          x = x + 2;
+        }
+        func g(integer x) : integer {
+          if (x > 0) then {
+            return (1);
+          } else {
+            return (2);
+          };
+          write(x);
+        }
+        main {
+          var {
+            integer i;
+          }
+          for (i = 0; i < 10; i = i + 1) {
+            if (i == 2) then {
+              continue;
+              write(i);
+            } else {
+              break;
+            };
+            write(i + 1);
+          };
+          while (true) {
+            write(i);
+          };
+          write(i + 2);
+        }",
+    );
+
+    // only the first unreachable statement of a block is reported
+    assert_eq!(
+        unreachable_lines(&errors),
+        vec!["3", "12", "21", "25", "30"],
+        "{:?}",
+        errors
+    );
+    assert_eq!(errors.len(), 5, "{:?}", errors);
+}
+
+#[test]
+fn a_switch_should_go_on_when_a_case_breaks_out_of_it() {
+    init();
+
+    let errors = semantic_errors(
+        "func f(integer x) : integer {
+          switch (x) {
+            case 1:
+              return (1);
+            case 2:
+              break;
+            default:
+              return (0);
+          };
+          return (2);
+        }
+        func g(integer x) : integer {
+          while (x < 10) {
+            switch (x) {
+              case 1:
+                continue;
+              default:
+                return (x);
+            };
+            x = x + 1;
+          };
+          return (0);
+        }
+        main {
+          write(f(1) + g(1));
+        }",
+    );
+
+    assert_eq!(unreachable_lines(&errors), vec!["20"], "{:?}", errors);
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+}
@@ -0,0 +1,112 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
+use comp442_compiler::semantics::checking::SemanticError;
+use comp442_compiler::semantics::symbol_table::{generate_symbol_table, Scope, Type};
+
+mod common;
+
+#[test]
+fn global_variables_should_be_in_the_global_table() {
+    init();
+
+    let lexer = common::setup_lexer_from_string(
+        "const integer SIZE = 4;
+        integer count;
+        float values[SIZE];
+        main {
+        }",
+    );
+    let root = parse_with_options(lexer, ParseOptions::default())
+        .unwrap()
+        .1
+        .into_ast_root()
+        .unwrap();
+    let (symbol_table, errors) = generate_symbol_table(&root);
+
+    assert!(errors.is_empty(), "{:?}", errors);
+    match symbol_table.find_scope_by_ident("count") {
+        Some(Scope::Variable(e)) => assert_eq!(e.var_type(), &Type::Integer),
+        other => panic!("{:?}", other),
+    }
+    match symbol_table.find_scope_by_ident("values") {
+        Some(Scope::Variable(e)) => assert_eq!(e.var_type(), &Type::FloatArray(vec![4])),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn global_variables_should_be_visible_from_all_functions() {
+    init();
+
+    let errors = semantic_errors(
+        "integer count;
+        POINT origin;
+        class POINT {
+          public integer x;
+          public func move() : void;
+        };
+        func POINT::move() : void {
+          x = x + count;
+        }
+        func bump() : integer {
+          count = count + 1;
+          return (count);
+        }
+        main {
+          origin.x = bump();
+          origin.move();
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn locals_and_data_members_should_shadow_global_variables() {
+    init();
+
+    let errors = semantic_errors(
+        "string x;
+        class POINT {
+          public integer x;
+          public func get() : integer;
+        };
+        func POINT::get() : integer {
+          return (x);
+        }
+        func f(integer x) : integer {
+          return (x + 1);
+        }
+        main {
+          var {
+            float x;
+          }
+          x = 1.5;
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn global_variables_should_be_checked() {
+    init();
+
+    let errors = semantic_errors(
+        "integer count;
+        float count;
+        SHAPE s;
+        main {
+          count = 1;
+        }",
+    );
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::MultipleDeclIdent(_))));
+    assert!(errors
+        .iter()
+        .any(|e| matches!(e, SemanticError::UndeclaredClass(_))));
+}
@@ -0,0 +1,55 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::semantics::checking::SemanticError;
+
+mod common;
+
+#[test]
+fn break_and_continue_should_be_allowed_in_loops() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            integer i;
+            integer j;
+          }
+          for (i = 0; i < 10; i = i + 1) {
+            j = 0;
+            while (j < i) {
+              if (j == 3) then { break; } else { j = j + 1; continue; };
+            };
+            if (i == 5) then { continue; } else ;
+            break;
+          };
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn break_and_continue_should_be_rejected_outside_loops() {
+    init();
+
+    let errors = semantic_errors(
+        "func f(integer x) : integer {
+          if (x == 0) then { break; } else ;
+          return (x);
+        }
+        main {
+          var {
+            integer i;
+          }
+          while (i < 3) {
+            i = i + 1;
+          };
+          continue;
+        }",
+    );
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::NotInLoop(_))));
+}
@@ -0,0 +1,164 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::semantics::checking::SemanticError;
+
+mod common;
+
+#[test]
+fn member_functions_should_resolve_through_the_class_hierarchy() {
+    init();
+
+    let errors = semantic_errors(
+        "class BASE {
+          public integer x;
+          public func get() : integer;
+        };
+        class DERIVED inherits BASE {
+          public func twice(integer k) : integer;
+        };
+        func BASE::get() : integer { return (x); }
+        func DERIVED::twice(integer k) : integer { return (get() + x * k); }
+        main {
+          var {
+            DERIVED d;
+            integer i;
+          }
+          i = d.get();
+          i = d.twice(2);
+          d.twice(i);
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn member_functions_should_reject_unknown_members() {
+    init();
+
+    let errors = semantic_errors(
+        "class A {
+          public integer x;
+          public func get() : integer;
+        };
+        func A::get() : integer { return (y); }
+        main {
+          var {
+            A a;
+            integer i;
+          }
+          i = a.size(1);
+        }",
+    );
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::UndeclaredVariable(_))));
+}
+
+#[test]
+fn data_members_should_resolve_through_the_class_hierarchy() {
+    init();
+
+    let errors = semantic_errors(
+        "class POINT {
+          public integer x;
+          public integer y;
+        };
+        class SHAPE {
+          public POINT origin;
+          public integer sides[4];
+        };
+        class SQUARE inherits SHAPE {
+          public integer len;
+        };
+        main {
+          var {
+            SQUARE q;
+            integer i;
+          }
+          q.origin.x = 1;
+          q.sides[2] = q.len + q.origin.y;
+          i = q.sides[q.origin.x];
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn member_functions_should_be_overridden_with_the_same_signature() {
+    init();
+
+    let errors = semantic_errors(
+        "class POLYNOMIAL {
+          public func evaluate(integer x) : integer;
+          public func scale(integer x) : integer;
+        };
+        class LINEAR inherits POLYNOMIAL {
+          public integer a;
+          public func evaluate(integer x) : integer;
+          public func scale(integer x) : float;
+        };
+        class QUADRATIC inherits LINEAR {
+          private func evaluate(integer x) : integer;
+        };
+        func POLYNOMIAL::evaluate(integer x) : integer { return (0); }
+        func POLYNOMIAL::scale(integer x) : integer { return (x); }
+        func LINEAR::evaluate(integer x) : integer { return (a * x); }
+        func LINEAR::scale(integer x) : float { return (1.5); }
+        func QUADRATIC::evaluate(integer x) : integer { return (x * x); }
+        main {
+          var {
+            LINEAR l;
+            integer i;
+          }
+          i = l.evaluate(2);
+        }",
+    );
+
+    // LINEAR::evaluate overrides POLYNOMIAL::evaluate, but not with a different return type or visibility
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::InvalidOverride(_))));
+}
+
+#[test]
+fn only_the_first_base_class_should_have_member_functions() {
+    init();
+
+    let errors = semantic_errors(
+        "class A {
+          public integer a;
+          public func getA() : integer;
+        };
+        class B {
+          public integer b;
+          public func getB() : integer;
+        };
+        class D inherits B {
+        };
+        class C inherits A, B {
+        };
+        class E inherits A, D {
+        };
+        class F inherits B, A {
+        };
+        class G {
+          public integer g;
+        };
+        class H inherits A, G {
+        };
+        func A::getA() : integer { return (a); }
+        func B::getB() : integer { return (b); }
+        main {
+        }",
+    );
+
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::UnsupportedInheritance(_))));
+}
@@ -0,0 +1,280 @@
+extern crate comp442_compiler;
+use common::init;
+use comp442_compiler::parser::modules::{load_modules, FileId, Module, ModuleError};
+use comp442_compiler::parser::parse::{parse_with_options, ParseOptions};
+use comp442_compiler::semantics::checking::SemanticError;
+use comp442_compiler::semantics::symbol_table::{
+    check_program_semantics, generate_program_symbol_table,
+};
+use std::path::{Path, PathBuf};
+
+mod common;
+
+/// Writes the files of a program in a directory of its own, the first one being the program's own file
+fn write_program(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR"))
+        .join("modules")
+        .join(name);
+    for (path, src) in files {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, src).unwrap();
+    }
+    dir.join(files[0].0)
+}
+
+fn load(path: &Path) -> Result<Vec<Module>, Vec<ModuleError>> {
+    let root = parse_with_options(common::setup_lexer_from_file(path), ParseOptions::default())
+        .unwrap()
+        .1
+        .into_ast_root()
+        .unwrap();
+    load_modules(path, root, ParseOptions::default())
+}
+
+fn semantic_errors(path: &Path) -> Vec<(FileId, SemanticError)> {
+    let modules = load(path).unwrap();
+    let (symbol_table, mut errors) = generate_program_symbol_table(&modules);
+    errors.append(&mut check_program_semantics(&modules, &symbol_table));
+    errors
+}
+
+#[test]
+fn imported_files_should_be_loaded_once_before_their_importers() {
+    init();
+
+    let path = write_program(
+        "load_order",
+        &[
+            (
+                "program.src",
+                "import \"lib/shapes.src\";
+                import \"lib/util.src\";
+                main {
+                  write(twice(SIDES));
+                }",
+            ),
+            ("lib/shapes.src", "const integer SIDES = 4;"),
+            (
+                "lib/util.src",
+                "import \"shapes.src\";
+                func twice(integer x) : integer {
+                  return (x * 2);
+                }",
+            ),
+        ],
+    );
+
+    let modules = load(&path).unwrap();
+
+    let files: Vec<(FileId, String)> = modules
+        .iter()
+        .map(|m| (m.file_id(), m.file_name()))
+        .collect();
+    assert_eq!(
+        files,
+        vec![
+            (1, "shapes.src".to_string()),
+            (2, "util.src".to_string()),
+            (0, "program.src".to_string())
+        ]
+    );
+    assert!(semantic_errors(&path).is_empty());
+}
+
+#[test]
+fn missing_imports_should_be_reported() {
+    init();
+
+    let path = write_program(
+        "missing_import",
+        &[(
+            "program.src",
+            "import \"nowhere.src\";
+            main {
+            }",
+        )],
+    );
+
+    let errors = load(&path).unwrap_err();
+
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        &errors[0],
+        ModuleError::MissingFile { line_num: 1, .. }
+    ));
+    assert!(errors[0].to_string().contains("nowhere.src"));
+}
+
+#[test]
+fn declarations_clashing_across_files_should_be_reported_in_their_file() {
+    init();
+
+    let path = write_program(
+        "clashes",
+        &[
+            (
+                "program.src",
+                "import \"lib.src\";
+                integer count;
+                class SHAPE {
+                };
+                func area(integer x) : integer {
+                  return (x);
+                }
+                func area(float x) : integer {
+                  return (1);
+                }
+                main {
+                }",
+            ),
+            (
+                "lib.src",
+                "integer count;
+                class SHAPE {
+                };
+                func area(integer x) : integer {
+                  return (x);
+                }",
+            ),
+        ],
+    );
+
+    let errors = semantic_errors(&path);
+
+    // the overload of area taking a float doesn't clash
+    assert_eq!(errors.len(), 3, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|(file_id, e)| *file_id == 0 && matches!(e, SemanticError::MultipleDeclIdent(_))));
+}
+
+#[test]
+fn member_functions_should_be_definable_in_another_file() {
+    init();
+
+    let path = write_program(
+        "member_functions",
+        &[
+            (
+                "program.src",
+                "import \"shape.src\";
+                func SHAPE::area() : integer {
+                  return (size * size);
+                }
+                main {
+                  var {
+                    SHAPE s;
+                  }
+                  write(s.area());
+                }",
+            ),
+            (
+                "shape.src",
+                "class SHAPE {
+                  public integer size;
+                  public func area() : integer;
+                };",
+            ),
+        ],
+    );
+
+    let errors = semantic_errors(&path);
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn member_functions_should_not_be_defined_twice() {
+    init();
+
+    let path = write_program(
+        "member_functions_twice",
+        &[
+            (
+                "program.src",
+                "import \"shape.src\";
+                func SHAPE::area() : integer {
+                  return (1);
+                }
+                main {
+                }",
+            ),
+            (
+                "shape.src",
+                "class SHAPE {
+                  public func area() : integer;
+                };
+                func SHAPE::area() : integer {
+                  return (2);
+                }",
+            ),
+        ],
+    );
+
+    let errors = semantic_errors(&path);
+
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(errors[0].1, SemanticError::MultipleDeclIdent(_)));
+}
+
+#[test]
+fn programs_should_have_a_main_function() {
+    init();
+
+    let path = write_program(
+        "no_main",
+        &[
+            ("program.src", "import \"lib.src\";"),
+            ("lib.src", "integer count;"),
+        ],
+    );
+
+    let errors = semantic_errors(&path);
+
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert_eq!(errors[0].0, 0);
+    assert!(matches!(errors[0].1, SemanticError::FunctionNotFound(_)));
+}
+
+#[test]
+fn enums_should_be_usable_by_the_files_importing_them() {
+    init();
+
+    let path = write_program(
+        "enums",
+        &[
+            (
+                "program.src",
+                "import \"size.src\";
+                import \"color.src\";
+                main {
+                  var {
+                    Color c;
+                  }
+                  c = Green;
+                  write(integer(c));
+                }",
+            ),
+            ("color.src", "enum Color { Red, Green };"),
+            (
+                "size.src",
+                "enum Size { Small, Large };
+                func f(Color c) : integer {
+                  return (1);
+                }",
+            ),
+        ],
+    );
+
+    let errors = semantic_errors(&path);
+
+    // size.src uses Color, which is loaded after it since it doesn't import color.src
+    assert!(!errors.is_empty());
+    assert!(
+        errors.iter().all(|(file_id, _)| *file_id == 1),
+        "{:?}",
+        errors
+    );
+    assert!(matches!(errors[0].1, SemanticError::UndeclaredClass(_)));
+}
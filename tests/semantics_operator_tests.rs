@@ -0,0 +1,40 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::semantics::checking::SemanticError;
+
+mod common;
+
+#[test]
+fn modulus_should_validate_on_integers() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            integer x;
+          }
+          x = x % 3 + 7 % x;
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn modulus_should_reject_floats() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            float f;
+          }
+          f = f % 2.0;
+        }",
+    );
+
+    assert!(matches!(
+        errors.as_slice(),
+        [SemanticError::TypeMistmatch(_)]
+    ));
+}
@@ -0,0 +1,95 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::semantics::checking::SemanticError;
+
+mod common;
+
+#[test]
+fn overloads_should_resolve_by_argument_types() {
+    init();
+
+    let errors = semantic_errors(
+        "func f(integer x) : integer { return (x); }
+        func f(float x) : float { return (x); }
+        func f(integer x, bool b) : bool { return (b); }
+        main {
+          var {
+            integer i;
+            float r;
+            bool b;
+          }
+          i = f(1);
+          r = f(1.5);
+          b = f(i, true);
+        }",
+    );
+
+    // plain overloads are not suspicious, they don't warn either
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn overloads_should_reject_unmatched_calls() {
+    init();
+
+    let errors = semantic_errors(
+        "func f(integer x) : integer { return (x); }
+        func f(float x) : float { return (x); }
+        main {
+          var {
+            integer i;
+          }
+          i = f(true);
+          i = f(1, 2);
+        }",
+    );
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::InvalidParameters(_))));
+}
+
+#[test]
+fn overloads_should_report_ambiguous_calls() {
+    init();
+
+    let errors = semantic_errors(
+        "func f(integer a[], integer b[3]) : void { }
+        func f(integer a[3], integer b[]) : void { }
+        func g(integer a[]) : void { }
+        func g(integer a[3]) : void { }
+        main {
+          var {
+            integer x[3];
+          }
+          f(x, x);
+          g(x);
+        }",
+    );
+
+    // an exact match beats an unsized array parameter, so only the call to f is ambiguous
+    assert!(
+        matches!(errors.as_slice(), [SemanticError::AmbiguousCall(_)]),
+        "{:?}",
+        errors
+    );
+}
+
+#[test]
+fn overloads_should_not_differ_only_by_return_type() {
+    init();
+
+    let errors = semantic_errors(
+        "func f(integer x) : integer { return (x); }
+        func f(integer x) : bool { return (true); }
+        main {
+        }",
+    );
+
+    assert!(
+        matches!(errors.as_slice(), [SemanticError::MultipleDeclIdent(_)]),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,69 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::semantics::checking::SemanticError;
+
+mod common;
+
+#[test]
+fn strings_should_support_concatenation_and_equality() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            string a;
+            string b;
+            integer i;
+          }
+          a = \"hello\";
+          b = a + \" \" + \"world\";
+          if (a == b) then { i = 1; } else { i = 0; };
+          if (a <> b) then { i = 1; } else ;
+          write(b);
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn strings_should_reject_other_operators() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            string a;
+            string b;
+            integer i;
+          }
+          b = a - \"x\";
+          b = a * a;
+          if (a < b) then { i = 1; } else ;
+          b = -a;
+        }",
+    );
+
+    assert_eq!(errors.len(), 4, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::TypeMistmatch(_))));
+}
+
+#[test]
+fn strings_should_not_mix_with_other_types() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            string a;
+            integer i;
+          }
+          a = a + 1;
+          i = a;
+        }",
+    );
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+}
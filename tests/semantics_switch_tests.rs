@@ -0,0 +1,157 @@
+extern crate comp442_compiler;
+use common::{init, semantic_errors};
+use comp442_compiler::semantics::checking::SemanticError;
+
+mod common;
+
+#[test]
+fn switch_should_accept_integer_and_constant_labels() {
+    init();
+
+    let errors = semantic_errors(
+        "const integer TWO = 2;
+        main {
+          var {
+            const integer FOUR = TWO * 2;
+            integer i;
+          }
+          switch (i + 1) {
+            case 1:
+              i = 0;
+            case TWO:
+            case -TWO:
+              break;
+            case FOUR + 1:
+              i = 5;
+              break;
+            default:
+              i = 9;
+          };
+        }",
+    );
+
+    assert!(errors.is_empty(), "{:?}", errors);
+}
+
+#[test]
+fn switch_should_reject_duplicate_cases() {
+    init();
+
+    let errors = semantic_errors(
+        "const integer TWO = 2;
+        func f(integer x) : integer {
+          switch (x) {
+            case 2:
+              x = 1;
+            case TWO:
+              x = 2;
+          };
+          return (x);
+        }
+        main {
+          var {
+            integer i;
+          }
+          switch (i) {
+            default:
+              i = 1;
+            case 1:
+              i = 2;
+            default:
+              i = 3;
+          };
+        }",
+    );
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::DuplicateCase(_))));
+}
+
+#[test]
+fn switch_should_reject_non_constant_labels() {
+    init();
+
+    let errors = semantic_errors(
+        "func f(integer x) : integer {
+          var {
+            integer y;
+          }
+          switch (x) {
+            case y:
+              x = 1;
+          };
+          return (x);
+        }
+        main {
+          var {
+            integer i;
+          }
+          switch (i) {
+            case f(1):
+              i = 2;
+          };
+        }",
+    );
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::InvalidConstant(_))));
+}
+
+#[test]
+fn switch_should_only_take_integers() {
+    init();
+
+    let errors = semantic_errors(
+        "const bool YES = true;
+        main {
+          var {
+            integer i;
+            float f;
+          }
+          switch (f) {
+            case 1:
+              i = 1;
+          };
+          switch (i) {
+            case YES:
+              i = 1;
+          };
+        }",
+    );
+
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, SemanticError::TypeMistmatch(_))));
+}
+
+#[test]
+fn continue_in_a_switch_should_need_a_loop() {
+    init();
+
+    let errors = semantic_errors(
+        "main {
+          var {
+            integer i;
+          }
+          while (i < 3) {
+            switch (i) {
+              case 0:
+                continue;
+            };
+            i = i + 1;
+          };
+          switch (i) {
+            case 3:
+              continue;
+          };
+        }",
+    );
+
+    assert_eq!(errors.len(), 1, "{:?}", errors);
+    assert!(matches!(errors[0], SemanticError::NotInLoop(_)));
+}